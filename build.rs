@@ -0,0 +1,49 @@
+//! Capture build metadata for `clack version`.
+//!
+//! Exposed to the crate as compile-time env vars: CLACK_GIT_SHA, CLACK_BUILD_TIMESTAMP
+//! (unix seconds, honouring SOURCE_DATE_EPOCH for reproducible builds), CLACK_RUSTC_VERSION
+//! and CLACK_FEATURES (comma-separated). Anything that can't be determined is "unknown".
+
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    // Re-run when the checked-out commit moves
+    for path in [".git/HEAD", ".git/refs/heads"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+
+    let git_sha = command_output("git", &["rev-parse", "--short=12", "HEAD"]).unwrap_or_else(|| "unknown".to_string());
+
+    let timestamp = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0));
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = command_output(&rustc, &["--version"]).unwrap_or_else(|| "unknown".to_string());
+
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(|f| f.to_lowercase().replace('_', "-")))
+        .collect();
+    features.sort();
+
+    println!("cargo:rustc-env=CLACK_GIT_SHA={}", git_sha);
+    println!("cargo:rustc-env=CLACK_BUILD_TIMESTAMP={}", timestamp);
+    println!("cargo:rustc-env=CLACK_RUSTC_VERSION={}", rustc_version);
+    println!("cargo:rustc-env=CLACK_FEATURES={}", features.join(","));
+}
+
+/// Trimmed stdout of a successful command, `None` if it can't run or fails
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
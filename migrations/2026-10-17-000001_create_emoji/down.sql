@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_emoji_workspace_id;
+DROP TABLE IF EXISTS emoji;
@@ -0,0 +1,13 @@
+-- Emoji table: custom emoji (URL or alias:name) and standard names (empty value)
+CREATE TABLE emoji (
+    name TEXT NOT NULL,
+    workspace_id TEXT NOT NULL,
+    value TEXT NOT NULL,
+
+    -- Cache metadata
+    cached_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (name, workspace_id)
+);
+
+CREATE INDEX idx_emoji_workspace_id ON emoji(workspace_id);
@@ -0,0 +1,9 @@
+-- Last fetch table: newest message ts seen per channel, for --since-last-run
+CREATE TABLE last_fetch (
+    workspace_id TEXT NOT NULL,
+    conversation_id TEXT NOT NULL,
+    last_ts TEXT NOT NULL,
+    fetched_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (workspace_id, conversation_id)
+);
@@ -0,0 +1,85 @@
+use anyhow::Result;
+
+/// Tracks individual lookup failures in multi-item commands.
+///
+/// With `--fail-fast` the first failure aborts the command; otherwise
+/// (`--continue`, the default) failures are counted and reported as a
+/// warning on stderr once the command finishes.
+#[derive(Debug, Default)]
+pub struct LookupFailures {
+    fail_fast: bool,
+    failures: Vec<String>,
+}
+
+impl LookupFailures {
+    pub fn new(fail_fast: bool) -> Self {
+        Self {
+            fail_fast,
+            failures: Vec::new(),
+        }
+    }
+
+    /// Unwrap one lookup result. `what` names the item, e.g. "user U123".
+    ///
+    /// Returns the error under `--fail-fast`; otherwise records it and yields `None`.
+    pub fn check<T>(&mut self, what: &str, result: Result<T>) -> Result<Option<T>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if self.fail_fast => Err(e.context(format!("Lookup of {} failed (--fail-fast)", what))),
+            Err(e) => {
+                self.failures.push(format!("{}: {}", what, e));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Recorded failures, each as "<what>: <error>"
+    pub fn failures(&self) -> &[String] {
+        &self.failures
+    }
+
+    /// Warning line for stderr, or `None` when every lookup succeeded
+    pub fn warning(&self) -> Option<String> {
+        let first = self.failures.first()?;
+        let more = match self.failures.len() {
+            1 => String::new(),
+            n => format!(" (and {} more)", n - 1),
+        };
+        Some(format!(
+            "Warning: {} lookup{} failed; output may show raw IDs. First failure: {}{}. Use --fail-fast to stop on the first error",
+            self.failures.len(),
+            if self.failures.len() == 1 { "" } else { "s" },
+            first,
+            more
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_continue_counts_failures() {
+        let mut failures = LookupFailures::new(false);
+        assert_eq!(failures.check("user U1", Ok(1)).unwrap(), Some(1));
+        assert_eq!(failures.check::<i32>("user U2", Err(anyhow::anyhow!("missing_scope"))).unwrap(), None);
+        assert_eq!(failures.check::<i32>("user U3", Err(anyhow::anyhow!("user_not_found"))).unwrap(), None);
+        assert_eq!(failures.failures().len(), 2);
+
+        let warning = failures.warning().unwrap();
+        assert!(warning.contains("2 lookups failed"));
+        assert!(warning.contains("user U2: missing_scope (and 1 more)"));
+    }
+
+    #[test]
+    fn test_fail_fast_returns_first_error() {
+        let mut failures = LookupFailures::new(true);
+        let err = failures
+            .check::<i32>("user U2", Err(anyhow::anyhow!("missing_scope")))
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("user U2"));
+        assert!(format!("{:#}", err).contains("missing_scope"));
+        assert!(LookupFailures::new(true).warning().is_none());
+    }
+}
@@ -1,10 +1,14 @@
 pub mod auth;
+pub mod bookmarks;
 pub mod channels;
 pub mod chat;
 pub mod client;
+pub mod emoji;
+pub mod failures;
 pub mod files;
 pub mod messages;
 pub mod pins;
 pub mod reactions;
+pub mod recording;
 pub mod search;
 pub mod users;
@@ -0,0 +1,217 @@
+//! Optional application-level encryption for cached payloads.
+//!
+//! When `CLACK_CACHE_KEY` is set, free-text columns (`full_object`, message
+//! `text`, emails, topics) are sealed with AES-256-GCM under a key derived from
+//! the passphrase. Names and IDs stay in plaintext because lookups query them.
+//!
+//! The PBKDF2 salt is random per install, kept in `cache.salt` next to the
+//! database, so the same passphrase gives different keys on different machines.
+//!
+//! Changing or removing the key (or the salt file) makes existing encrypted rows
+//! unreadable; they are treated as cache misses and overwritten on the next fetch.
+
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::io::Write;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub const CACHE_KEY_ENV: &str = "CLACK_CACHE_KEY";
+
+// Marks a sealed value so plaintext rows from before encryption still load
+const SEALED_PREFIX: &str = "enc:v1:";
+const KDF_ITERATIONS: u32 = 100_000;
+
+/// File next to `cache.db` holding this install's KDF salt
+pub const SALT_FILE: &str = "cache.salt";
+const SALT_LEN: usize = 16;
+
+pub struct CacheCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl CacheCipher {
+    /// Derive an AES-256 key from a passphrase and salt with PBKDF2-HMAC-SHA256
+    pub fn from_passphrase(passphrase: &str, salt: &[u8]) -> Self {
+        let mut key_bytes = [0u8; 32];
+        ring::pbkdf2::derive(
+            ring::pbkdf2::PBKDF2_HMAC_SHA256,
+            NonZeroU32::new(KDF_ITERATIONS).unwrap(),
+            salt,
+            passphrase.as_bytes(),
+            &mut key_bytes,
+        );
+
+        let unbound = UnboundKey::new(&AES_256_GCM, &key_bytes).expect("AES-256 key is 32 bytes");
+        Self {
+            key: LessSafeKey::new(unbound),
+            rng: SystemRandom::new(),
+        }
+    }
+
+    /// Encrypt a value, returning a prefixed base64 string of nonce + ciphertext
+    pub fn seal(&self, plaintext: &str) -> Result<String> {
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce_bytes)
+            .map_err(|_| anyhow::anyhow!("Failed to generate nonce"))?;
+
+        let mut buffer = plaintext.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce_bytes), Aad::empty(), &mut buffer)
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt cache value"))?;
+
+        let mut payload = nonce_bytes.to_vec();
+        payload.extend_from_slice(&buffer);
+        Ok(format!("{}{}", SEALED_PREFIX, BASE64.encode(payload)))
+    }
+
+    /// Decrypt a value produced by `seal`; plaintext values pass through unchanged
+    pub fn open(&self, stored: &str) -> Result<String> {
+        let Some(encoded) = stored.strip_prefix(SEALED_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+
+        let mut payload = BASE64.decode(encoded)?;
+        if payload.len() < NONCE_LEN {
+            anyhow::bail!("Encrypted cache value is truncated");
+        }
+
+        let mut ciphertext = payload.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&payload)
+            .map_err(|_| anyhow::anyhow!("Invalid nonce in encrypted cache value"))?;
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::empty(), &mut ciphertext)
+            .map_err(|_| anyhow::anyhow!("Failed to decrypt cache value (was {} changed?)", CACHE_KEY_ENV))?;
+
+        Ok(String::from_utf8(plaintext.to_vec())?)
+    }
+}
+
+static CIPHER: OnceLock<Option<CacheCipher>> = OnceLock::new();
+
+/// `CLACK_CACHE_KEY`, if set to something non-empty
+fn passphrase() -> Option<String> {
+    std::env::var(CACHE_KEY_ENV).ok().filter(|key| !key.is_empty())
+}
+
+/// Read this install's KDF salt from `dir`, creating a random one on first use
+pub fn load_or_create_salt(dir: &Path) -> Result<Vec<u8>> {
+    let path = dir.join(SALT_FILE);
+    match std::fs::read(&path) {
+        Ok(salt) if salt.len() == SALT_LEN => return Ok(salt),
+        Ok(_) => anyhow::bail!(
+            "{} is not a {}-byte salt; delete it to start a new encrypted cache",
+            path.display(),
+            SALT_LEN
+        ),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+
+    let mut salt = vec![0u8; SALT_LEN];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| anyhow::anyhow!("Failed to generate cache key salt"))?;
+    match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(mut file) => {
+            file.write_all(&salt)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            Ok(salt)
+        }
+        // Another clack process created it first; use theirs
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => load_or_create_salt(dir),
+        Err(e) => Err(e).with_context(|| format!("Failed to create {}", path.display())),
+    }
+}
+
+/// Set up the process-wide cipher for the cache in `dir` if `CLACK_CACHE_KEY` is set.
+/// A process only ever uses one cache, so the first call wins.
+pub fn init(dir: &Path) -> Result<()> {
+    if CIPHER.get().is_some() {
+        return Ok(());
+    }
+    let cipher = match passphrase() {
+        Some(key) => Some(CacheCipher::from_passphrase(&key, &load_or_create_salt(dir)?)),
+        None => None,
+    };
+    let _ = CIPHER.set(cipher);
+    Ok(())
+}
+
+/// The process-wide cipher, if `CLACK_CACHE_KEY` is set and [`init`] has run
+pub fn cipher() -> Option<&'static CacheCipher> {
+    CIPHER.get().and_then(Option::as_ref)
+}
+
+/// Encrypt a value for storage if a cache key is configured.
+///
+/// Errors rather than storing plaintext when the key is set but sealing fails.
+pub fn seal(value: &str) -> Result<String> {
+    match cipher() {
+        Some(cipher) => cipher.seal(value),
+        None if passphrase().is_some() => {
+            anyhow::bail!("{} is set but cache encryption was not initialized", CACHE_KEY_ENV)
+        }
+        None => Ok(value.to_string()),
+    }
+}
+
+/// Decrypt a stored value; errors if it is encrypted and no key (or the wrong key) is set
+pub fn open(value: &str) -> Result<String> {
+    match cipher() {
+        Some(cipher) => cipher.open(value),
+        None if value.starts_with(SEALED_PREFIX) => {
+            anyhow::bail!("Cache value is encrypted but {} is not set", CACHE_KEY_ENV)
+        }
+        None => Ok(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let cipher = CacheCipher::from_passphrase("correct horse", b"salt");
+        let sealed = cipher.seal(r#"{"text":"hello"}"#).unwrap();
+
+        assert!(sealed.starts_with(SEALED_PREFIX));
+        assert!(!sealed.contains("hello"));
+        assert_eq!(cipher.open(&sealed).unwrap(), r#"{"text":"hello"}"#);
+    }
+
+    #[test]
+    fn test_open_with_wrong_key_fails() {
+        let sealed = CacheCipher::from_passphrase("one", b"salt").seal("secret").unwrap();
+        assert!(CacheCipher::from_passphrase("two", b"salt").open(&sealed).is_err());
+        // Same passphrase, another install's salt
+        assert!(CacheCipher::from_passphrase("one", b"other").open(&sealed).is_err());
+    }
+
+    #[test]
+    fn test_salt_is_created_once_per_install() {
+        let (a, b) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+
+        let salt = load_or_create_salt(a.path()).unwrap();
+        assert_eq!(salt.len(), SALT_LEN);
+        assert_eq!(load_or_create_salt(a.path()).unwrap(), salt);
+        assert_ne!(load_or_create_salt(b.path()).unwrap(), salt);
+
+        std::fs::write(a.path().join(SALT_FILE), b"short").unwrap();
+        assert!(load_or_create_salt(a.path()).is_err());
+    }
+
+    #[test]
+    fn test_open_passes_plaintext_through() {
+        let cipher = CacheCipher::from_passphrase("key", b"salt");
+        assert_eq!(cipher.open("plain value").unwrap(), "plain value");
+    }
+}
@@ -2,9 +2,10 @@ use anyhow::{Context, Result};
 use diesel::prelude::*;
 use diesel::sqlite::SqliteConnection;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use tracing::debug;
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
 
@@ -13,38 +14,44 @@ pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
 pub type CachePool = Arc<Mutex<String>>; // Stores the DB path
 pub type CacheConnection = SqliteConnection;
 
-/// Get platform-specific cache directory
-pub fn get_cache_dir() -> Result<PathBuf> {
-    let cache_dir = dirs::cache_dir()
-        .context("Failed to determine cache directory for this platform")?;
-
-    let clack_cache = cache_dir.join("clack");
+/// Get the cache directory: `override_dir` (`--cache-dir`/`CLACK_CACHE_DIR`) when given,
+/// otherwise the platform cache directory + `clack`. Created if missing.
+pub fn get_cache_dir(override_dir: Option<&Path>) -> Result<PathBuf> {
+    let clack_cache = match override_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => dirs::cache_dir()
+            .context("Failed to determine cache directory for this platform")?
+            .join("clack"),
+    };
 
     // Create directory if it doesn't exist
     std::fs::create_dir_all(&clack_cache)
-        .context("Failed to create clack cache directory")?;
+        .with_context(|| format!("Failed to create clack cache directory {}", clack_cache.display()))?;
 
     Ok(clack_cache)
 }
 
 /// Get full path to cache database file
-pub fn get_cache_db_path() -> Result<PathBuf> {
-    let cache_dir = get_cache_dir()?;
+pub fn get_cache_db_path(override_dir: Option<&Path>) -> Result<PathBuf> {
+    let cache_dir = get_cache_dir(override_dir)?;
     Ok(cache_dir.join("cache.db"))
 }
 
 /// Initialize the cache database and run migrations
-pub fn init_cache_db(verbose: bool) -> Result<()> {
-    let db_path = get_cache_db_path()?;
-    init_cache_db_at_path(&db_path, verbose)
+pub fn init_cache_db(override_dir: Option<&Path>) -> Result<()> {
+    let db_path = get_cache_db_path(override_dir)?;
+    init_cache_db_at_path(&db_path)
 }
 
 /// Initialize cache database at a specific path (for testing)
-pub fn init_cache_db_at_path(db_path: &PathBuf, verbose: bool) -> Result<()> {
+pub fn init_cache_db_at_path(db_path: &Path) -> Result<()> {
     let db_url = format!("sqlite://{}", db_path.display());
 
-    if verbose {
-        eprintln!("Initializing cache database at: {}", db_path.display());
+    debug!(path = %db_path.display(), "initializing cache database");
+
+    // The key's salt lives next to the database
+    if let Some(dir) = db_path.parent() {
+        super::crypto::init(dir)?;
     }
 
     // Create synchronous connection for migrations
@@ -65,9 +72,7 @@ pub fn init_cache_db_at_path(db_path: &PathBuf, verbose: bool) -> Result<()> {
     conn.run_pending_migrations(MIGRATIONS)
         .map_err(|e| anyhow::anyhow!("Failed to run migrations: {}", e))?;
 
-    if verbose {
-        eprintln!("Cache database initialized successfully");
-    }
+    debug!(encrypted = super::crypto::cipher().is_some(), "cache database initialized");
 
     Ok(())
 }
@@ -75,16 +80,14 @@ pub fn init_cache_db_at_path(db_path: &PathBuf, verbose: bool) -> Result<()> {
 /// Create a connection pool for the cache database
 /// For SQLite with async-connection-wrapper, this stores the DB URL
 /// Actual connections are created on demand
-pub async fn create_cache_pool(verbose: bool) -> Result<CachePool> {
+pub async fn create_cache_pool(override_dir: Option<&Path>) -> Result<CachePool> {
     // Initialize database and run migrations
-    init_cache_db(verbose)?;
+    init_cache_db(override_dir)?;
 
-    let db_path = get_cache_db_path()?;
+    let db_path = get_cache_db_path(override_dir)?;
     let db_url = format!("sqlite://{}", db_path.display());
 
-    if verbose {
-        eprintln!("Cache database ready at: {}", db_url);
-    }
+    debug!(url = %db_url, "cache database ready");
 
     Ok(Arc::new(Mutex::new(db_url)))
 }
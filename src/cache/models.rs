@@ -1,7 +1,30 @@
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
 
-use super::schema::{conversations, messages, users};
+use super::crypto;
+use super::schema::{conversations, emoji, last_fetch, messages, users};
+
+#[derive(Debug, Queryable, Selectable, Insertable)]
+#[diesel(table_name = emoji)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct CachedEmoji {
+    pub name: String,
+    pub workspace_id: String,
+    /// Image URL, `alias:<name>`, or empty for standard emoji
+    pub value: String,
+    pub cached_at: NaiveDateTime,
+}
+
+/// Newest message ts seen for a channel; not cache data, so never expires or gets cleared
+#[derive(Debug, Queryable, Selectable, Insertable)]
+#[diesel(table_name = last_fetch)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct LastFetch {
+    pub workspace_id: String,
+    pub conversation_id: String,
+    pub last_ts: String,
+    pub fetched_at: NaiveDateTime,
+}
 
 #[derive(Debug, Queryable, Selectable, Insertable)]
 #[diesel(table_name = users)]
@@ -80,8 +103,8 @@ pub struct CachedMessage {
 
 // Helper functions to convert between API models and cache models
 impl CachedUser {
-    pub fn from_api_user(user: &crate::models::user::User, workspace_id: &str) -> Self {
-        Self {
+    pub fn from_api_user(user: &crate::models::user::User, workspace_id: &str) -> anyhow::Result<Self> {
+        Ok(Self {
             id: user.id.clone(),
             workspace_id: workspace_id.to_string(),
             name: user.name.clone(),
@@ -91,26 +114,28 @@ impl CachedUser {
             is_admin: user.is_admin,
             is_owner: user.is_owner,
             tz: user.tz.clone(),
-            profile_email: user.profile.email.clone(),
+            profile_email: user.profile.email.as_deref().map(crypto::seal).transpose()?,
             profile_display_name: user.profile.display_name.clone(),
             profile_status_emoji: user.profile.status_emoji.clone(),
-            profile_status_text: user.profile.status_text.clone(),
+            profile_status_text: user.profile.status_text.as_deref().map(crypto::seal).transpose()?,
             profile_image_72: user.profile.image_72.clone(),
-            full_object: serde_json::to_string(user).unwrap_or_default(),
+            full_object: crypto::seal(&serde_json::to_string(user)?)?,
             cached_at: chrono::Utc::now().naive_utc(),
             deleted_at: None,
-        }
+        })
     }
 
     pub fn to_api_user(&self) -> anyhow::Result<crate::models::user::User> {
-        serde_json::from_str(&self.full_object)
+        serde_json::from_str(&crypto::open(&self.full_object)?)
             .map_err(|e| anyhow::anyhow!("Failed to deserialize cached user: {}", e))
     }
 }
 
 impl CachedConversation {
-    pub fn from_api_channel(channel: &crate::models::channel::Channel, workspace_id: &str) -> Self {
-        Self {
+    pub fn from_api_channel(channel: &crate::models::channel::Channel, workspace_id: &str) -> anyhow::Result<Self> {
+        let topic = channel.topic.as_ref().map_or((None, None), |t| t.set_by());
+        let purpose = channel.purpose.as_ref().map_or((None, None), |p| p.set_by());
+        Ok(Self {
             id: channel.id.clone(),
             workspace_id: workspace_id.to_string(),
             name: channel.name.clone(),
@@ -120,43 +145,47 @@ impl CachedConversation {
             is_mpim: channel.is_mpim,
             is_private: channel.is_private,
             is_archived: channel.is_archived.unwrap_or(false),
-            topic_value: channel.topic.as_ref().map(|t| t.value.clone()),
-            topic_creator: None,
-            topic_last_set: None,
-            purpose_value: channel.purpose.as_ref().map(|p| p.value.clone()),
-            purpose_creator: None,
-            purpose_last_set: None,
+            topic_value: channel.topic.as_ref().map(|t| crypto::seal(&t.value)).transpose()?,
+            topic_creator: topic.0.map(str::to_string),
+            topic_last_set: topic.1.map(|t| t as i32),
+            purpose_value: channel.purpose.as_ref().map(|p| crypto::seal(&p.value)).transpose()?,
+            purpose_creator: purpose.0.map(str::to_string),
+            purpose_last_set: purpose.1.map(|t| t as i32),
             num_members: channel.num_members.map(|n| n as i32),
-            full_object: serde_json::to_string(channel).unwrap_or_default(),
+            full_object: crypto::seal(&serde_json::to_string(channel)?)?,
             cached_at: chrono::Utc::now().naive_utc(),
             deleted_at: None,
-        }
+        })
     }
 
     pub fn to_api_channel(&self) -> anyhow::Result<crate::models::channel::Channel> {
-        serde_json::from_str(&self.full_object)
+        serde_json::from_str(&crypto::open(&self.full_object)?)
             .map_err(|e| anyhow::anyhow!("Failed to deserialize cached conversation: {}", e))
     }
 }
 
 impl CachedMessage {
-    pub fn from_api_message(message: &crate::models::message::Message, conversation_id: &str, workspace_id: &str) -> Self {
-        Self {
+    pub fn from_api_message(
+        message: &crate::models::message::Message,
+        conversation_id: &str,
+        workspace_id: &str,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
             conversation_id: conversation_id.to_string(),
             workspace_id: workspace_id.to_string(),
             ts: message.ts.clone(),
             user_id: message.user.clone(),
-            text: message.text.clone(),
+            text: crypto::seal(&message.text)?,
             thread_ts: message.thread_ts.clone(),
             permalink: message.permalink.clone(),
-            full_object: serde_json::to_string(message).unwrap_or_default(),
+            full_object: crypto::seal(&serde_json::to_string(message)?)?,
             cached_at: chrono::Utc::now().naive_utc(),
             deleted_at: None,
-        }
+        })
     }
 
     pub fn to_api_message(&self) -> anyhow::Result<crate::models::message::Message> {
-        serde_json::from_str(&self.full_object)
+        serde_json::from_str(&crypto::open(&self.full_object)?)
             .map_err(|e| anyhow::anyhow!("Failed to deserialize cached message: {}", e))
     }
 }
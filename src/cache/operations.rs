@@ -1,18 +1,22 @@
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{NaiveDateTime, Utc};
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, trace};
 
 use super::db::CacheConnection;
-use super::models::{CachedConversation, CachedMessage, CachedUser};
-use super::schema::{conversations, messages, users};
+use super::models::{CachedConversation, CachedEmoji, CachedMessage, CachedUser, LastFetch};
+use super::schema::{conversations, emoji, last_fetch, messages, users};
 use crate::models::channel::Channel;
-use crate::models::message::Message;
+use crate::models::message::{parse_ts, Message};
 use crate::models::user::User;
 
 // TTL constants (in seconds)
 const USER_TTL_SECONDS: i64 = 3600 * 24 * 7; // 1 week
 const CONVERSATION_TTL_SECONDS: i64 = 3600 * 24 * 7; // 1 week
 const MESSAGE_TTL_SECONDS: i64 = 3600 * 24 * 7; // 1 week
+const EMOJI_TTL_SECONDS: i64 = 3600 * 24; // 1 day
 
 /// Check if a cached item is fresh based on TTL
 fn is_fresh(cached_at: chrono::NaiveDateTime, ttl_seconds: i64) -> bool {
@@ -32,7 +36,6 @@ pub fn get_user(
     conn: &mut CacheConnection,
     ws_id: &str,
     user_id: &str,
-    verbose: bool,
     ttl_override: Option<i64>,
 ) -> Result<Option<User>> {
     use super::schema::users::dsl::*;
@@ -49,21 +52,15 @@ pub fn get_user(
     match cached_user {
         Some(cached) => {
             if is_fresh(cached.cached_at, ttl) {
-                if verbose {
-                    eprintln!("[CACHE] User {} - HIT (fresh)", user_id);
-                }
+                debug!(entity = "user", id = %user_id, outcome = "hit", "cache lookup");
                 Ok(Some(cached.to_api_user()?))
             } else {
-                if verbose {
-                    eprintln!("[CACHE] User {} - MISS (stale)", user_id);
-                }
+                debug!(entity = "user", id = %user_id, outcome = "stale", "cache lookup");
                 Ok(None)
             }
         }
         None => {
-            if verbose {
-                eprintln!("[CACHE] User {} - MISS (not found)", user_id);
-            }
+            debug!(entity = "user", id = %user_id, outcome = "miss", "cache lookup");
             Ok(None)
         }
     }
@@ -77,7 +74,6 @@ pub fn get_user(
 pub fn get_users(
     conn: &mut CacheConnection,
     ws_id: &str,
-    verbose: bool,
     ttl_override: Option<i64>,
 ) -> Result<Option<Vec<User>>> {
     use super::schema::users::dsl::*;
@@ -88,9 +84,7 @@ pub fn get_users(
         .load(conn)?;
 
     if cached_users.is_empty() {
-        if verbose {
-            eprintln!("[CACHE] Users - MISS (empty)");
-        }
+        debug!(entity = "users", outcome = "miss", "cache lookup");
         return Ok(None);
     }
 
@@ -100,15 +94,11 @@ pub fn get_users(
     let all_fresh = cached_users.iter().all(|u| is_fresh(u.cached_at, ttl));
 
     if all_fresh {
-        if verbose {
-            eprintln!("[CACHE] Users - HIT ({} users)", cached_users.len());
-        }
+        debug!(entity = "users", count = cached_users.len(), outcome = "hit", "cache lookup");
         let api_users: Result<Vec<User>> = cached_users.iter().map(|u| u.to_api_user()).collect();
         Ok(Some(api_users?))
     } else {
-        if verbose {
-            eprintln!("[CACHE] Users - MISS (some stale)");
-        }
+        debug!(entity = "users", outcome = "stale", "cache lookup");
         Ok(None)
     }
 }
@@ -129,7 +119,6 @@ pub fn get_user_by_name(
     conn: &mut CacheConnection,
     ws_id: &str,
     user_name: &str,
-    verbose: bool,
     ttl_override: Option<i64>,
 ) -> Result<Vec<User>> {
     use super::schema::users::dsl::*;
@@ -159,16 +148,13 @@ pub fn get_user_by_name(
         .filter_map(|u| u.to_api_user().ok())
         .collect();
 
-    if verbose {
-        match matching_users.len() {
-            0 => eprintln!("[CACHE] User '{}' - MISS (not found)", user_name),
-            1 => eprintln!(
-                "[CACHE] User '{}' - HIT (ID: {})",
-                user_name, matching_users[0].id
-            ),
-            n => eprintln!("[CACHE] User '{}' - HIT ({} matches)", user_name, n),
-        }
-    }
+    debug!(
+        entity = "user",
+        name = %user_name,
+        matches = matching_users.len(),
+        outcome = if matching_users.is_empty() { "miss" } else { "hit" },
+        "cache lookup"
+    );
 
     Ok(matching_users)
 }
@@ -177,18 +163,15 @@ pub fn upsert_user(
     conn: &mut CacheConnection,
     workspace_id: &str,
     user: &User,
-    verbose: bool,
 ) -> Result<()> {
-    let cached = CachedUser::from_api_user(user, workspace_id);
+    let cached = CachedUser::from_api_user(user, workspace_id)?;
 
     diesel::replace_into(users::table)
         .values(&cached)
         .execute(conn)
         ?;
 
-    if verbose {
-        eprintln!("[CACHE] User {} - UPSERTED", user.id);
-    }
+    debug!(entity = "user", id = %user.id, "cache upsert");
 
     Ok(())
 }
@@ -197,12 +180,11 @@ pub fn upsert_users(
     conn: &mut CacheConnection,
     workspace_id: &str,
     user_list: &[User],
-    verbose: bool,
 ) -> Result<()> {
     let cached_users: Vec<CachedUser> = user_list
         .iter()
         .map(|u| CachedUser::from_api_user(u, workspace_id))
-        .collect();
+        .collect::<Result<_>>()?;
 
     for cached in cached_users {
         diesel::replace_into(users::table)
@@ -211,9 +193,7 @@ pub fn upsert_users(
             ?;
     }
 
-    if verbose {
-        eprintln!("[CACHE] Users - UPSERTED {} users", user_list.len());
-    }
+    debug!(entity = "users", count = user_list.len(), "cache upsert");
 
     Ok(())
 }
@@ -229,7 +209,6 @@ pub fn get_conversation(
     conn: &mut CacheConnection,
     ws_id: &str,
     conversation_id: &str,
-    verbose: bool,
     ttl_override: Option<i64>,
 ) -> Result<Option<Channel>> {
     use super::schema::conversations::dsl::*;
@@ -246,21 +225,15 @@ pub fn get_conversation(
     match cached_conv {
         Some(cached) => {
             if is_fresh(cached.cached_at, ttl) {
-                if verbose {
-                    eprintln!("[CACHE] Conversation {} - HIT (fresh)", conversation_id);
-                }
+                debug!(entity = "conversation", id = %conversation_id, outcome = "hit", "cache lookup");
                 Ok(Some(cached.to_api_channel()?))
             } else {
-                if verbose {
-                    eprintln!("[CACHE] Conversation {} - MISS (stale)", conversation_id);
-                }
+                debug!(entity = "conversation", id = %conversation_id, outcome = "stale", "cache lookup");
                 Ok(None)
             }
         }
         None => {
-            if verbose {
-                eprintln!("[CACHE] Conversation {} - MISS (not found)", conversation_id);
-            }
+            debug!(entity = "conversation", id = %conversation_id, outcome = "miss", "cache lookup");
             Ok(None)
         }
     }
@@ -283,7 +256,6 @@ pub fn get_conversation_by_name(
     conn: &mut CacheConnection,
     ws_id: &str,
     conv_name: &str,
-    verbose: bool,
     ttl_override: Option<i64>,
 ) -> Result<Option<Channel>> {
     use super::schema::conversations::dsl::*;
@@ -303,29 +275,41 @@ pub fn get_conversation_by_name(
     match cached_conv {
         Some(cached) => {
             if is_fresh(cached.cached_at, ttl) {
-                if verbose {
-                    eprintln!(
-                        "[CACHE] Conversation '{}' - HIT (fresh, ID: {})",
-                        conv_name, cached.id
-                    );
-                }
+                debug!(entity = "conversation", name = %conv_name, id = %cached.id, outcome = "hit", "cache lookup");
                 Ok(Some(cached.to_api_channel()?))
             } else {
-                if verbose {
-                    eprintln!("[CACHE] Conversation '{}' - MISS (stale)", conv_name);
-                }
+                debug!(entity = "conversation", name = %conv_name, outcome = "stale", "cache lookup");
                 Ok(None)
             }
         }
         None => {
-            if verbose {
-                eprintln!("[CACHE] Conversation '{}' - MISS (not found)", conv_name);
-            }
+            debug!(entity = "conversation", name = %conv_name, outcome = "miss", "cache lookup");
             Ok(None)
         }
     }
 }
 
+/// Get the cached DM with a user, whatever its age (a DM's ID never changes).
+pub fn get_im_for_user(conn: &mut CacheConnection, ws_id: &str, user_id: &str) -> Result<Option<Channel>> {
+    use super::schema::conversations::dsl::*;
+
+    let cached_ims: Vec<CachedConversation> = conversations
+        .filter(workspace_id.eq(ws_id))
+        .filter(is_im.eq(true))
+        .filter(deleted_at.is_null())
+        .load(conn)?;
+
+    for cached in cached_ims {
+        let channel = cached.to_api_channel()?;
+        if channel.user.as_deref() == Some(user_id) {
+            debug!(entity = "im", user = %user_id, id = %channel.id, outcome = "hit", "cache lookup");
+            return Ok(Some(channel));
+        }
+    }
+    debug!(entity = "im", user = %user_id, outcome = "miss", "cache lookup");
+    Ok(None)
+}
+
 /// Get all conversations from cache for a workspace.
 ///
 /// # Arguments
@@ -334,7 +318,6 @@ pub fn get_conversation_by_name(
 pub fn get_conversations(
     conn: &mut CacheConnection,
     ws_id: &str,
-    verbose: bool,
     ttl_override: Option<i64>,
 ) -> Result<Option<Vec<Channel>>> {
     use super::schema::conversations::dsl::*;
@@ -345,9 +328,7 @@ pub fn get_conversations(
         .load(conn)?;
 
     if cached_convs.is_empty() {
-        if verbose {
-            eprintln!("[CACHE] Conversations - MISS (empty)");
-        }
+        debug!(entity = "conversations", outcome = "miss", "cache lookup");
         return Ok(None);
     }
 
@@ -356,19 +337,12 @@ pub fn get_conversations(
     let all_fresh = cached_convs.iter().all(|c| is_fresh(c.cached_at, ttl));
 
     if all_fresh {
-        if verbose {
-            eprintln!(
-                "[CACHE] Conversations - HIT ({} conversations)",
-                cached_convs.len()
-            );
-        }
+        debug!(entity = "conversations", count = cached_convs.len(), outcome = "hit", "cache lookup");
         let api_channels: Result<Vec<Channel>> =
             cached_convs.iter().map(|c| c.to_api_channel()).collect();
         Ok(Some(api_channels?))
     } else {
-        if verbose {
-            eprintln!("[CACHE] Conversations - MISS (some stale)");
-        }
+        debug!(entity = "conversations", outcome = "stale", "cache lookup");
         Ok(None)
     }
 }
@@ -377,18 +351,15 @@ pub fn upsert_conversation(
     conn: &mut CacheConnection,
     workspace_id: &str,
     channel: &Channel,
-    verbose: bool,
 ) -> Result<()> {
-    let cached = CachedConversation::from_api_channel(channel, workspace_id);
+    let cached = CachedConversation::from_api_channel(channel, workspace_id)?;
 
     diesel::replace_into(conversations::table)
         .values(&cached)
         .execute(conn)
         ?;
 
-    if verbose {
-        eprintln!("[CACHE] Conversation #{} ({}) - UPSERTED", channel.name, channel.id);
-    }
+    trace!(entity = "conversation", id = %channel.id, name = %channel.name, "cache upsert");
 
     Ok(())
 }
@@ -397,23 +368,18 @@ pub fn upsert_conversations(
     conn: &mut CacheConnection,
     workspace_id: &str,
     channel_list: &[Channel],
-    verbose: bool,
 ) -> Result<()> {
     for channel in channel_list {
-        let cached = CachedConversation::from_api_channel(channel, workspace_id);
+        let cached = CachedConversation::from_api_channel(channel, workspace_id)?;
         diesel::replace_into(conversations::table)
             .values(&cached)
             .execute(conn)
             ?;
 
-        if verbose {
-            eprintln!("[CACHE] Conversation #{} ({}) - UPSERTED", channel.name, channel.id);
-        }
+        trace!(entity = "conversation", id = %channel.id, name = %channel.name, "cache upsert");
     }
 
-    if verbose {
-        eprintln!("[CACHE] Conversations - UPSERTED {} conversations total", channel_list.len());
-    }
+    debug!(entity = "conversations", count = channel_list.len(), "cache upsert");
 
     Ok(())
 }
@@ -424,7 +390,6 @@ pub fn get_messages(
     conn: &mut CacheConnection,
     ws_id: &str,
     conv_id: &str,
-    verbose: bool,
 ) -> Result<Option<Vec<Message>>> {
     use super::schema::messages::dsl::*;
 
@@ -436,9 +401,7 @@ pub fn get_messages(
         ?;
 
     if cached_msgs.is_empty() {
-        if verbose {
-            eprintln!("[CACHE] Messages (conv {}) - MISS (empty)", conv_id);
-        }
+        debug!(entity = "messages", conversation = %conv_id, outcome = "miss", "cache lookup");
         return Ok(None);
     }
 
@@ -447,18 +410,14 @@ pub fn get_messages(
         .all(|m| is_fresh(m.cached_at, MESSAGE_TTL_SECONDS));
 
     if all_fresh {
-        if verbose {
-            eprintln!("[CACHE] Messages (conv {}) - HIT ({} messages)", conv_id, cached_msgs.len());
-        }
+        debug!(entity = "messages", conversation = %conv_id, count = cached_msgs.len(), outcome = "hit", "cache lookup");
         let api_messages: Result<Vec<Message>> = cached_msgs
             .iter()
             .map(|m| m.to_api_message())
             .collect();
         Ok(Some(api_messages?))
     } else {
-        if verbose {
-            eprintln!("[CACHE] Messages (conv {}) - MISS (some stale)", conv_id);
-        }
+        debug!(entity = "messages", conversation = %conv_id, outcome = "stale", "cache lookup");
         Ok(None)
     }
 }
@@ -468,20 +427,183 @@ pub fn upsert_messages(
     workspace_id: &str,
     conv_id: &str,
     message_list: &[Message],
-    verbose: bool,
 ) -> Result<()> {
     for message in message_list {
-        let cached = CachedMessage::from_api_message(message, conv_id, workspace_id);
+        let mut cached = CachedMessage::from_api_message(message, conv_id, workspace_id)?;
+        // History payloads carry no permalink; keep one backfilled earlier
+        if cached.permalink.is_none() {
+            cached.permalink = messages::table
+                .find((conv_id, workspace_id, &message.ts))
+                .select(messages::permalink)
+                .first::<Option<String>>(conn)
+                .optional()?
+                .flatten();
+        }
         diesel::replace_into(messages::table)
             .values(&cached)
             .execute(conn)
             ?;
     }
 
-    if verbose {
-        eprintln!("[CACHE] Messages (conv {}) - UPSERTED {} messages", conv_id, message_list.len());
+    debug!(entity = "messages", conversation = %conv_id, count = message_list.len(), "cache upsert");
+
+    Ok(())
+}
+
+/// Get every cached message of one thread (root and replies), oldest first, whatever its age
+pub fn get_thread_messages(
+    conn: &mut CacheConnection,
+    ws_id: &str,
+    conv_id: &str,
+    root_ts: &str,
+) -> Result<Vec<Message>> {
+    use super::schema::messages::dsl::*;
+
+    let cached_msgs: Vec<CachedMessage> = messages
+        .filter(conversation_id.eq(conv_id))
+        .filter(workspace_id.eq(ws_id))
+        .filter(thread_ts.eq(root_ts))
+        .filter(deleted_at.is_null())
+        .load(conn)?;
+
+    let mut thread: Vec<Message> = cached_msgs.iter().map(|m| m.to_api_message()).collect::<Result<_>>()?;
+    thread.sort_by_key(|m| parse_ts(&m.ts).unwrap_or_default());
+    debug!(entity = "thread", conversation = %conv_id, ts = %root_ts, count = thread.len(), "cache lookup");
+    Ok(thread)
+}
+
+/// Get cached permalinks for the given message timestamps (ts -> permalink)
+pub fn get_permalinks(
+    conn: &mut CacheConnection,
+    ws_id: &str,
+    conv_id: &str,
+    ts_list: &[String],
+) -> Result<HashMap<String, String>> {
+    let rows: Vec<(String, Option<String>)> = messages::table
+        .filter(messages::workspace_id.eq(ws_id))
+        .filter(messages::conversation_id.eq(conv_id))
+        .filter(messages::ts.eq_any(ts_list))
+        .select((messages::ts, messages::permalink))
+        .load(conn)?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(msg_ts, link)| link.map(|link| (msg_ts, link)))
+        .collect())
+}
+
+/// Store a permalink on an already-cached message; a no-op if the message isn't cached
+pub fn set_permalink(
+    conn: &mut CacheConnection,
+    ws_id: &str,
+    conv_id: &str,
+    msg_ts: &str,
+    link: &str,
+) -> Result<()> {
+    let updated = diesel::update(messages::table.find((conv_id, ws_id, msg_ts)))
+        .set(messages::permalink.eq(link))
+        .execute(conn)?;
+
+    debug!(entity = "permalink", conversation = %conv_id, ts = %msg_ts, rows = updated, "cache update");
+
+    Ok(())
+}
+
+// Emoji operations
+
+/// Get the workspace's emoji map (name -> URL, `alias:<name>`, or "" for standard)
+pub fn get_emoji(
+    conn: &mut CacheConnection,
+    ws_id: &str,
+) -> Result<Option<HashMap<String, String>>> {
+    let cached: Vec<CachedEmoji> = emoji::table
+        .filter(emoji::workspace_id.eq(ws_id))
+        .load(conn)?;
+
+    if cached.is_empty() {
+        debug!(entity = "emoji", outcome = "miss", "cache lookup");
+        return Ok(None);
     }
 
+    if !cached.iter().all(|e| is_fresh(e.cached_at, EMOJI_TTL_SECONDS)) {
+        debug!(entity = "emoji", outcome = "stale", "cache lookup");
+        return Ok(None);
+    }
+
+    debug!(entity = "emoji", count = cached.len(), outcome = "hit", "cache lookup");
+    Ok(Some(cached.into_iter().map(|e| (e.name, e.value)).collect()))
+}
+
+/// Replace the workspace's cached emoji map so removed custom emoji disappear
+pub fn replace_emoji(
+    conn: &mut CacheConnection,
+    ws_id: &str,
+    emoji_map: &HashMap<String, String>,
+) -> Result<()> {
+    let now = Utc::now().naive_utc();
+    let rows: Vec<CachedEmoji> = emoji_map
+        .iter()
+        .map(|(name, value)| CachedEmoji {
+            name: name.clone(),
+            workspace_id: ws_id.to_string(),
+            value: value.clone(),
+            cached_at: now,
+        })
+        .collect();
+
+    conn.transaction(|conn| {
+        diesel::delete(emoji::table.filter(emoji::workspace_id.eq(ws_id))).execute(conn)?;
+        for row in &rows {
+            diesel::insert_into(emoji::table).values(row).execute(conn)?;
+        }
+        diesel::QueryResult::Ok(())
+    })?;
+
+    debug!(entity = "emoji", count = rows.len(), "cache replace");
+
+    Ok(())
+}
+
+// Last fetch operations (used by --since-last-run). The table is bookkeeping, not cached
+// Slack data, so clear_workspace_cache and clear_all_cache deliberately leave it alone:
+// wiping it would make the next scheduled run re-print the whole history or fall back to
+// --limit and skip messages. The recorded ts only ever moves forward.
+
+/// Get the newest message ts recorded for a channel
+pub fn get_last_fetch(
+    conn: &mut CacheConnection,
+    ws_id: &str,
+    conv_id: &str,
+) -> Result<Option<String>> {
+    let last_ts: Option<String> = last_fetch::table
+        .filter(last_fetch::workspace_id.eq(ws_id))
+        .filter(last_fetch::conversation_id.eq(conv_id))
+        .select(last_fetch::last_ts)
+        .first(conn)
+        .optional()?;
+
+    debug!(entity = "last_fetch", conversation = %conv_id, ts = ?last_ts, "cache lookup");
+
+    Ok(last_ts)
+}
+
+/// Record the newest message ts seen for a channel
+pub fn set_last_fetch(
+    conn: &mut CacheConnection,
+    ws_id: &str,
+    conv_id: &str,
+    ts: &str,
+) -> Result<()> {
+    let row = LastFetch {
+        workspace_id: ws_id.to_string(),
+        conversation_id: conv_id.to_string(),
+        last_ts: ts.to_string(),
+        fetched_at: Utc::now().naive_utc(),
+    };
+    diesel::replace_into(last_fetch::table).values(&row).execute(conn)?;
+
+    debug!(entity = "last_fetch", conversation = %conv_id, ts = %ts, "cache update");
+
     Ok(())
 }
 
@@ -490,9 +612,12 @@ pub fn upsert_messages(
 pub fn clear_workspace_cache(
     conn: &mut CacheConnection,
     workspace_id: &str,
-    verbose: bool,
 ) -> Result<()> {
-    use super::schema::{conversations, messages, users};
+    use super::schema::{conversations, emoji, messages, users};
+
+    diesel::delete(emoji::table.filter(emoji::workspace_id.eq(workspace_id)))
+        .execute(conn)
+        ?;
 
     diesel::delete(messages::table.filter(messages::workspace_id.eq(workspace_id)))
         .execute(conn)
@@ -506,27 +631,209 @@ pub fn clear_workspace_cache(
         .execute(conn)
         ?;
 
-    if verbose {
-        eprintln!("[CACHE] Cleared all cache for workspace {}", workspace_id);
-    }
+    debug!(workspace = %workspace_id, "cache cleared");
 
     Ok(())
 }
 
-pub fn clear_all_cache(conn: &mut CacheConnection, verbose: bool) -> Result<()> {
-    use super::schema::{conversations, messages, users};
+pub fn clear_all_cache(conn: &mut CacheConnection) -> Result<()> {
+    use super::schema::{conversations, emoji, messages, users};
 
+    diesel::delete(emoji::table).execute(conn)?;
     diesel::delete(messages::table).execute(conn)?;
     diesel::delete(conversations::table).execute(conn)?;
     diesel::delete(users::table).execute(conn)?;
 
-    if verbose {
-        eprintln!("[CACHE] Cleared all cache");
-    }
+    debug!("cache cleared for all workspaces");
 
     Ok(())
 }
 
+// Export / import operations
+
+pub const CACHE_EXPORT_VERSION: u32 = 2;
+
+/// Portable snapshot of one workspace's cache, built from the `full_object` columns
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CacheExport {
+    pub version: u32,
+    pub workspace_id: String,
+    pub exported_at: String,
+    pub users: Vec<ExportedUser>,
+    pub conversations: Vec<ExportedConversation>,
+    pub messages: Vec<ExportedMessage>,
+}
+
+/// Each exported row keeps its original `cached_at` so an import doesn't make old data look fresh
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedUser {
+    pub cached_at: NaiveDateTime,
+    pub user: User,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedConversation {
+    pub cached_at: NaiveDateTime,
+    pub conversation: Channel,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedMessage {
+    pub conversation_id: String,
+    pub cached_at: NaiveDateTime,
+    pub message: Message,
+}
+
+/// Counts of rows written and skipped by [`import_workspace`]
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportStats {
+    pub imported: usize,
+    /// Rows left alone because the local copy was cached more recently
+    pub skipped: usize,
+}
+
+/// Dump every cached user, conversation and message for a workspace, ignoring TTL.
+///
+/// Rows that can no longer be decoded (e.g. encrypted under a different key) are skipped.
+pub fn export_workspace(conn: &mut CacheConnection, ws_id: &str) -> Result<CacheExport> {
+    let cached_users: Vec<CachedUser> = users::table
+        .filter(users::workspace_id.eq(ws_id))
+        .filter(users::deleted_at.is_null())
+        .load(conn)?;
+
+    let cached_convs: Vec<CachedConversation> = conversations::table
+        .filter(conversations::workspace_id.eq(ws_id))
+        .filter(conversations::deleted_at.is_null())
+        .load(conn)?;
+
+    let cached_msgs: Vec<CachedMessage> = messages::table
+        .filter(messages::workspace_id.eq(ws_id))
+        .filter(messages::deleted_at.is_null())
+        .load(conn)?;
+
+    let export = CacheExport {
+        version: CACHE_EXPORT_VERSION,
+        workspace_id: ws_id.to_string(),
+        exported_at: Utc::now().to_rfc3339(),
+        users: cached_users
+            .iter()
+            .filter_map(|u| {
+                Some(ExportedUser {
+                    cached_at: u.cached_at,
+                    user: u.to_api_user().ok()?,
+                })
+            })
+            .collect(),
+        conversations: cached_convs
+            .iter()
+            .filter_map(|c| {
+                Some(ExportedConversation {
+                    cached_at: c.cached_at,
+                    conversation: c.to_api_channel().ok()?,
+                })
+            })
+            .collect(),
+        messages: cached_msgs
+            .iter()
+            .filter_map(|m| {
+                Some(ExportedMessage {
+                    conversation_id: m.conversation_id.clone(),
+                    cached_at: m.cached_at,
+                    message: m.to_api_message().ok()?,
+                })
+            })
+            .collect(),
+    };
+
+    debug!(
+        users = export.users.len(),
+        conversations = export.conversations.len(),
+        messages = export.messages.len(),
+        "cache exported"
+    );
+
+    Ok(export)
+}
+
+/// Merge an export into the cache under the export's workspace.
+///
+/// Rows keep the `cached_at` they were exported with, and a row is skipped when the
+/// local copy was cached more recently than the exported one.
+pub fn import_workspace(conn: &mut CacheConnection, export: &CacheExport) -> Result<ImportStats> {
+    if export.version != CACHE_EXPORT_VERSION {
+        anyhow::bail!(
+            "Unsupported cache export version {} (expected {})",
+            export.version,
+            CACHE_EXPORT_VERSION
+        );
+    }
+
+    let ws_id = export.workspace_id.as_str();
+
+    conn.transaction(|conn| {
+        let mut stats = ImportStats::default();
+
+        for exported in &export.users {
+            let local: Option<NaiveDateTime> = users::table
+                .filter(users::workspace_id.eq(ws_id))
+                .filter(users::id.eq(&exported.user.id))
+                .select(users::cached_at)
+                .first(conn)
+                .optional()?;
+            if local.is_some_and(|local| local > exported.cached_at) {
+                stats.skipped += 1;
+                continue;
+            }
+
+            let mut cached = CachedUser::from_api_user(&exported.user, ws_id)?;
+            cached.cached_at = exported.cached_at;
+            diesel::replace_into(users::table).values(&cached).execute(conn)?;
+            stats.imported += 1;
+        }
+
+        for exported in &export.conversations {
+            let local: Option<NaiveDateTime> = conversations::table
+                .filter(conversations::workspace_id.eq(ws_id))
+                .filter(conversations::id.eq(&exported.conversation.id))
+                .select(conversations::cached_at)
+                .first(conn)
+                .optional()?;
+            if local.is_some_and(|local| local > exported.cached_at) {
+                stats.skipped += 1;
+                continue;
+            }
+
+            let mut cached = CachedConversation::from_api_channel(&exported.conversation, ws_id)?;
+            cached.cached_at = exported.cached_at;
+            diesel::replace_into(conversations::table).values(&cached).execute(conn)?;
+            stats.imported += 1;
+        }
+
+        for exported in &export.messages {
+            let local: Option<NaiveDateTime> = messages::table
+                .filter(messages::workspace_id.eq(ws_id))
+                .filter(messages::conversation_id.eq(&exported.conversation_id))
+                .filter(messages::ts.eq(&exported.message.ts))
+                .select(messages::cached_at)
+                .first(conn)
+                .optional()?;
+            if local.is_some_and(|local| local > exported.cached_at) {
+                stats.skipped += 1;
+                continue;
+            }
+
+            let mut cached = CachedMessage::from_api_message(&exported.message, &exported.conversation_id, ws_id)?;
+            cached.cached_at = exported.cached_at;
+            diesel::replace_into(messages::table).values(&cached).execute(conn)?;
+            stats.imported += 1;
+        }
+
+        debug!(imported = stats.imported, skipped = stats.skipped, "cache imported");
+
+        Ok(stats)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -566,4 +873,206 @@ mod tests {
         // With i64::MAX TTL override, should be fresh
         assert!(is_fresh(ten_years_ago, i64::MAX));
     }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source_path = temp_dir.path().join("source.db");
+        let target_path = temp_dir.path().join("target.db");
+        super::super::db::init_cache_db_at_path(&source_path).unwrap();
+        super::super::db::init_cache_db_at_path(&target_path).unwrap();
+
+        let user: User = serde_json::from_str(
+            r#"{"id": "U1", "name": "alice", "deleted": false, "is_bot": false, "profile": {}}"#,
+        )
+        .unwrap();
+        let channel: Channel = serde_json::from_str(r#"{"id": "C1", "name": "general"}"#).unwrap();
+        let message: Message =
+            serde_json::from_str(r#"{"ts": "1234567890.000001", "user": "U1", "text": "hello"}"#).unwrap();
+
+        let mut source = SqliteConnection::establish(&format!("sqlite://{}", source_path.display())).unwrap();
+        upsert_user(&mut source, "T1", &user).unwrap();
+        upsert_conversation(&mut source, "T1", &channel).unwrap();
+        upsert_messages(&mut source, "T1", "C1", &[message]).unwrap();
+        // Other workspaces are not part of the export
+        upsert_user(&mut source, "T2", &user).unwrap();
+
+        let export = export_workspace(&mut source, "T1").unwrap();
+        assert_eq!(export.workspace_id, "T1");
+        assert_eq!(export.users.len(), 1);
+        assert_eq!(export.conversations.len(), 1);
+        assert_eq!(export.messages.len(), 1);
+
+        // Round-trip through JSON like the CLI does
+        let json = serde_json::to_string(&export).unwrap();
+        let export: CacheExport = serde_json::from_str(&json).unwrap();
+
+        assert!(export.users[0].cached_at <= Utc::now().naive_utc());
+
+        let mut target = SqliteConnection::establish(&format!("sqlite://{}", target_path.display())).unwrap();
+        let stats = import_workspace(&mut target, &export).unwrap();
+        assert_eq!(stats, ImportStats { imported: 3, skipped: 0 });
+
+        assert_eq!(get_user(&mut target, "T1", "U1", None).unwrap().unwrap().name, "alice");
+        assert_eq!(get_conversation(&mut target, "T1", "C1", None).unwrap().unwrap().name, "general");
+        assert_eq!(get_messages(&mut target, "T1", "C1").unwrap().unwrap()[0].text, "hello");
+    }
+
+    #[test]
+    fn test_import_keeps_newer_local_rows() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("cache.db");
+        super::super::db::init_cache_db_at_path(&db_path).unwrap();
+        let mut conn = SqliteConnection::establish(&format!("sqlite://{}", db_path.display())).unwrap();
+
+        let local: User = serde_json::from_str(
+            r#"{"id": "U1", "name": "alice-renamed", "deleted": false, "is_bot": false, "profile": {}}"#,
+        )
+        .unwrap();
+        upsert_user(&mut conn, "T1", &local).unwrap();
+
+        let stale: User = serde_json::from_str(
+            r#"{"id": "U1", "name": "alice", "deleted": false, "is_bot": false, "profile": {}}"#,
+        )
+        .unwrap();
+        let other: User = serde_json::from_str(
+            r#"{"id": "U2", "name": "bob", "deleted": false, "is_bot": false, "profile": {}}"#,
+        )
+        .unwrap();
+        let two_days_ago = Utc::now().naive_utc() - chrono::Duration::days(2);
+        let export = CacheExport {
+            version: CACHE_EXPORT_VERSION,
+            workspace_id: "T1".to_string(),
+            exported_at: Utc::now().to_rfc3339(),
+            users: vec![
+                ExportedUser { cached_at: two_days_ago, user: stale },
+                ExportedUser { cached_at: two_days_ago, user: other },
+            ],
+            conversations: vec![],
+            messages: vec![],
+        };
+
+        let stats = import_workspace(&mut conn, &export).unwrap();
+        assert_eq!(stats, ImportStats { imported: 1, skipped: 1 });
+
+        // The newer local row wins
+        assert_eq!(get_user(&mut conn, "T1", "U1", None).unwrap().unwrap().name, "alice-renamed");
+
+        // The imported row keeps its original age instead of looking freshly cached
+        let cached_at: NaiveDateTime = users::table
+            .filter(users::id.eq("U2"))
+            .select(users::cached_at)
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(cached_at, two_days_ago);
+        assert!(get_user(&mut conn, "T1", "U2", Some(3600)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_replace_emoji_drops_removed_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("cache.db");
+        super::super::db::init_cache_db_at_path(&db_path).unwrap();
+        let mut conn = SqliteConnection::establish(&format!("sqlite://{}", db_path.display())).unwrap();
+
+        assert!(get_emoji(&mut conn, "T1").unwrap().is_none());
+
+        let first: HashMap<String, String> = [
+            ("partyparrot".to_string(), "https://emoji.slack-edge.com/parrot.gif".to_string()),
+            ("old".to_string(), "alias:partyparrot".to_string()),
+        ]
+        .into();
+        replace_emoji(&mut conn, "T1", &first).unwrap();
+
+        let second: HashMap<String, String> = [("thumbsup".to_string(), String::new())].into();
+        replace_emoji(&mut conn, "T1", &second).unwrap();
+
+        let cached = get_emoji(&mut conn, "T1").unwrap().unwrap();
+        assert_eq!(cached, second);
+    }
+
+    #[test]
+    fn test_last_fetch_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("cache.db");
+        super::super::db::init_cache_db_at_path(&db_path).unwrap();
+        let mut conn = SqliteConnection::establish(&format!("sqlite://{}", db_path.display())).unwrap();
+
+        assert_eq!(get_last_fetch(&mut conn, "T1", "C1").unwrap(), None);
+
+        set_last_fetch(&mut conn, "T1", "C1", "1717250400.000100").unwrap();
+        set_last_fetch(&mut conn, "T1", "C1", "1717252200.000200").unwrap();
+        set_last_fetch(&mut conn, "T1", "C2", "1.0").unwrap();
+
+        assert_eq!(
+            get_last_fetch(&mut conn, "T1", "C1").unwrap().as_deref(),
+            Some("1717252200.000200")
+        );
+        assert_eq!(get_last_fetch(&mut conn, "T2", "C1").unwrap(), None);
+
+        // Survives a workspace cache clear
+        clear_workspace_cache(&mut conn, "T1").unwrap();
+        assert!(get_last_fetch(&mut conn, "T1", "C2").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_permalink_survives_history_refresh() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("cache.db");
+        super::super::db::init_cache_db_at_path(&db_path).unwrap();
+        let mut conn = SqliteConnection::establish(&format!("sqlite://{}", db_path.display())).unwrap();
+
+        let message: Message = serde_json::from_value(serde_json::json!({
+            "ts": "1700000000.000100",
+            "text": "deploy done"
+        }))
+        .unwrap();
+        let ts_list = vec![message.ts.clone()];
+
+        upsert_messages(&mut conn, "T1", "C1", std::slice::from_ref(&message)).unwrap();
+        assert!(get_permalinks(&mut conn, "T1", "C1", &ts_list).unwrap().is_empty());
+
+        let link = "https://acme.slack.com/archives/C1/p1700000000000100";
+        set_permalink(&mut conn, "T1", "C1", &message.ts, link).unwrap();
+
+        // A later history fetch without permalinks must not wipe the backfilled one
+        upsert_messages(&mut conn, "T1", "C1", &[message]).unwrap();
+        let links = get_permalinks(&mut conn, "T1", "C1", &ts_list).unwrap();
+        assert_eq!(links.get("1700000000.000100").map(String::as_str), Some(link));
+        assert!(get_permalinks(&mut conn, "T1", "C2", &ts_list).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_im_for_user() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("cache.db");
+        super::super::db::init_cache_db_at_path(&db_path).unwrap();
+        let mut conn = SqliteConnection::establish(&format!("sqlite://{}", db_path.display())).unwrap();
+
+        let im: Channel = serde_json::from_value(serde_json::json!({"id": "D1", "is_im": true, "user": "U1"})).unwrap();
+        let general: Channel = serde_json::from_value(serde_json::json!({"id": "C1", "name": "general"})).unwrap();
+        upsert_conversations(&mut conn, "T1", &[im, general]).unwrap();
+
+        assert_eq!(get_im_for_user(&mut conn, "T1", "U1").unwrap().map(|c| c.id).as_deref(), Some("D1"));
+        assert!(get_im_for_user(&mut conn, "T1", "U2").unwrap().is_none());
+        assert!(get_im_for_user(&mut conn, "T2", "U1").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_import_rejects_unknown_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("cache.db");
+        super::super::db::init_cache_db_at_path(&db_path).unwrap();
+        let mut conn = SqliteConnection::establish(&format!("sqlite://{}", db_path.display())).unwrap();
+
+        let export = CacheExport {
+            version: CACHE_EXPORT_VERSION + 1,
+            workspace_id: "T1".to_string(),
+            exported_at: Utc::now().to_rfc3339(),
+            users: vec![],
+            conversations: vec![],
+            messages: vec![],
+        };
+        assert!(import_workspace(&mut conn, &export).is_err());
+    }
 }
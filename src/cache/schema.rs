@@ -24,6 +24,24 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    emoji (name, workspace_id) {
+        name -> Text,
+        workspace_id -> Text,
+        value -> Text,
+        cached_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    last_fetch (workspace_id, conversation_id) {
+        workspace_id -> Text,
+        conversation_id -> Text,
+        last_ts -> Text,
+        fetched_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     messages (conversation_id, workspace_id, ts) {
         conversation_id -> Text,
@@ -63,6 +81,8 @@ diesel::table! {
 
 diesel::allow_tables_to_appear_in_same_query!(
     conversations,
+    emoji,
+    last_fetch,
     messages,
     users,
 );
@@ -1,4 +1,10 @@
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::logging::LogLevel;
+use crate::output::color::{ColorChoice, IdFormat};
+use crate::output::file_formatter::FileGroupBy;
+use crate::output::message_formatter::{AuthorWidth, ReactionDisplay};
 
 #[derive(Parser)]
 #[command(name = "clack")]
@@ -8,11 +14,15 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
 
-    /// Disable colorized output
+    /// Disable colorized output (same as --color never)
     #[arg(long, global = true)]
     pub no_color: bool,
 
-    /// Output format (human, human-compact, json, yaml)
+    /// When to use colors
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    pub color: ColorChoice,
+
+    /// Output format (human, human-compact, json, yaml; transcript for conversations history)
     #[arg(long, global = true, default_value = "human")]
     pub format: String,
 
@@ -20,10 +30,70 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub no_pager: bool,
 
-    /// Enable verbose logging
+    /// Total items to return across pages for users, channels, files and search lists (--limit is the page size)
+    #[arg(long, global = true, value_name = "N", value_parser = total_count)]
+    pub count: Option<usize>,
+
+    /// Truncate message text to N characters in human output (json/yaml keep full text)
+    #[arg(long, global = true, value_name = "N", value_parser = max_text_length)]
+    pub max_text_length: Option<usize>,
+
+    /// Trim each line of message text and collapse blank-line runs in human output (code blocks kept)
+    #[arg(long, global = true)]
+    pub trim: bool,
+
+    /// Show message times as the raw Slack ts (1700000000.123456) instead of a local date
+    #[arg(long, global = true)]
+    pub raw_ts: bool,
+
+    /// Treat skin-tone variants (:wave::skin-tone-3:) as their base emoji in human output and tallies
+    #[arg(long, global = true)]
+    pub normalize_emoji_skin_tones: bool,
+
+    /// JSON file mapping custom emoji shortcodes to display strings ({"shipit": "[shipit]"}) in human output
+    #[arg(long, global = true, value_name = "FILE")]
+    pub emoji_map_file: Option<PathBuf>,
+
+    /// How to show reactions under messages: every emoji, just the total, or none
+    #[arg(long, global = true, value_enum, default_value = "full", value_name = "MODE")]
+    pub reactions: ReactionDisplay,
+
+    /// Show Slack IDs next to names (show), leave them out (hide), or print only the IDs (only)
+    #[arg(long, global = true, value_enum, default_value = "show", value_name = "MODE")]
+    pub id_format: IdFormat,
+
+    /// Workspace subdomain for message links (acme for acme.slack.com); defaults to the one auth.test reports
+    #[arg(long, global = true, value_name = "DOMAIN")]
+    pub team_domain: Option<String>,
+
+    /// Use plain ASCII instead of emoji and box-drawing glyphs (default when the locale isn't UTF-8)
+    #[arg(long, global = true, overrides_with = "no_ascii")]
+    pub ascii: bool,
+
+    /// Use emoji and box-drawing glyphs even when the locale isn't UTF-8
+    #[arg(long, global = true, overrides_with = "ascii")]
+    pub no_ascii: bool,
+
+    /// Print only the values matching a JSONPath subset, one per line (e.g. '$.messages[*].ts')
+    #[arg(long, global = true, value_name = "EXPR")]
+    pub json_path: Option<String>,
+
+    /// Pretty-print json output (default when stdout is a terminal)
+    #[arg(long, global = true, overrides_with = "compact")]
+    pub pretty: bool,
+
+    /// Print json output on a single line (default when piped)
+    #[arg(long, global = true, overrides_with = "pretty")]
+    pub compact: bool,
+
+    /// Enable verbose logging (same as --log-level debug)
     #[arg(short, long, global = true)]
     pub verbose: bool,
 
+    /// Diagnostics to print on stderr (default: warn)
+    #[arg(long, global = true, value_enum, value_name = "LEVEL")]
+    pub log_level: Option<LogLevel>,
+
     /// Show raw HTTP response bodies for debugging
     #[arg(long, global = true)]
     pub debug_response: bool,
@@ -31,6 +101,168 @@ pub struct Cli {
     /// Force cache refresh - bypass cache and query API directly
     #[arg(long, global = true)]
     pub refresh_cache: bool,
+
+    /// Trust an extra root CA certificate (PEM or DER), e.g. for a TLS-intercepting proxy
+    #[arg(long, global = true, value_name = "PATH")]
+    pub ca_cert: Option<PathBuf>,
+
+    /// Disable TLS certificate verification (dangerous; prefer --ca-cert)
+    #[arg(long, global = true)]
+    pub insecure: bool,
+
+    /// Workspace to target with an Enterprise Grid org token (e.g. T0123ABCD)
+    #[arg(long, global = true, value_name = "TEAM_ID")]
+    pub team: Option<String>,
+
+    /// Maximum total seconds to spend waiting on rate limits and server-error retries before giving up
+    #[arg(long, global = true, value_name = "SECS")]
+    pub rate_limit_budget: Option<u64>,
+
+    /// Fail immediately when rate limited, reporting Slack's Retry-After, instead of waiting
+    #[arg(long, global = true)]
+    pub no_wait: bool,
+
+    /// Resolve channel names from the cache only; fail instead of crawling conversations.list on a miss
+    #[arg(long, global = true, alias = "channel-cache-only")]
+    pub no_api_resolve: bool,
+
+    /// How many times to retry a request after a rate limit or transient server error
+    #[arg(long, global = true, value_name = "N", default_value_t = crate::api::client::DEFAULT_MAX_RETRIES)]
+    pub max_retries: u32,
+
+    /// Delay before the first retry of a server error
+    #[arg(long, global = true, hide = true, value_name = "DURATION", default_value = "500ms", value_parser = duration)]
+    pub retry_base_delay: std::time::Duration,
+
+    /// Growth factor between server-error retries
+    #[arg(long, global = true, hide = true, value_name = "FACTOR", default_value_t = 2.0)]
+    pub retry_multiplier: f64,
+
+    /// Longest delay between server-error retries (before jitter)
+    #[arg(long, global = true, hide = true, value_name = "DURATION", default_value = "8s", value_parser = duration)]
+    pub retry_max_delay: std::time::Duration,
+
+    /// Retry server errors after exact delays, without random jitter
+    #[arg(long, global = true, hide = true)]
+    pub no_retry_jitter: bool,
+
+    /// Retry 500/502/503/504 responses with exponential backoff (default)
+    #[arg(long = "retry-on-5xx", global = true, overrides_with = "no_retry_on_5xx")]
+    pub retry_on_5xx: bool,
+
+    /// Fail on the first 5xx response instead of retrying
+    #[arg(long = "no-retry-on-5xx", global = true, overrides_with = "retry_on_5xx")]
+    pub no_retry_on_5xx: bool,
+
+    /// Append structured API request logs (NDJSON) to this file
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Disable the local cache entirely - no cache reads or writes
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    /// Send API requests to this Slack Web API root instead of https://slack.com/api (proxy, replay or mock server)
+    #[arg(long, global = true, hide = true, env = "CLACK_BASE_URL", value_name = "URL", value_parser = base_url)]
+    pub base_url: Option<String>,
+
+    /// Save every API response as a JSON file in DIR for --replay (tokens are redacted)
+    #[arg(long, global = true, value_name = "DIR", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Answer API calls from responses saved with --record instead of calling Slack (no token needed)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub replay: Option<PathBuf>,
+
+    /// Keep the cache database in DIR instead of the platform cache directory (created if missing)
+    #[arg(long, global = true, env = "CLACK_CACHE_DIR", value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Stop multi-item commands at the first failed lookup (user, thread) instead of warning
+    #[arg(long, global = true, alias = "strict", overrides_with = "continue_on_error")]
+    pub fail_fast: bool,
+
+    /// Skip failed lookups and report them as one warning on stderr (default)
+    #[arg(long = "continue", global = true, overrides_with = "fail_fast")]
+    pub continue_on_error: bool,
+
+    /// Open the resulting URL (permalink, profile) in the default browser
+    #[arg(long, global = true)]
+    pub open: bool,
+
+    /// Print a one-line result summary to stderr after the output
+    #[arg(long, global = true)]
+    pub summary: bool,
+}
+
+impl Cli {
+    /// Effective color setting; `--no-color` wins over `--color`
+    pub fn color_choice(&self) -> ColorChoice {
+        if self.no_color {
+            ColorChoice::Never
+        } else {
+            self.color
+        }
+    }
+
+    /// Whether human output uses ASCII glyphs; without a flag, follows the locale
+    pub fn ascii_glyphs(&self) -> bool {
+        if self.ascii {
+            true
+        } else if self.no_ascii {
+            false
+        } else {
+            !crate::output::glyphs::locale_supports_utf8()
+        }
+    }
+
+    /// Effective log level; an explicit `--log-level` wins over `--verbose`
+    pub fn effective_log_level(&self) -> LogLevel {
+        match self.log_level {
+            Some(level) => level,
+            None if self.verbose => LogLevel::Debug,
+            None => LogLevel::Warn,
+        }
+    }
+
+    /// Backoff for server-error retries from the hidden --retry-* flags
+    pub fn retry_config(&self) -> crate::api::client::RetryConfig {
+        crate::api::client::RetryConfig {
+            base_delay: self.retry_base_delay,
+            multiplier: self.retry_multiplier,
+            max_delay: self.retry_max_delay,
+            jitter: !self.no_retry_jitter,
+        }
+    }
+
+    /// Display settings for human output, shared by every formatter
+    pub fn render_options(&self) -> crate::output::render::RenderOptions {
+        crate::output::render::RenderOptions {
+            max_text_length: self.max_text_length,
+            normalize_skin_tones: self.normalize_emoji_skin_tones,
+            trim: self.trim,
+            raw_ts: self.raw_ts,
+            ascii: self.ascii_glyphs(),
+            reaction_display: self.reactions,
+            id_format: self.id_format,
+            team_domain: self.team_domain.as_deref().map(|domain| {
+                crate::api::client::team_domain_from_url(domain).unwrap_or_else(|| domain.to_string())
+            }),
+            transcript: self.format == "transcript",
+            // Loaded from --emoji-map-file in main, where a bad file can be reported
+            emoji_map: Default::default(),
+        }
+    }
+
+    /// `--record` or `--replay`, if either was given
+    pub fn recording(&self) -> Option<crate::api::recording::Recording> {
+        use crate::api::recording::Recording;
+        match (&self.record, &self.replay) {
+            (Some(dir), _) => Some(Recording::Record(dir.clone())),
+            (None, Some(dir)) => Some(Recording::Replay(dir.clone())),
+            (None, None) => None,
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -75,33 +307,101 @@ pub enum Commands {
         #[command(subcommand)]
         auth_type: AuthType,
     },
+    /// Local cache management
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+    /// Export conversations to files
+    Export {
+        #[command(subcommand)]
+        command: ExportCommands,
+    },
+    /// Fetch channels' full history into the local cache without printing it
+    #[command(after_help = "Examples:\n  clack warm general random\n  clack warm deploys --oldest 1717200000")]
+    Warm {
+        /// Channel IDs or names (e.g., C1234ABCD #general random)
+        #[arg(required = true)]
+        channels: Vec<String>,
+
+        /// Only go back as far as this timestamp
+        #[arg(long)]
+        oldest: Option<String>,
+    },
     /// Stream real-time updates (runs until Ctrl+C)
     Stream {
-        /// Poll interval in seconds
-        #[arg(long, default_value = "10")]
-        interval: u64,
+        /// Poll interval: seconds (10, 2.5) or with a unit (1500ms, 30s, 2m); at least 1s
+        #[arg(long, default_value = "10", value_parser = poll_interval)]
+        interval: std::time::Duration,
+
+        /// Persist seen messages to this file so restarts don't re-print them
+        #[arg(long)]
+        state_file: Option<PathBuf>,
+
+        /// Put this in front of every output line; {channel} and {query} are filled in
+        #[arg(long, value_name = "TEMPLATE")]
+        prefix: Option<String>,
+
+        /// Don't print what the first poll finds; only show matches that arrive after startup
+        #[arg(long)]
+        skip_initial: bool,
 
         #[command(subcommand)]
         stream_type: StreamType,
     },
+    /// Show version and build details (commit, build date, rustc, features)
+    Version {
+        /// Print as JSON (same as --format json)
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum UsersCommands {
     /// List all users
+    #[command(after_help = "Examples:\n  clack users list --presence\n  clack users list --only-deleted --format json\n  clack users list --email-domain corp.com --humans-only --count 10000")]
     List {
-        /// Maximum number of users to return
+        /// Users per page; only the first page is shown unless --count asks for more
         #[arg(long, default_value = "200")]
         limit: u32,
 
         /// Include deleted/deactivated users
         #[arg(long)]
         include_deleted: bool,
+
+        /// Show only deleted/deactivated users
+        #[arg(long)]
+        only_deleted: bool,
+
+        /// Workspace to list (sent as team_id; defaults to the current workspace)
+        #[arg(long, value_name = "TEAM_ID")]
+        team_id: Option<String>,
+
+        /// Show who is active or away (one users.getPresence call per user)
+        #[arg(long)]
+        presence: bool,
+
+        /// Only users whose profile email is at this domain or a subdomain (e.g. corp.com); users without an email are left out
+        #[arg(long, value_name = "DOMAIN")]
+        email_domain: Option<String>,
+
+        /// Leave out bots, integrations and Slackbot
+        #[arg(long)]
+        humans_only: bool,
+    },
+    /// Compare the cached users with a fresh list: who was added, removed or changed
+    Diff {
+        /// Users per page; every page is always fetched
+        #[arg(long, default_value = "200")]
+        limit: u32,
     },
-    /// Get information about a specific user
+    /// Get information about one or more users
+    #[command(after_help = "Examples:\n  clack users info @alice\n  clack users info U0123ABCD @bob --format json")]
     Info {
-        /// Slack user ID (e.g., U1234ABCD)
-        user_id: String,
+        /// Slack user IDs or @names (e.g., U1234ABCD @alice)
+        #[arg(required = true)]
+        users: Vec<String>,
     },
     /// Get user profile information
     Profile {
@@ -116,27 +416,53 @@ pub enum ProfileCommands {
     Get {
         /// Slack user ID (optional, defaults to authenticated user)
         user_id: Option<String>,
+
+        /// Show every field Slack returns, including custom profile fields
+        #[arg(long)]
+        all_fields: bool,
     },
 }
 
 #[derive(Subcommand)]
 pub enum ConversationsCommands {
     /// List all channels the bot has access to
+    #[command(after_help = "Examples:\n  clack conversations list --include-archived\n  clack conversations list --sort-channels-by-activity --count 50")]
     List {
         /// Include archived channels
         #[arg(long)]
         include_archived: bool,
 
+        /// Show only archived channels
+        #[arg(long)]
+        only_archived: bool,
+
         /// Maximum number of channels to retrieve per page (default: 200, max: 1000)
         #[arg(long, default_value = "200")]
         limit: u32,
+
+        /// Sort by last message time, newest first (one extra conversations.history call per channel)
+        #[arg(long)]
+        sort_channels_by_activity: bool,
     },
     /// Get information about a specific channel
     Info {
-        /// Channel ID or name (e.g., C1234ABCD, #general, or general)
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
         channel: String,
+
+        /// Add message count, participants and last activity from recent history
+        #[arg(long)]
+        history_stats: bool,
+
+        /// Window for --history-stats, in days
+        #[arg(long, default_value = "30", requires = "history_stats")]
+        days: u32,
+
+        /// Print the channel object exactly as conversations.info returns it, with every field
+        #[arg(long, conflicts_with = "history_stats")]
+        raw: bool,
     },
     /// Get message history from a channel
+    #[command(after_help = "Examples:\n  clack conversations history general --limit 50\n  clack conversations history deploys --since-last-run --oneline\n  clack conversations history C0123ABCD --oldest 1717200000 --group-by-day\n  clack conversations history general --format transcript\n  clack conversations history incidents --oldest 1717200000 --participants")]
     History {
         /// Channel ID or name
         channel: String,
@@ -152,29 +478,187 @@ pub enum ConversationsCommands {
         /// Start of time range (Unix timestamp)
         #[arg(long)]
         oldest: Option<String>,
+
+        /// Include messages posted by bots and integrations (default)
+        #[arg(long, overrides_with = "exclude_bots")]
+        include_bots: bool,
+
+        /// Hide messages posted by bots and integrations
+        #[arg(long, overrides_with = "include_bots")]
+        exclude_bots: bool,
+
+        /// Only keep messages that @-mention this user (ID or @name)
+        #[arg(long, value_name = "USER")]
+        mentions: Option<String>,
+
+        /// Show oldest first with a separator line between days
+        #[arg(long)]
+        group_by_day: bool,
+
+        /// Only fetch messages newer than the last --since-last-run for this channel
+        #[arg(long, conflicts_with_all = ["latest", "oldest"])]
+        since_last_run: bool,
+
+        /// Pad author handles to a column so message headers line up (N characters, or auto)
+        #[arg(long, value_name = "N|auto", num_args = 0..=1, default_missing_value = "auto")]
+        author_name_width: Option<AuthorWidth>,
+
+        /// Resolve canonical permalinks via chat.getPermalink for messages without one (cached; bounded per run)
+        #[arg(long)]
+        accurate_links: bool,
+
+        /// Instead of the messages, show how many were posted from each author timezone
+        #[arg(long)]
+        user_timezone_summary: bool,
+
+        /// Instead of the messages, show mean/median/p90 time to first reply on thread roots
+        #[arg(long, conflicts_with = "user_timezone_summary")]
+        author_stats: bool,
+
+        /// Collapse repeated copies of a message, keeping the latest edit (default with --since-last-run)
+        #[arg(long, alias = "dedupe-edits", overrides_with = "no_dedupe")]
+        dedupe: bool,
+
+        /// Keep repeated copies of a message as fetched
+        #[arg(long, overrides_with = "dedupe")]
+        no_dedupe: bool,
+
+        /// Print each message on one line: [time] #channel @user: first 80 characters
+        #[arg(long, conflicts_with_all = ["group_by_day", "author_name_width", "user_timezone_summary", "author_stats"])]
+        oneline: bool,
+
+        /// Fetch the whole history (back to --oldest) into the cache and print only a count
+        #[arg(long, alias = "save-cache-only", conflicts_with_all = ["latest", "since_last_run"])]
+        warm: bool,
+
+        /// Instead of the messages, list who posted in the window, busiest first (every page back to --oldest)
+        #[arg(
+            long,
+            alias = "participants-only",
+            conflicts_with_all = ["user_timezone_summary", "author_stats", "oneline", "group_by_day", "author_name_width", "warm"]
+        )]
+        participants: bool,
     },
     /// Get all replies in a conversation thread
+    #[command(after_help = "Examples:\n  clack conversations replies general 1700000000.000100\n  clack conversations replies --thread-ts-from-permalink https://acme.slack.com/archives/C0123ABCD/p1700000000000100")]
     Replies {
-        /// Channel ID or name (e.g., C1234ABCD, #general, or general)
-        channel: String,
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
+        #[arg(required_unless_present = "thread_ts_from_permalink")]
+        channel: Option<String>,
 
         /// Message timestamp/ID (e.g., 1234567890.123456)
-        message_ts: String,
+        #[arg(required_unless_present = "thread_ts_from_permalink")]
+        message_ts: Option<String>,
+
+        /// Read the channel and thread from a message permalink; links to a reply are resolved to the thread root
+        #[arg(long, value_name = "URL", conflicts_with_all = ["channel", "message_ts"])]
+        thread_ts_from_permalink: Option<String>,
+
+        /// Save the whole thread to FILE: JSON for .json, YAML for .yaml/.yml, Markdown otherwise
+        #[arg(long, value_name = "FILE")]
+        export: Option<PathBuf>,
     },
     /// Get list of members in a conversation
     Members {
-        /// Channel ID or name (e.g., C1234ABCD, #general, or general)
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
         channel: String,
 
         /// Maximum number of members to retrieve
         #[arg(long, default_value = "200")]
         limit: u32,
     },
+    /// Compare the members of two conversations
+    MembersDiff {
+        /// First channel ID or name
+        channel_a: String,
+
+        /// Second channel ID or name
+        channel_b: String,
+
+        /// Print `<side>\t<user_id>` lines (side is a, b or both) without resolving names
+        #[arg(long)]
+        ids_only: bool,
+
+        /// Members fetched per page
+        #[arg(long, default_value = "200")]
+        limit: u32,
+    },
+    /// Rank the most-used reactions in a channel
+    Reactions {
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
+        channel: String,
+
+        /// Only scan messages from the last N days (default: full history)
+        #[arg(long)]
+        days: Option<u32>,
+
+        /// Number of entries to show in each ranking
+        #[arg(long, default_value = "10")]
+        top: usize,
+
+        /// Only rank emoji, skip the top reactors list
+        #[arg(long)]
+        emoji_only: bool,
+    },
+    /// Find messages matching a regex, optionally previewing a replacement (read-only)
+    #[command(after_help = "Examples:\n  clack conversations grep general 'deploy(ed)?' -i --days 7\n  clack conversations grep general 'http://(\\S+)' --replace 'https://$1'")]
+    Grep {
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
+        channel: String,
+
+        /// Regular expression to match against message text
+        pattern: String,
+
+        /// Show what each match would look like replaced ($1, ${name} refer to groups); nothing is edited
+        #[arg(long, value_name = "TEMPLATE")]
+        replace: Option<String>,
+
+        /// Match case-insensitively
+        #[arg(long, short = 'i')]
+        ignore_case: bool,
+
+        /// Only scan messages from the last N days (default: full history)
+        #[arg(long)]
+        days: Option<u32>,
+    },
+    /// Print the permalink of the most recent message in a channel
+    OpenLast {
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
+        channel: String,
+    },
+    /// Leave every channel you're in whose name contains a substring
+    Leave {
+        /// Case-insensitive substring of the channel names to leave
+        #[arg(long, value_parser = non_blank)]
+        matching: String,
+
+        /// List the channels that would be left without leaving them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Create a channel, optionally set up from a TOML template
+    Create {
+        /// Channel name (lowercase, no spaces; a leading # is ignored)
+        name: String,
+
+        /// Create a private channel
+        #[arg(long)]
+        private: bool,
+
+        /// TOML file with topic, purpose, members, message and bookmarks to apply after creating
+        #[arg(long, value_name = "FILE")]
+        from_template: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum SearchType {
     /// Search messages
+    #[command(after_help = "Examples:\n  clack search messages \"deploy\" --from @alice --during week\n  clack search messages \"outage\" --channel incidents --after 2024-06-01 --oneline")]
     Messages {
         /// Search query
         query: String,
@@ -207,15 +691,32 @@ pub enum SearchType {
         #[arg(long)]
         during: Option<String>,
 
+        /// Only keep matches with ts after this Slack timestamp (filters fetched page only)
+        #[arg(long, value_name = "TS", value_parser = slack_ts)]
+        after_ts: Option<(i64, u32)>,
+
+        /// Only keep matches with ts before this Slack timestamp (filters fetched page only)
+        #[arg(long, value_name = "TS", value_parser = slack_ts)]
+        before_ts: Option<(i64, u32)>,
+
         /// Page number (1-indexed)
         #[arg(long, default_value = "1")]
         page: u32,
 
         /// Maximum number of results per page
-        #[arg(long, default_value = "20")]
+        #[arg(long, alias = "per-page", default_value = "20")]
         limit: u32,
+
+        /// In json/yaml, embed each match's resolved user and channel objects
+        #[arg(long, alias = "json-with-context")]
+        enrich: bool,
+
+        /// Print each match on one line: [time] #channel @user: first 80 characters
+        #[arg(long)]
+        oneline: bool,
     },
     /// Search files
+    #[command(after_help = "Examples:\n  clack search files \"roadmap\" --from @alice\n  clack search files \"invoice\" --after 2024-01-01 --format json")]
     Files {
         /// Search query
         query: String,
@@ -249,7 +750,7 @@ pub enum SearchType {
         page: u32,
 
         /// Maximum number of results per page
-        #[arg(long, default_value = "20")]
+        #[arg(long, alias = "per-page", default_value = "20")]
         limit: u32,
     },
     /// Search all (messages and files)
@@ -261,15 +762,28 @@ pub enum SearchType {
         #[arg(long, alias = "in")]
         channel: Option<String>,
 
+        /// Merge messages and files into one timeline, newest first
+        #[arg(long)]
+        interleave: bool,
+
+        /// Show only the matching messages, as `search messages` would
+        #[arg(long, conflicts_with_all = ["files_only", "interleave"])]
+        messages_only: bool,
+
+        /// Show only the matching files, as `search files` would
+        #[arg(long, conflicts_with = "interleave")]
+        files_only: bool,
+
         /// Page number (1-indexed)
         #[arg(long, default_value = "1")]
         page: u32,
 
         /// Maximum number of results per page
-        #[arg(long, default_value = "20")]
+        #[arg(long, alias = "per-page", default_value = "20")]
         limit: u32,
     },
     /// Search channels by name
+    #[command(after_help = "Examples:\n  clack search channels eng\n  clack search channels incident --only-archived")]
     Channels {
         /// Search query (channel name substring)
         query: String,
@@ -277,14 +791,27 @@ pub enum SearchType {
         /// Include archived channels
         #[arg(long)]
         include_archived: bool,
+
+        /// Show only archived channels
+        #[arg(long)]
+        only_archived: bool,
+
+        /// Only show public channels
+        #[arg(long, conflicts_with = "private_only")]
+        public_only: bool,
+
+        /// Only show private channels
+        #[arg(long)]
+        private_only: bool,
     },
 }
 
 #[derive(Subcommand)]
 pub enum FilesCommands {
     /// List files in the workspace
+    #[command(after_help = "Examples:\n  clack files list --user U0123ABCD\n  clack files list --count 1000 --group-by user\n  clack files list --count 5000 --unused --older-than 90")]
     List {
-        /// Maximum number of files to return
+        /// Files per page; only the first page is shown unless --count asks for more
         #[arg(long, default_value = "200")]
         limit: u32,
 
@@ -295,11 +822,32 @@ pub enum FilesCommands {
         /// Filter by channel (channel ID or name)
         #[arg(long)]
         channel: Option<String>,
+
+        /// Print file counts and total size per uploader or per conversation instead of the list
+        #[arg(long, value_enum, value_name = "BY")]
+        group_by: Option<FileGroupBy>,
+
+        /// Print only files not shared in any channel or DM, with their total size
+        #[arg(long, conflicts_with = "group_by")]
+        unused: bool,
+
+        /// With --unused, only files uploaded at least this many days ago
+        #[arg(long, value_name = "DAYS", requires = "unused")]
+        older_than: Option<u64>,
     },
     /// Get information about a specific file
+    #[command(after_help = "Examples:\n  clack files info F0123ABCD --channels --versions")]
     Info {
         /// File ID (e.g., F1234ABCD)
         file_id: String,
+
+        /// Show the channels and DMs the file is shared in, by name
+        #[arg(long)]
+        channels: bool,
+
+        /// List the file's revisions and their dates, when Slack returns them
+        #[arg(long)]
+        versions: bool,
     },
 }
 
@@ -307,12 +855,12 @@ pub enum FilesCommands {
 pub enum PinsCommands {
     /// List pinned items in a channel
     List {
-        /// Channel ID or name (e.g., C1234ABCD, #general, or general)
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
         channel: String,
     },
     /// Pin a message to a channel
     Add {
-        /// Channel ID or name (e.g., C1234ABCD, #general, or general)
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
         channel: String,
 
         /// Message timestamp to pin (e.g., 1234567890.123456)
@@ -320,7 +868,7 @@ pub enum PinsCommands {
     },
     /// Remove a pin from a channel
     Remove {
-        /// Channel ID or name (e.g., C1234ABCD, #general, or general)
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
         channel: String,
 
         /// Message timestamp to unpin (e.g., 1234567890.123456)
@@ -331,34 +879,45 @@ pub enum PinsCommands {
 #[derive(Subcommand)]
 pub enum ReactionsCommands {
     /// Add a reaction to a message
+    #[command(after_help = "Examples:\n  clack reactions add general 1700000000.000100 thumbsup")]
     Add {
-        /// Channel ID or name (e.g., C1234ABCD, #general, or general)
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
         channel: String,
 
         /// Message timestamp (e.g., 1234567890.123456)
         message_ts: String,
 
-        /// Emoji name (without colons, e.g., thumbsup, heart, rocket)
+        /// Emoji name (e.g., thumbsup, heart, rocket) or the emoji itself (👍)
         emoji: String,
+
+        /// Check the name against the workspace's emoji (emoji.list) before reacting
+        #[arg(long)]
+        validate: bool,
     },
     /// Remove a reaction from a message
     Remove {
-        /// Channel ID or name (e.g., C1234ABCD, #general, or general)
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
         channel: String,
 
         /// Message timestamp (e.g., 1234567890.123456)
         message_ts: String,
 
-        /// Emoji name (without colons, e.g., thumbsup, heart, rocket)
-        emoji: String,
+        /// Emoji name (e.g., thumbsup, heart, rocket) or the emoji itself (👍)
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        emoji: Option<String>,
+
+        /// Remove every reaction you added to the message
+        #[arg(long)]
+        all: bool,
     },
 }
 
 #[derive(Subcommand)]
 pub enum ChatCommands {
     /// Post a message to a channel
+    #[command(after_help = "Examples:\n  clack chat post general \"Deploy finished\"\n  clack chat post general \"Looks good\" --thread-ts 1700000000.000100\n  clack chat post general \"Today's report\" --file report.pdf")]
     Post {
-        /// Channel ID or name (e.g., C1234ABCD, #general, or general)
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
         channel: String,
 
         /// Message text (use - to read from stdin)
@@ -367,13 +926,61 @@ pub enum ChatCommands {
         /// Thread timestamp to reply to (makes this a thread reply)
         #[arg(long)]
         thread_ts: Option<String>,
+
+        /// Upload this file and share it with the text as its comment
+        #[arg(long, value_name = "PATH")]
+        file: Option<PathBuf>,
+
+        /// Post without showing the preview and asking first (the prompt only appears on a terminal)
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Get the permalink for a message
+    Permalink {
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
+        channel: String,
+
+        /// Message timestamp (e.g., 1234567890.123456)
+        message_ts: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CacheCommands {
+    /// Export the current workspace's cache to a JSON file
+    Export {
+        /// Destination file (e.g., cache-backup.json)
+        file: PathBuf,
+    },
+    /// Import a JSON cache export, upserting into the local cache
+    Import {
+        /// File produced by `clack cache export`
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ExportCommands {
+    /// Write every thread in a channel to its own file, named by the root ts
+    #[command(after_help = "Examples:\n  clack export threads general --output-dir ./threads")]
+    Threads {
+        /// Channel ID or name (e.g., C1234ABCD, #general, or general), or @user for a DM
+        channel: String,
+
+        /// Directory for the thread files (created if missing); existing files are skipped
+        #[arg(long, value_name = "DIR")]
+        output_dir: PathBuf,
     },
 }
 
 #[derive(Subcommand)]
 pub enum AuthType {
     /// Test authentication and display workspace metadata
-    Test,
+    Test {
+        /// Check every token in this file (one per line) instead of SLACK_TOKEN
+        #[arg(long, value_name = "FILE")]
+        tokens_file: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -407,9 +1014,88 @@ pub enum StreamSearchType {
         /// Filter by attachment type (link, file, image, etc.)
         #[arg(long)]
         has: Option<String>,
+
+        /// Include messages posted by bots and integrations (default)
+        #[arg(long, overrides_with = "exclude_bots")]
+        include_bots: bool,
+
+        /// Hide messages posted by bots and integrations
+        #[arg(long, overrides_with = "include_bots")]
+        exclude_bots: bool,
     },
 }
 
+/// Reject empty or whitespace-only values for filters that would otherwise match everything
+fn non_blank(value: &str) -> Result<String, String> {
+    if value.trim().is_empty() {
+        return Err("value must not be empty".to_string());
+    }
+    Ok(value.to_string())
+}
+
+/// `--count` is a positive number of items
+fn total_count(value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(0) => Err("must be at least 1".to_string()),
+        Ok(n) => Ok(n),
+        Err(_) => Err(format!("'{}' is not a number", value)),
+    }
+}
+
+/// `--max-text-length` needs room for at least one character plus the "..." marker
+fn max_text_length(value: &str) -> Result<usize, String> {
+    let n: usize = value.parse().map_err(|_| format!("'{}' is not a number", value))?;
+    if n < 4 {
+        return Err("must be at least 4 (one character plus \"...\")".to_string());
+    }
+    Ok(n)
+}
+
+/// A Slack ts such as `1717250400.000100`, kept exact as (seconds, microseconds)
+fn slack_ts(value: &str) -> Result<(i64, u32), String> {
+    crate::models::message::parse_ts(value)
+        .ok_or_else(|| format!("'{}' is not a Slack timestamp (seconds, optionally with up to 6 decimals)", value))
+}
+
+/// An http(s) API root, without the trailing slash endpoints are joined with
+fn base_url(value: &str) -> Result<String, String> {
+    if !(value.starts_with("http://") || value.starts_with("https://")) {
+        return Err(format!("'{}' is not an http:// or https:// URL", value));
+    }
+    Ok(value.trim_end_matches('/').to_string())
+}
+
+/// `--interval` for streaming: a number of seconds, optionally fractional, or a number
+/// with an `ms`, `s`, `m` or `h` suffix. Shorter than MIN_POLL_INTERVAL is rejected.
+fn poll_interval(value: &str) -> Result<std::time::Duration, String> {
+    let interval = duration(value)?;
+    if interval < crate::stream::MIN_POLL_INTERVAL {
+        return Err(format!(
+            "must be at least {}s to avoid hammering the Slack API",
+            crate::stream::MIN_POLL_INTERVAL.as_secs()
+        ));
+    }
+    Ok(interval)
+}
+
+/// A number of seconds, optionally fractional, or a number with an `ms`, `s`, `m` or `h` suffix
+fn duration(value: &str) -> Result<std::time::Duration, String> {
+    let value = value.trim();
+    let split = value.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("'{}' is not a duration (try 10, 2.5s, 1500ms or 2m)", value))?;
+    let seconds = match unit.trim() {
+        "" | "s" => number,
+        "ms" => number / 1000.0,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        other => return Err(format!("unknown unit '{}' (use ms, s, m or h)", other)),
+    };
+    std::time::Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -431,14 +1117,111 @@ mod tests {
                 UsersCommands::List {
                     limit,
                     include_deleted,
+                    only_deleted,
+                    team_id,
+                    presence,
+                    email_domain,
+                    humans_only,
                 } => {
                     assert_eq!(limit, 50);
                     assert!(include_deleted);
+                    assert!(!only_deleted);
+                    assert_eq!(team_id, None);
+                    assert!(!presence);
+                    assert_eq!(email_domain, None);
+                    assert!(!humans_only);
                 }
                 _ => panic!("Expected Users List command"),
             },
             _ => panic!("Expected Users command"),
         }
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--team-id", "T0123ABCD"]);
+        match cli.command {
+            Commands::Users {
+                command: UsersCommands::List { team_id, .. },
+            } => assert_eq!(team_id.as_deref(), Some("T0123ABCD")),
+            _ => panic!("Expected Users List command"),
+        }
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--presence"]);
+        match cli.command {
+            Commands::Users {
+                command: UsersCommands::List { presence, .. },
+            } => assert!(presence),
+            _ => panic!("Expected Users List command"),
+        }
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--email-domain", "corp.com", "--humans-only"]);
+        match cli.command {
+            Commands::Users {
+                command: UsersCommands::List { email_domain, humans_only, .. },
+            } => assert!(email_domain.as_deref() == Some("corp.com") && humans_only),
+            _ => panic!("Expected Users List command"),
+        }
+    }
+
+    #[test]
+    fn test_only_deleted_and_only_archived() {
+        let cli = Cli::parse_from(["clack", "users", "list", "--only-deleted"]);
+        match cli.command {
+            Commands::Users {
+                command: UsersCommands::List { only_deleted, include_deleted, .. },
+            } => assert!(only_deleted && !include_deleted),
+            _ => panic!("Expected Users List command"),
+        }
+
+        let cli = Cli::parse_from(["clack", "conversations", "list", "--only-archived"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::List { only_archived, .. },
+            } => assert!(only_archived),
+            _ => panic!("Expected Conversations List command"),
+        }
+
+        let cli = Cli::parse_from(["clack", "search", "channels", "old", "--only-archived"]);
+        match cli.command {
+            Commands::Search {
+                search_type: SearchType::Channels { only_archived, .. },
+            } => assert!(only_archived),
+            _ => panic!("Expected Search Channels command"),
+        }
+    }
+
+    #[test]
+    fn test_warm_commands() {
+        let cli = Cli::parse_from(["clack", "warm", "general", "#random", "--oldest", "1700000000"]);
+        match cli.command {
+            Commands::Warm { channels, oldest } => {
+                assert_eq!(channels, vec!["general", "#random"]);
+                assert_eq!(oldest.as_deref(), Some("1700000000"));
+            }
+            _ => panic!("Expected Warm command"),
+        }
+        assert!(Cli::try_parse_from(["clack", "warm"]).is_err());
+
+        for flag in ["--warm", "--save-cache-only"] {
+            let cli = Cli::parse_from(["clack", "conversations", "history", "general", flag]);
+            match cli.command {
+                Commands::Conversations {
+                    command: ConversationsCommands::History { warm, .. },
+                } => assert!(warm),
+                _ => panic!("Expected Conversations History command"),
+            }
+        }
+        assert!(Cli::try_parse_from(["clack", "conversations", "history", "general", "--warm", "--since-last-run"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_users_diff_command() {
+        let cli = Cli::parse_from(["clack", "users", "diff", "--limit", "500"]);
+        match cli.command {
+            Commands::Users {
+                command: UsersCommands::Diff { limit },
+            } => assert_eq!(limit, 500),
+            _ => panic!("Expected Users Diff command"),
+        }
     }
 
     #[test]
@@ -446,7 +1229,7 @@ mod tests {
         let cli = Cli::parse_from(["clack", "users", "info", "U123"]);
         match cli.command {
             Commands::Users { command } => match command {
-                UsersCommands::Info { user_id } => assert_eq!(user_id, "U123"),
+                UsersCommands::Info { users } => assert_eq!(users, vec!["U123"]),
                 _ => panic!("Expected Users Info command"),
             },
             _ => panic!("Expected Users command"),
@@ -454,15 +1237,30 @@ mod tests {
     }
 
     #[test]
-    fn test_conversations_history_command_basic() {
-        let cli = Cli::parse_from(["clack", "conversations", "history", "C123"]);
+    fn test_users_info_command_multiple() {
+        let cli = Cli::parse_from(["clack", "users", "info", "U123", "@alice", "W456"]);
         match cli.command {
-            Commands::Conversations { command } => match command {
-                ConversationsCommands::History {
+            Commands::Users { command } => match command {
+                UsersCommands::Info { users } => assert_eq!(users, vec!["U123", "@alice", "W456"]),
+                _ => panic!("Expected Users Info command"),
+            },
+            _ => panic!("Expected Users command"),
+        }
+
+        assert!(Cli::try_parse_from(["clack", "users", "info"]).is_err());
+    }
+
+    #[test]
+    fn test_conversations_history_command_basic() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "C123"]);
+        match cli.command {
+            Commands::Conversations { command } => match command {
+                ConversationsCommands::History {
                     channel,
                     limit,
                     latest,
                     oldest,
+                    ..
                 } => {
                     assert_eq!(channel, "C123");
                     assert_eq!(limit, 200); // default value
@@ -496,6 +1294,7 @@ mod tests {
                     limit,
                     latest,
                     oldest,
+                    ..
                 } => {
                     assert_eq!(channel, "C123");
                     assert_eq!(limit, 50);
@@ -508,6 +1307,250 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_files_info_channels() {
+        let cli = Cli::parse_from(["clack", "files", "info", "F123", "--channels"]);
+        match cli.command {
+            Commands::Files {
+                command: FilesCommands::Info {
+                    file_id,
+                    channels,
+                    versions,
+                },
+            } => {
+                assert_eq!(file_id, "F123");
+                assert!(channels);
+                assert!(!versions);
+            }
+            _ => panic!("Expected Files Info command"),
+        }
+    }
+
+    /// Split an example line like a shell would, for the quoting the examples use
+    fn split_example(line: &str) -> Vec<String> {
+        let mut args = Vec::new();
+        let mut current = String::new();
+        let mut quote = None;
+        for c in line.chars() {
+            match (quote, c) {
+                (None, '"' | '\'') => quote = Some(c),
+                (Some(q), _) if c == q => quote = None,
+                (None, ' ') if !current.is_empty() => args.push(std::mem::take(&mut current)),
+                (None, ' ') => {}
+                _ => current.push(c),
+            }
+        }
+        args.push(current);
+        args
+    }
+
+    #[test]
+    fn test_help_examples_parse() {
+        use clap::CommandFactory;
+
+        fn check(command: &clap::Command, checked: &mut usize) {
+            if let Some(help) = command.get_after_help() {
+                for line in help.to_string().lines().filter(|l| l.trim_start().starts_with("clack ")) {
+                    let args = split_example(line.trim());
+                    assert!(Cli::try_parse_from(&args).is_ok(), "example doesn't parse: {}", line);
+                    *checked += 1;
+                }
+            }
+            for sub in command.get_subcommands() {
+                check(sub, checked);
+            }
+        }
+
+        let mut checked = 0;
+        check(&Cli::command(), &mut checked);
+        assert!(checked > 20);
+    }
+
+    #[test]
+    fn test_version_command() {
+        let cli = Cli::parse_from(["clack", "version", "--json"]);
+        assert!(matches!(cli.command, Commands::Version { json: true }));
+        let cli = Cli::parse_from(["clack", "version"]);
+        assert!(matches!(cli.command, Commands::Version { json: false }));
+    }
+
+    #[test]
+    fn test_files_list_group_by() {
+        let cli = Cli::parse_from(["clack", "files", "list", "--group-by", "channel"]);
+        match cli.command {
+            Commands::Files {
+                command: FilesCommands::List { group_by, .. },
+            } => assert_eq!(group_by, Some(FileGroupBy::Channel)),
+            _ => panic!("Expected Files List command"),
+        }
+        assert!(Cli::try_parse_from(["clack", "files", "list", "--group-by", "type"]).is_err());
+    }
+
+    #[test]
+    fn test_search_all_only_one_half() {
+        let cli = Cli::parse_from(["clack", "search", "all", "deploy", "--files-only"]);
+        match cli.command {
+            Commands::Search {
+                search_type: SearchType::All { messages_only, files_only, .. },
+            } => assert!(files_only && !messages_only),
+            _ => panic!("Expected Search All command"),
+        }
+        assert!(Cli::try_parse_from(["clack", "search", "all", "deploy", "--messages-only", "--files-only"]).is_err());
+        assert!(Cli::try_parse_from(["clack", "search", "all", "deploy", "--messages-only", "--interleave"]).is_err());
+    }
+
+    #[test]
+    fn test_files_list_unused() {
+        let cli = Cli::parse_from(["clack", "files", "list", "--unused", "--older-than", "90"]);
+        match cli.command {
+            Commands::Files {
+                command: FilesCommands::List { unused, older_than, .. },
+            } => {
+                assert!(unused);
+                assert_eq!(older_than, Some(90));
+            }
+            _ => panic!("Expected Files List command"),
+        }
+        assert!(Cli::try_parse_from(["clack", "files", "list", "--older-than", "90"]).is_err());
+        assert!(Cli::try_parse_from(["clack", "files", "list", "--unused", "--group-by", "user"]).is_err());
+    }
+
+    #[test]
+    fn test_conversations_history_accurate_links() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "general", "--accurate-links"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::History { accurate_links, .. },
+            } => assert!(accurate_links),
+            _ => panic!("Expected Conversations History command"),
+        }
+    }
+
+    #[test]
+    fn test_conversations_history_user_timezone_summary() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "general", "--user-timezone-summary"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::History { user_timezone_summary, .. },
+            } => assert!(user_timezone_summary),
+            _ => panic!("Expected Conversations History command"),
+        }
+    }
+
+    #[test]
+    fn test_conversations_history_dedupe_flags() {
+        let parse = |args: &[&str]| -> (bool, bool) {
+            let cli = Cli::parse_from([&["clack", "conversations", "history", "general"], args].concat());
+            match cli.command {
+                Commands::Conversations {
+                    command: ConversationsCommands::History { dedupe, no_dedupe, .. },
+                } => (dedupe, no_dedupe),
+                _ => panic!("Expected Conversations History command"),
+            }
+        };
+        assert_eq!(parse(&[]), (false, false));
+        assert_eq!(parse(&["--dedupe-edits"]), (true, false));
+        assert_eq!(parse(&["--dedupe", "--no-dedupe"]), (false, true));
+    }
+
+    #[test]
+    fn test_conversations_history_author_name_width() {
+        let author_width = |args: &[&str]| {
+            let cli = Cli::parse_from(args);
+            match cli.command {
+                Commands::Conversations {
+                    command: ConversationsCommands::History { author_name_width, .. },
+                } => author_name_width,
+                _ => panic!("Expected Conversations History command"),
+            }
+        };
+
+        assert_eq!(author_width(&["clack", "conversations", "history", "C123"]), None);
+        assert_eq!(
+            author_width(&["clack", "conversations", "history", "C123", "--author-name-width"]),
+            Some(AuthorWidth::Auto)
+        );
+        assert_eq!(
+            author_width(&["clack", "conversations", "history", "C123", "--author-name-width", "18"]),
+            Some(AuthorWidth::Fixed(18))
+        );
+        assert!(Cli::try_parse_from(["clack", "conversations", "history", "C123", "--author-name-width", "wide"]).is_err());
+    }
+
+    #[test]
+    fn test_conversations_history_bot_filters() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "C123"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::History { exclude_bots, .. },
+            } => assert!(!exclude_bots),
+            _ => panic!("Expected Conversations History command"),
+        }
+
+        let cli = Cli::parse_from(["clack", "conversations", "history", "C123", "--exclude-bots"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::History { exclude_bots, .. },
+            } => assert!(exclude_bots),
+            _ => panic!("Expected Conversations History command"),
+        }
+
+        // The last of --include-bots/--exclude-bots wins
+        let cli = Cli::parse_from([
+            "clack",
+            "conversations",
+            "history",
+            "C123",
+            "--exclude-bots",
+            "--include-bots",
+        ]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::History { exclude_bots, .. },
+            } => assert!(!exclude_bots),
+            _ => panic!("Expected Conversations History command"),
+        }
+    }
+
+    #[test]
+    fn test_conversations_history_participants() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "general", "--participants-only"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::History { participants, .. },
+            } => assert!(participants),
+            _ => panic!("Expected Conversations History command"),
+        }
+        assert!(Cli::try_parse_from(["clack", "conversations", "history", "general", "--participants", "--oneline"]).is_err());
+    }
+
+    #[test]
+    fn test_conversations_history_author_stats() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "support", "--author-stats", "--oldest", "1700000000"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::History { author_stats, .. },
+            } => assert!(author_stats),
+            _ => panic!("Expected Conversations History command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "clack", "conversations", "history", "support", "--author-stats", "--user-timezone-summary"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_conversations_history_mentions() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "C123", "--mentions", "@alice"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::History { mentions, .. },
+            } => assert_eq!(mentions.as_deref(), Some("@alice")),
+            _ => panic!("Expected Conversations History command"),
+        }
+    }
+
     #[test]
     fn test_global_format_option() {
         let cli = Cli::parse_from(["clack", "--format", "json", "users", "list"]);
@@ -520,10 +1563,39 @@ mod tests {
         assert!(cli.no_color);
     }
 
+    #[test]
+    fn test_global_color_option() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert_eq!(cli.color_choice(), ColorChoice::Auto);
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--color", "always"]);
+        assert_eq!(cli.color_choice(), ColorChoice::Always);
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--color", "always", "--no-color"]);
+        assert_eq!(cli.color_choice(), ColorChoice::Never);
+
+        assert!(Cli::try_parse_from(["clack", "users", "list", "--color", "sometimes"]).is_err());
+    }
+
     #[test]
     fn test_global_verbose_option() {
         let cli = Cli::parse_from(["clack", "-v", "users", "list"]);
         assert!(cli.verbose);
+        assert_eq!(cli.effective_log_level(), LogLevel::Debug);
+    }
+
+    #[test]
+    fn test_global_log_level_option() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert_eq!(cli.effective_log_level(), LogLevel::Warn);
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--log-level", "trace"]);
+        assert_eq!(cli.effective_log_level(), LogLevel::Trace);
+
+        let cli = Cli::parse_from(["clack", "-v", "users", "list", "--log-level", "error"]);
+        assert_eq!(cli.effective_log_level(), LogLevel::Error);
+
+        assert!(Cli::try_parse_from(["clack", "users", "list", "--log-level", "loud"]).is_err());
     }
 
     #[test]
@@ -534,9 +1606,10 @@ mod tests {
                 ConversationsCommands::Replies {
                     channel,
                     message_ts,
+                    ..
                 } => {
-                    assert_eq!(channel, "C123");
-                    assert_eq!(message_ts, "1234567890.123456");
+                    assert_eq!(channel.as_deref(), Some("C123"));
+                    assert_eq!(message_ts.as_deref(), Some("1234567890.123456"));
                 }
                 _ => panic!("Expected Conversations Replies command"),
             },
@@ -544,6 +1617,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_conversations_replies_from_permalink() {
+        let link = "https://acme.slack.com/archives/C123/p1700000300000100";
+        let cli = Cli::parse_from(["clack", "conversations", "replies", "--thread-ts-from-permalink", link]);
+        match cli.command {
+            Commands::Conversations {
+                command:
+                    ConversationsCommands::Replies {
+                        channel,
+                        message_ts,
+                        thread_ts_from_permalink,
+                        ..
+                    },
+            } => {
+                assert_eq!(channel, None);
+                assert_eq!(message_ts, None);
+                assert_eq!(thread_ts_from_permalink.as_deref(), Some(link));
+            }
+            _ => panic!("Expected Conversations Replies command"),
+        }
+
+        // Either the permalink or both positionals, never a mix
+        assert!(Cli::try_parse_from(["clack", "conversations", "replies", "C123"]).is_err());
+        assert!(Cli::try_parse_from(["clack", "conversations", "replies", "C123", "--thread-ts-from-permalink", link])
+            .is_err());
+    }
+
+    #[test]
+    fn test_conversations_grep() {
+        let cli = Cli::parse_from([
+            "clack", "conversations", "grep", "general", "api[_-]?key", "-i", "--replace", "[redacted]", "--days", "7",
+        ]);
+        match cli.command {
+            Commands::Conversations {
+                command:
+                    ConversationsCommands::Grep {
+                        channel,
+                        pattern,
+                        replace,
+                        ignore_case,
+                        days,
+                    },
+            } => {
+                assert_eq!(channel, "general");
+                assert_eq!(pattern, "api[_-]?key");
+                assert_eq!(replace.as_deref(), Some("[redacted]"));
+                assert!(ignore_case);
+                assert_eq!(days, Some(7));
+            }
+            _ => panic!("Expected Conversations Grep command"),
+        }
+    }
+
+    #[test]
+    fn test_conversations_replies_export() {
+        let cli = Cli::parse_from(["clack", "conversations", "replies", "general", "1.2", "--export", "thread.md"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::Replies { export, .. },
+            } => assert_eq!(export, Some(PathBuf::from("thread.md"))),
+            _ => panic!("Expected Conversations Replies command"),
+        }
+    }
+
     #[test]
     fn test_conversations_replies_command_with_channel_name() {
         let cli = Cli::parse_from(["clack", "conversations", "replies", "#general", "1234567890.123456"]);
@@ -552,9 +1689,10 @@ mod tests {
                 ConversationsCommands::Replies {
                     channel,
                     message_ts,
+                    ..
                 } => {
-                    assert_eq!(channel, "#general");
-                    assert_eq!(message_ts, "1234567890.123456");
+                    assert_eq!(channel.as_deref(), Some("#general"));
+                    assert_eq!(message_ts.as_deref(), Some("1234567890.123456"));
                 }
                 _ => panic!("Expected Conversations Replies command"),
             },
@@ -567,9 +1705,16 @@ mod tests {
         let cli = Cli::parse_from(["clack", "conversations", "list"]);
         match cli.command {
             Commands::Conversations { command } => match command {
-                ConversationsCommands::List { include_archived, limit } => {
+                ConversationsCommands::List {
+                    include_archived,
+                    only_archived,
+                    limit,
+                    sort_channels_by_activity,
+                } => {
                     assert!(!include_archived);
+                    assert!(!only_archived);
                     assert_eq!(limit, 200); // default value
+                    assert!(!sort_channels_by_activity);
                 }
                 _ => panic!("Expected Conversations List command"),
             },
@@ -582,8 +1727,14 @@ mod tests {
         let cli = Cli::parse_from(["clack", "conversations", "list", "--include-archived"]);
         match cli.command {
             Commands::Conversations { command } => match command {
-                ConversationsCommands::List { include_archived, limit } => {
+                ConversationsCommands::List {
+                    include_archived,
+                    only_archived,
+                    limit,
+                    ..
+                } => {
                     assert!(include_archived);
+                    assert!(!only_archived);
                     assert_eq!(limit, 200); // default value
                 }
                 _ => panic!("Expected Conversations List command"),
@@ -592,13 +1743,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_conversations_list_sort_channels_by_activity() {
+        let cli = Cli::parse_from(["clack", "conversations", "list", "--sort-channels-by-activity"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::List { sort_channels_by_activity, .. },
+            } => assert!(sort_channels_by_activity),
+            _ => panic!("Expected Conversations List command"),
+        }
+    }
+
     #[test]
     fn test_conversations_info_command() {
         let cli = Cli::parse_from(["clack", "conversations", "info", "C123"]);
         match cli.command {
             Commands::Conversations { command } => match command {
-                ConversationsCommands::Info { channel } => {
+                ConversationsCommands::Info {
+                    channel,
+                    history_stats,
+                    days,
+                    raw,
+                } => {
                     assert_eq!(channel, "C123");
+                    assert!(!history_stats);
+                    assert_eq!(days, 30);
+                    assert!(!raw);
                 }
                 _ => panic!("Expected Conversations Info command"),
             },
@@ -606,6 +1776,108 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_conversations_info_history_stats() {
+        let cli = Cli::parse_from(["clack", "conversations", "info", "#general", "--history-stats", "--days", "7"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::Info { history_stats, days, .. },
+            } => {
+                assert!(history_stats);
+                assert_eq!(days, 7);
+            }
+            _ => panic!("Expected Conversations Info command"),
+        }
+
+        // --days only applies to the stats window
+        assert!(Cli::try_parse_from(["clack", "conversations", "info", "C123", "--days", "7"]).is_err());
+    }
+
+    #[test]
+    fn test_conversations_info_raw() {
+        let cli = Cli::parse_from(["clack", "conversations", "info", "#general", "--raw"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::Info { raw, .. },
+            } => assert!(raw),
+            _ => panic!("Expected Conversations Info command"),
+        }
+
+        // The raw object has no place for the stats
+        assert!(Cli::try_parse_from(["clack", "conversations", "info", "C123", "--raw", "--history-stats"]).is_err());
+    }
+
+    #[test]
+    fn test_history_since_last_run() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "general", "--since-last-run"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::History { since_last_run, .. },
+            } => assert!(since_last_run),
+            _ => panic!("Expected Conversations History command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "clack", "conversations", "history", "general", "--since-last-run", "--oldest", "1717250400",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_reactions_remove_all() {
+        let cli = Cli::parse_from(["clack", "reactions", "remove", "general", "1234567890.123456", "--all"]);
+        match cli.command {
+            Commands::Reactions {
+                command: ReactionsCommands::Remove { emoji, all, .. },
+            } => {
+                assert!(all);
+                assert_eq!(emoji, None);
+            }
+            _ => panic!("Expected Reactions Remove command"),
+        }
+
+        // Needs either an emoji or --all, not both
+        assert!(Cli::try_parse_from(["clack", "reactions", "remove", "general", "1.2"]).is_err());
+        assert!(Cli::try_parse_from(["clack", "reactions", "remove", "general", "1.2", "eyes", "--all"]).is_err());
+    }
+
+    #[test]
+    fn test_conversations_leave_matching() {
+        let cli = Cli::parse_from(["clack", "conversations", "leave", "--matching", "incident-", "--dry-run"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::Leave { matching, dry_run, yes },
+            } => {
+                assert_eq!(matching, "incident-");
+                assert!(dry_run);
+                assert!(!yes);
+            }
+            _ => panic!("Expected Conversations Leave command"),
+        }
+
+        assert!(Cli::try_parse_from(["clack", "conversations", "leave"]).is_err());
+        // A blank substring would match every channel
+        assert!(Cli::try_parse_from(["clack", "conversations", "leave", "--matching", ""]).is_err());
+        assert!(Cli::try_parse_from(["clack", "conversations", "leave", "--matching", "   "]).is_err());
+    }
+
+    #[test]
+    fn test_conversations_create_from_template() {
+        let cli = Cli::parse_from([
+            "clack", "conversations", "create", "#proj-falcon", "--private", "--from-template", "falcon.toml",
+        ]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::Create { name, private, from_template },
+            } => {
+                assert_eq!(name, "#proj-falcon");
+                assert!(private);
+                assert_eq!(from_template, Some(PathBuf::from("falcon.toml")));
+            }
+            _ => panic!("Expected Conversations Create command"),
+        }
+    }
+
     #[test]
     fn test_search_messages_basic() {
         let cli = Cli::parse_from(["clack", "search", "messages", "hello world"]);
@@ -635,6 +1907,72 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_messages_ts_range() {
+        let cli = Cli::parse_from([
+            "clack", "search", "messages", "error",
+            "--after-ts", "1717250400.000100", "--before-ts", "1717252200",
+        ]);
+        match cli.command {
+            Commands::Search {
+                search_type: SearchType::Messages { after_ts, before_ts, .. },
+            } => {
+                assert_eq!(after_ts, Some((1717250400, 100)));
+                assert_eq!(before_ts, Some((1717252200, 0)));
+            }
+            _ => panic!("Expected Search Messages command"),
+        }
+    }
+
+    #[test]
+    fn test_search_messages_per_page_alias() {
+        let cli = Cli::parse_from(["clack", "search", "messages", "error", "--per-page", "50", "--page", "3"]);
+        match cli.command {
+            Commands::Search {
+                search_type: SearchType::Messages { page, limit, .. },
+            } => {
+                assert_eq!(page, 3);
+                assert_eq!(limit, 50);
+            }
+            _ => panic!("Expected Search Messages command"),
+        }
+    }
+
+    #[test]
+    fn test_oneline_flag() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "general", "--oneline"]);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::History { oneline, .. },
+            } => assert!(oneline),
+            _ => panic!("Expected Conversations History command"),
+        }
+
+        let cli = Cli::parse_from(["clack", "search", "messages", "deploy", "--oneline"]);
+        match cli.command {
+            Commands::Search {
+                search_type: SearchType::Messages { oneline, .. },
+            } => assert!(oneline),
+            _ => panic!("Expected Search Messages command"),
+        }
+
+        assert!(Cli::try_parse_from(["clack", "conversations", "history", "general", "--oneline", "--group-by-day"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_search_messages_enrich() {
+        for flag in ["--enrich", "--json-with-context"] {
+            let cli = Cli::parse_from(["clack", "search", "messages", "deploy", flag]);
+            match cli.command {
+                Commands::Search {
+                    search_type: SearchType::Messages { enrich, .. },
+                } => assert!(enrich),
+                _ => panic!("Expected Search Messages command"),
+            }
+        }
+    }
+
     #[test]
     fn test_search_messages_with_filters() {
         let cli = Cli::parse_from([
@@ -720,6 +2058,7 @@ mod tests {
                 SearchType::Channels {
                     query,
                     include_archived,
+                    ..
                 } => {
                     assert_eq!(query, "engineering");
                     assert!(!include_archived);
@@ -738,6 +2077,7 @@ mod tests {
                 SearchType::Channels {
                     query,
                     include_archived,
+                    ..
                 } => {
                     assert_eq!(query, "old-project");
                     assert!(include_archived);
@@ -748,17 +2088,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_channels_visibility_filters() {
+        let cli = Cli::parse_from(["clack", "search", "channels", "eng", "--private-only"]);
+        match cli.command {
+            Commands::Search {
+                search_type: SearchType::Channels { public_only, private_only, .. },
+            } => assert!(private_only && !public_only),
+            _ => panic!("Expected Search Channels command"),
+        }
+
+        assert!(Cli::try_parse_from(["clack", "search", "channels", "eng", "--public-only", "--private-only"]).is_err());
+    }
+
     #[test]
     fn test_auth_test_command() {
         let cli = Cli::parse_from(["clack", "auth", "test"]);
         match cli.command {
             Commands::Auth { auth_type } => match auth_type {
-                AuthType::Test => {
-                    // Success - command parsed correctly
-                }
+                AuthType::Test { tokens_file } => assert_eq!(tokens_file, None),
             },
             _ => panic!("Expected Auth command"),
         }
+
+        let cli = Cli::parse_from(["clack", "auth", "test", "--tokens-file", "tokens.txt"]);
+        match cli.command {
+            Commands::Auth {
+                auth_type: AuthType::Test { tokens_file },
+            } => assert_eq!(tokens_file, Some(PathBuf::from("tokens.txt"))),
+            _ => panic!("Expected Auth command"),
+        }
     }
 
     #[test]
@@ -773,7 +2132,7 @@ mod tests {
         assert!(cli.refresh_cache);
         match cli.command {
             Commands::Conversations { command } => match command {
-                ConversationsCommands::Info { channel } => {
+                ConversationsCommands::Info { channel, .. } => {
                     assert_eq!(channel, "C123");
                 }
                 _ => panic!("Expected Conversations Info command"),
@@ -788,6 +2147,387 @@ mod tests {
         assert!(cli.refresh_cache);
     }
 
+    #[test]
+    fn test_conversations_members_diff_command() {
+        let cli = Cli::parse_from(["clack", "conversations", "members-diff", "#eng", "eng-private", "--ids-only"]);
+        match cli.command {
+            Commands::Conversations {
+                command:
+                    ConversationsCommands::MembersDiff {
+                        channel_a,
+                        channel_b,
+                        ids_only,
+                        limit,
+                    },
+            } => {
+                assert_eq!(channel_a, "#eng");
+                assert_eq!(channel_b, "eng-private");
+                assert!(ids_only);
+                assert_eq!(limit, 200);
+            }
+            _ => panic!("Expected Conversations MembersDiff command"),
+        }
+
+        assert!(Cli::try_parse_from(["clack", "conversations", "members-diff", "#eng"]).is_err());
+    }
+
+    #[test]
+    fn test_conversations_reactions_command() {
+        let cli = Cli::parse_from(["clack", "conversations", "reactions", "#general", "--days", "30", "--emoji-only"]);
+        match cli.command {
+            Commands::Conversations { command } => match command {
+                ConversationsCommands::Reactions {
+                    channel,
+                    days,
+                    top,
+                    emoji_only,
+                } => {
+                    assert_eq!(channel, "#general");
+                    assert_eq!(days, Some(30));
+                    assert_eq!(top, 10);
+                    assert!(emoji_only);
+                }
+                _ => panic!("Expected Reactions command"),
+            },
+            _ => panic!("Expected Conversations command"),
+        }
+    }
+
+    #[test]
+    fn test_export_threads_command() {
+        let cli = Cli::parse_from([
+            "clack", "export", "threads", "#incidents", "--output-dir", "archive", "--format", "markdown",
+        ]);
+        assert_eq!(cli.format, "markdown");
+        match cli.command {
+            Commands::Export {
+                command: ExportCommands::Threads { channel, output_dir },
+            } => {
+                assert_eq!(channel, "#incidents");
+                assert_eq!(output_dir, PathBuf::from("archive"));
+            }
+            _ => panic!("Expected Export Threads command"),
+        }
+        assert!(Cli::try_parse_from(["clack", "export", "threads", "general"]).is_err());
+    }
+
+    #[test]
+    fn test_cache_export_import_commands() {
+        let cli = Cli::parse_from(["clack", "cache", "export", "backup.json"]);
+        match cli.command {
+            Commands::Cache {
+                command: CacheCommands::Export { file },
+            } => assert_eq!(file, PathBuf::from("backup.json")),
+            _ => panic!("Expected Cache Export command"),
+        }
+
+        let cli = Cli::parse_from(["clack", "cache", "import", "backup.json"]);
+        match cli.command {
+            Commands::Cache {
+                command: CacheCommands::Import { file },
+            } => assert_eq!(file, PathBuf::from("backup.json")),
+            _ => panic!("Expected Cache Import command"),
+        }
+    }
+
+    #[test]
+    fn test_global_log_file_option() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert!(cli.log_file.is_none());
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--log-file", "/tmp/clack.ndjson"]);
+        assert_eq!(cli.log_file, Some(PathBuf::from("/tmp/clack.ndjson")));
+    }
+
+    #[test]
+    fn test_global_pretty_compact_options() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert!(!cli.pretty && !cli.compact);
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--format", "json", "--compact"]);
+        assert!(cli.compact && !cli.pretty);
+
+        // The last flag wins
+        let cli = Cli::parse_from(["clack", "users", "list", "--compact", "--pretty"]);
+        assert!(cli.pretty && !cli.compact);
+    }
+
+    #[test]
+    fn test_global_fail_fast_continue_options() {
+        let cli = Cli::parse_from(["clack", "users", "info", "U1", "U2"]);
+        assert!(!cli.fail_fast && !cli.continue_on_error);
+
+        let cli = Cli::parse_from(["clack", "users", "info", "U1", "--strict"]);
+        assert!(cli.fail_fast);
+
+        // The last flag wins
+        let cli = Cli::parse_from(["clack", "users", "info", "U1", "--fail-fast", "--continue"]);
+        assert!(cli.continue_on_error && !cli.fail_fast);
+    }
+
+    #[test]
+    fn test_hidden_retry_backoff_options() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert_eq!(cli.retry_config(), crate::api::client::RetryConfig::default());
+
+        let cli = Cli::parse_from([
+            "clack", "users", "list", "--retry-base-delay", "100ms", "--retry-multiplier", "3",
+            "--retry-max-delay", "2s", "--no-retry-jitter",
+        ]);
+        let config = cli.retry_config();
+        assert_eq!(config.base_delay, std::time::Duration::from_millis(100));
+        assert_eq!(config.multiplier, 3.0);
+        assert_eq!(config.max_delay, std::time::Duration::from_secs(2));
+        assert!(!config.jitter);
+    }
+
+    #[test]
+    fn test_global_count_option() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert_eq!(cli.count, None);
+
+        let cli = Cli::parse_from(["clack", "search", "messages", "deploy", "--limit", "100", "--count", "250"]);
+        assert_eq!(cli.count, Some(250));
+
+        assert!(Cli::try_parse_from(["clack", "files", "list", "--count", "0"]).is_err());
+        assert!(Cli::try_parse_from(["clack", "files", "list", "--count", "all"]).is_err());
+    }
+
+    #[test]
+    fn test_global_max_text_length_option() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert_eq!(cli.max_text_length, None);
+
+        let cli = Cli::parse_from(["clack", "conversations", "history", "general", "--max-text-length", "120"]);
+        assert_eq!(cli.max_text_length, Some(120));
+
+        // Anything shorter than the "..." marker plus one character is rejected
+        assert!(Cli::try_parse_from(["clack", "users", "list", "--max-text-length", "3"]).is_err());
+        assert!(Cli::try_parse_from(["clack", "users", "list", "--max-text-length", "x"]).is_err());
+        let cli = Cli::parse_from(["clack", "users", "list", "--max-text-length", "4"]);
+        assert_eq!(cli.max_text_length, Some(4));
+    }
+
+    #[test]
+    fn test_global_trim_option() {
+        assert!(!Cli::parse_from(["clack", "conversations", "history", "general"]).trim);
+        assert!(Cli::parse_from(["clack", "conversations", "history", "general", "--trim"]).trim);
+        assert!(Cli::parse_from(["clack", "--trim", "conversations", "replies", "general", "1700000000.000100"]).trim);
+    }
+
+    #[test]
+    fn test_global_raw_ts_option() {
+        assert!(!Cli::parse_from(["clack", "search", "messages", "deploy"]).raw_ts);
+        assert!(Cli::parse_from(["clack", "search", "messages", "deploy", "--raw-ts"]).raw_ts);
+    }
+
+    #[test]
+    fn test_global_tls_options() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert!(cli.ca_cert.is_none());
+        assert!(!cli.insecure);
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--ca-cert", "/etc/proxy-ca.pem", "--insecure"]);
+        assert_eq!(cli.ca_cert, Some(PathBuf::from("/etc/proxy-ca.pem")));
+        assert!(cli.insecure);
+    }
+
+    #[test]
+    fn test_global_team_option() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert!(cli.team.is_none());
+
+        let cli = Cli::parse_from(["clack", "--team", "T0123ABCD", "users", "list"]);
+        assert_eq!(cli.team.as_deref(), Some("T0123ABCD"));
+    }
+
+    #[test]
+    fn test_global_rate_limit_budget_option() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert_eq!(cli.rate_limit_budget, None);
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--rate-limit-budget", "60"]);
+        assert_eq!(cli.rate_limit_budget, Some(60));
+    }
+
+    #[test]
+    fn test_global_json_path_option() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "C123", "--json-path", "$[*].ts"]);
+        assert_eq!(cli.json_path.as_deref(), Some("$[*].ts"));
+        assert_eq!(Cli::parse_from(["clack", "users", "list"]).json_path, None);
+    }
+
+    #[test]
+    fn test_global_no_api_resolve_option() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "general", "--no-api-resolve"]);
+        assert!(cli.no_api_resolve);
+        let cli = Cli::parse_from(["clack", "--channel-cache-only", "conversations", "history", "general"]);
+        assert!(cli.no_api_resolve);
+    }
+
+    #[test]
+    fn test_global_ascii_options() {
+        let cli = Cli::parse_from(["clack", "users", "list", "--ascii"]);
+        assert!(cli.ascii_glyphs());
+        let cli = Cli::parse_from(["clack", "users", "list", "--ascii", "--no-ascii"]);
+        assert!(!cli.ascii);
+        assert!(!cli.ascii_glyphs());
+    }
+
+    #[test]
+    fn test_global_cache_dir_option() {
+        let cli = Cli::parse_from(["clack", "users", "list", "--cache-dir", "/tmp/clack-cache"]);
+        assert_eq!(cli.cache_dir, Some(PathBuf::from("/tmp/clack-cache")));
+    }
+
+    #[test]
+    fn test_global_reactions_option() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "general"]);
+        assert_eq!(cli.reactions, ReactionDisplay::Full);
+        let cli = Cli::parse_from(["clack", "conversations", "history", "general", "--reactions", "compact"]);
+        assert_eq!(cli.reactions, ReactionDisplay::Compact);
+        assert!(Cli::try_parse_from(["clack", "users", "list", "--reactions", "some"]).is_err());
+    }
+
+    #[test]
+    fn test_global_id_format_option() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert_eq!(cli.id_format, IdFormat::Show);
+        let cli = Cli::parse_from(["clack", "users", "list", "--id-format", "only"]);
+        assert_eq!(cli.id_format, IdFormat::Only);
+        assert!(Cli::try_parse_from(["clack", "users", "list", "--id-format", "both"]).is_err());
+    }
+
+    #[test]
+    fn test_chat_post_yes() {
+        let cli = Cli::parse_from(["clack", "chat", "post", "general", "hi"]);
+        match cli.command {
+            Commands::Chat {
+                command: ChatCommands::Post { yes, .. },
+            } => assert!(!yes),
+            _ => panic!("expected chat post"),
+        }
+        let cli = Cli::parse_from(["clack", "chat", "post", "general", "hi", "-y"]);
+        match cli.command {
+            Commands::Chat {
+                command: ChatCommands::Post { yes, .. },
+            } => assert!(yes),
+            _ => panic!("expected chat post"),
+        }
+    }
+
+    #[test]
+    fn test_global_base_url_option() {
+        let cli = Cli::parse_from(["clack", "users", "list", "--base-url", "http://localhost:8080/api/"]);
+        assert_eq!(cli.base_url.as_deref(), Some("http://localhost:8080/api"));
+        assert!(Cli::try_parse_from(["clack", "users", "list", "--base-url", "localhost:8080"]).is_err());
+    }
+
+    #[test]
+    fn test_record_and_replay_options() {
+        use crate::api::recording::Recording;
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert_eq!(cli.recording(), None);
+        let cli = Cli::parse_from(["clack", "users", "list", "--record", "fixtures"]);
+        assert_eq!(cli.recording(), Some(Recording::Record(PathBuf::from("fixtures"))));
+        let cli = Cli::parse_from(["clack", "--replay", "fixtures", "users", "list"]);
+        assert_eq!(cli.recording(), Some(Recording::Replay(PathBuf::from("fixtures"))));
+        assert!(Cli::try_parse_from(["clack", "users", "list", "--record", "a", "--replay", "b"]).is_err());
+    }
+
+    #[test]
+    fn test_global_emoji_map_file_option() {
+        let cli = Cli::parse_from(["clack", "conversations", "history", "general"]);
+        assert_eq!(cli.emoji_map_file, None);
+        let cli = Cli::parse_from(["clack", "conversations", "history", "general", "--emoji-map-file", "emoji.json"]);
+        assert_eq!(cli.emoji_map_file, Some(PathBuf::from("emoji.json")));
+    }
+
+    #[test]
+    fn test_render_options_from_globals() {
+        let cli = Cli::parse_from([
+            "clack", "conversations", "history", "general", "--max-text-length", "40", "--raw-ts", "--ascii", "--id-format", "hide",
+        ]);
+        let options = cli.render_options();
+        assert_eq!(options.max_text_length, Some(40));
+        assert!(options.raw_ts && options.ascii && !options.trim);
+        assert_eq!(options.id_format, IdFormat::Hide);
+        assert_eq!(options.team_domain, None);
+        assert!(!options.transcript);
+        let cli = Cli::parse_from(["clack", "conversations", "history", "general", "--format", "transcript"]);
+        assert!(cli.render_options().transcript);
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--team-domain", "https://acme.slack.com/"]);
+        assert_eq!(cli.render_options().team_domain.as_deref(), Some("acme"));
+        let cli = Cli::parse_from(["clack", "users", "list", "--team-domain", "acme"]);
+        assert_eq!(cli.render_options().team_domain.as_deref(), Some("acme"));
+    }
+
+    #[test]
+    fn test_global_retry_options() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert_eq!(cli.max_retries, 3);
+        assert!(!cli.no_retry_on_5xx);
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--max-retries", "5", "--no-retry-on-5xx"]);
+        assert_eq!(cli.max_retries, 5);
+        assert!(cli.no_retry_on_5xx);
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--no-retry-on-5xx", "--retry-on-5xx"]);
+        assert!(!cli.no_retry_on_5xx);
+    }
+
+    #[test]
+    fn test_global_no_wait_option() {
+        assert!(!Cli::parse_from(["clack", "users", "list"]).no_wait);
+        assert!(Cli::parse_from(["clack", "--no-wait", "users", "list"]).no_wait);
+    }
+
+    #[test]
+    fn test_global_no_cache_option() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert!(!cli.no_cache);
+
+        let cli = Cli::parse_from(["clack", "users", "list", "--no-cache"]);
+        assert!(cli.no_cache);
+    }
+
+    #[test]
+    fn test_conversations_open_last_command() {
+        let cli = Cli::parse_from(["clack", "conversations", "open-last", "#general", "--open"]);
+        assert!(cli.open);
+        match cli.command {
+            Commands::Conversations {
+                command: ConversationsCommands::OpenLast { channel },
+            } => assert_eq!(channel, "#general"),
+            _ => panic!("Expected Conversations OpenLast command"),
+        }
+    }
+
+    #[test]
+    fn test_chat_permalink_command() {
+        let cli = Cli::parse_from(["clack", "chat", "permalink", "general", "1234567890.123456", "--open"]);
+        assert!(cli.open);
+        match cli.command {
+            Commands::Chat {
+                command: ChatCommands::Permalink { channel, message_ts },
+            } => {
+                assert_eq!(channel, "general");
+                assert_eq!(message_ts, "1234567890.123456");
+            }
+            _ => panic!("Expected Chat Permalink command"),
+        }
+    }
+
+    #[test]
+    fn test_global_summary_option() {
+        let cli = Cli::parse_from(["clack", "users", "list"]);
+        assert!(!cli.summary);
+
+        let cli = Cli::parse_from(["clack", "search", "messages", "deploy", "--summary"]);
+        assert!(cli.summary);
+    }
+
     #[test]
     fn test_refresh_cache_default_false() {
         let cli = Cli::parse_from(["clack", "users", "list"]);
@@ -800,13 +2540,19 @@ mod tests {
         match cli.command {
             Commands::Stream {
                 interval,
+                state_file,
+                prefix,
+                skip_initial,
                 stream_type,
             } => {
-                assert_eq!(interval, 10); // default
+                assert_eq!(interval, std::time::Duration::from_secs(10)); // default
+                assert_eq!(state_file, None);
+                assert_eq!(prefix, None);
+                assert!(!skip_initial);
                 // format comes from global cli.format
                 match stream_type {
                     StreamType::Search { search_type } => match search_type {
-                        StreamSearchType::Messages { query, from, to, channel, has } => {
+                        StreamSearchType::Messages { query, from, to, channel, has, .. } => {
                             assert_eq!(query, "hello");
                             assert_eq!(from, None);
                             assert_eq!(to, None);
@@ -820,6 +2566,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_poll_interval_parsing() {
+        use std::time::Duration;
+        assert_eq!(poll_interval("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(poll_interval("2.5"), Ok(Duration::from_millis(2500)));
+        assert_eq!(poll_interval("1500ms"), Ok(Duration::from_millis(1500)));
+        assert_eq!(poll_interval("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(poll_interval("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(poll_interval("1h"), Ok(Duration::from_secs(3600)));
+        assert!(poll_interval("500ms").unwrap_err().contains("at least 1s"));
+        assert!(poll_interval("0").is_err());
+        assert!(poll_interval("5d").unwrap_err().contains("unknown unit"));
+        assert!(poll_interval("soon").is_err());
+    }
+
     #[test]
     fn test_stream_search_messages_with_options() {
         let cli = Cli::parse_from([
@@ -842,8 +2603,9 @@ mod tests {
             Commands::Stream {
                 interval,
                 stream_type,
+                ..
             } => {
-                assert_eq!(interval, 30);
+                assert_eq!(interval, std::time::Duration::from_secs(30));
                 match stream_type {
                     StreamType::Search { search_type } => match search_type {
                         StreamSearchType::Messages { query, from, channel, .. } => {
@@ -858,6 +2620,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_stream_state_file_option() {
+        let cli = Cli::parse_from([
+            "clack",
+            "stream",
+            "--state-file",
+            "/tmp/clack-stream.json",
+            "search",
+            "messages",
+            "deploy",
+        ]);
+        match cli.command {
+            Commands::Stream { state_file, .. } => {
+                assert_eq!(state_file, Some(PathBuf::from("/tmp/clack-stream.json")));
+            }
+            _ => panic!("Expected Stream command"),
+        }
+    }
+
+    #[test]
+    fn test_stream_prefix_option() {
+        let cli = Cli::parse_from(["clack", "stream", "--prefix", "[{channel}] ", "search", "messages", "deploy"]);
+        match cli.command {
+            Commands::Stream { prefix, .. } => assert_eq!(prefix.as_deref(), Some("[{channel}] ")),
+            _ => panic!("Expected Stream command"),
+        }
+    }
+
+    #[test]
+    fn test_stream_skip_initial_option() {
+        let cli = Cli::parse_from(["clack", "stream", "--skip-initial", "search", "messages", "deploy"]);
+        match cli.command {
+            Commands::Stream { skip_initial, .. } => assert!(skip_initial),
+            _ => panic!("Expected Stream command"),
+        }
+    }
+
     #[test]
     fn test_human_compact_format() {
         let cli = Cli::parse_from(["clack", "--format", "human-compact", "search", "messages", "test"]);
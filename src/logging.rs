@@ -0,0 +1,54 @@
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt;
+use tracing_subscriber::prelude::*;
+
+/// Minimum severity of diagnostics written to stderr (`--log-level`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// Install the stderr subscriber for `tracing` events. Call once, before any API or cache work.
+///
+/// Events carry their data as `key=value` fields (e.g. `outcome="hit"`, `elapsed_ms=42`) so
+/// the output can be filtered with grep or parsed by scripts. Dependencies (reqwest, hyper)
+/// only get through at warn and above, so debug output stays about clack itself.
+pub fn init(level: LogLevel) {
+    let level = LevelFilter::from(level);
+    let filter = Targets::new()
+        .with_target(env!("CARGO_CRATE_NAME"), level)
+        .with_default(level.min(LevelFilter::WARN));
+    let stderr = fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_ansi(atty::is(atty::Stream::Stderr))
+        .with_target(false);
+    tracing_subscriber::registry().with(stderr).with(filter).init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_level_filters() {
+        assert_eq!(LevelFilter::from(LogLevel::Warn), LevelFilter::WARN);
+        assert!(LevelFilter::from(LogLevel::Debug) > LevelFilter::from(LogLevel::Info));
+    }
+}
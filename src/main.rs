@@ -1,220 +1,694 @@
 mod api;
-mod cache;
 mod cli;
-mod models;
+mod logging;
 mod output;
 mod stream;
 
-use anyhow::Result;
+// The cache and models live in the library crate, which also exposes them to tests
+use clack::{cache, models};
+
+use anyhow::{Context, Result};
 use clap::Parser;
 use cli::{
-    AuthType, ChatCommands, Cli, Commands, ConversationsCommands, FilesCommands, PinsCommands,
-    ProfileCommands, ReactionsCommands, SearchType, StreamSearchType, StreamType, UsersCommands,
+    AuthType, CacheCommands, ChatCommands, Cli, Commands, ConversationsCommands, ExportCommands, FilesCommands,
+    PinsCommands, ProfileCommands, ReactionsCommands, SearchType, StreamSearchType, StreamType,
+    UsersCommands,
 };
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    let log_level = cli.effective_log_level();
+    logging::init(log_level);
+
+    // --json-path works on the json output; parse it before any API calls
+    let json_path = match cli.json_path.as_deref() {
+        Some(expr) => {
+            match cli.format.as_str() {
+                "human" => cli.format = "json".to_string(),
+                "json" => {}
+                other => anyhow::bail!("--json-path needs json output, not --format {}", other),
+            }
+            Some(output::jsonpath::parse(expr)?)
+        }
+        None => None,
+    };
+
+    // --pretty/--compact override TTY detection for json output
+    let json_pretty = if cli.compact {
+        Some(false)
+    } else if cli.pretty {
+        Some(true)
+    } else {
+        None
+    };
+
+    // `version` is answered locally: no token, network or cache needed
+    if let Commands::Version { json } = cli.command {
+        let info = output::version_formatter::BuildInfo::current();
+        let format = if json || json_path.is_some() { "json" } else { cli.format.as_str() };
+        let mut out = match format {
+            "json" | "yaml" => output::serialize(&info, format, json_pretty)?,
+            _ => {
+                let mut writer =
+                    output::color::ColorWriter::new(!cli.color_choice().enabled()).with_options(cli.render_options());
+                output::version_formatter::format_build_info(&info, &mut writer)?;
+                writer.into_string()?
+            }
+        };
+        if let Some(path) = &json_path {
+            out = output::jsonpath::render(&output::jsonpath::select(&serde_json::from_str(&out)?, path));
+        }
+        print!("{}", out);
+        if !out.ends_with('\n') {
+            println!();
+        }
+        return Ok(());
+    }
+
+    // Create API client with debug_response and refresh_cache flags
+    let tls = api::client::TlsConfig {
+        ca_cert: cli.ca_cert.clone(),
+        insecure: cli.insecure,
+    };
+
+    // `auth test --tokens-file` uses its own tokens, so SLACK_TOKEN isn't needed
+    if let Commands::Auth {
+        auth_type: cli::AuthType::Test {
+            tokens_file: Some(ref path),
+        },
+    } = cli.command
+    {
+        let tokens = api::auth::read_tokens_file(path)?;
+        let mut checks = Vec::with_capacity(tokens.len());
+        for token in &tokens {
+            // A throwaway client per token; no cache, so nothing about these tokens is persisted
+            let mut client = api::client::SlackClient::with_token(
+                token,
+                cli.base_url.as_deref().unwrap_or(api::client::DEFAULT_BASE_URL),
+                cli.debug_response,
+                false,
+                true,
+                None,
+                &tls,
+            )
+            .await?;
+            client.set_no_wait(cli.no_wait);
+            client.set_max_retries(cli.max_retries);
+            client.set_retry_server_errors(!cli.no_retry_on_5xx);
+            client.set_retry_config(cli.retry_config());
+            checks.push(api::auth::check_token(&client, token).await);
+        }
 
-    // Create API client with verbose, debug_response, and refresh_cache flags
-    let mut client = api::client::SlackClient::new(cli.verbose, cli.debug_response, cli.refresh_cache).await?;
+        let no_color = !cli.color_choice().enabled();
+        let render = cli.render_options();
+        let out = output::format::select(&cli.format, json_pretty, move || {
+            output::color::ColorWriter::new(no_color).with_options(render.clone())
+        })
+        .format_token_checks(&checks)?;
+        print!("{}", out);
+        if !out.ends_with('\n') {
+            println!();
+        }
+        return Ok(());
+    }
+    let mut client = match cli.replay {
+        // Nothing is sent while replaying, so SLACK_TOKEN isn't needed
+        Some(_) => {
+            api::client::SlackClient::with_token(
+                "",
+                cli.base_url.as_deref().unwrap_or(api::client::DEFAULT_BASE_URL),
+                cli.debug_response,
+                cli.refresh_cache,
+                cli.no_cache,
+                cli.cache_dir.as_deref(),
+                &tls,
+            )
+            .await?
+        }
+        None => {
+            api::client::SlackClient::new(
+                cli.base_url.as_deref(),
+                cli.debug_response,
+                cli.refresh_cache,
+                cli.no_cache,
+                cli.cache_dir.as_deref(),
+                &tls,
+            )
+            .await?
+        }
+    };
+    if let Some(recording) = cli.recording() {
+        client.set_recording(recording);
+    }
+    if let Some(ref log_file) = cli.log_file {
+        client.set_log_file(log_file)?;
+    }
+    if let Some(ref team) = cli.team {
+        client.set_team(team)?;
+    }
+    if let Some(budget) = cli.rate_limit_budget {
+        client.set_rate_limit_budget(std::time::Duration::from_secs(budget));
+    }
+    client.set_no_wait(cli.no_wait);
+    client.set_max_retries(cli.max_retries);
+    client.set_retry_server_errors(!cli.no_retry_on_5xx);
+    client.set_retry_config(cli.retry_config());
+    client.set_cache_only_resolve(cli.no_api_resolve);
 
     // Initialize workspace context (fetches team_id)
     client.init_workspace().await?;
 
+    // Resolve --color/--no-color/NO_COLOR once for every writer
+    let no_color = !cli.color_choice().enabled();
+
+    // Every human-format writer shares the same color and display settings
+    let mut render = cli.render_options();
+    if render.team_domain.is_none() {
+        render.team_domain = client.team_domain().map(str::to_string);
+    }
+    if let Some(ref path) = cli.emoji_map_file {
+        render.emoji_map = std::sync::Arc::new(output::emoji::load_emoji_map(path)?);
+    }
+    let new_writer = {
+        let render = render.clone();
+        move || output::color::ColorWriter::new(no_color).with_options(render.clone())
+    };
+    // Renders the results that need nothing beyond the data itself
+    let out = output::format::select(&cli.format, json_pretty, new_writer.clone());
+
     // Will accumulate all output here
     let mut final_output = String::new();
 
+    // URL for single-result commands, opened in the browser with --open
+    let mut open_url: Option<String> = None;
+
+    // Optional one-line summary printed to stderr with --summary
+    let mut summary: Option<output::summary::Summary> = None;
+
+    // Failed per-item lookups: fatal with --fail-fast, otherwise a warning at the end
+    let mut lookups = api::failures::LookupFailures::new(cli.fail_fast && !cli.continue_on_error);
+
     // Execute command
     match cli.command {
         Commands::Users { command } => match command {
             UsersCommands::List {
                 limit,
                 include_deleted,
+                only_deleted,
+                team_id,
+                presence,
+                email_domain,
+                humans_only,
             } => {
-                let users = api::users::list_users(&client, limit, include_deleted).await?;
-
-                final_output = match cli.format.as_str() {
-                    "json" => serde_json::to_string_pretty(&users)?,
-                    "yaml" => serde_yaml::to_string(&users)?,
-                    _ => {
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::user_formatter::format_users_list(&users, &mut writer)?;
-                        writer.into_string()?
+                if let Some(ref team_id) = team_id {
+                    api::client::validate_team_id(team_id)?;
+                }
+                // Filtered while paging so --count counts only the users that are shown
+                let mut users = api::users::list_users_matching(&client, limit, team_id.as_deref(), cli.count, |u| {
+                    let deleted_ok = if only_deleted { u.deleted } else { include_deleted || !u.deleted };
+                    deleted_ok
+                        && (!humans_only || u.is_human())
+                        && email_domain.as_deref().is_none_or(|domain| u.has_email_domain(domain))
+                })
+                .await?;
+
+                if presence {
+                    // Deactivated accounts have no presence worth an API call
+                    let mut active: Vec<&mut models::user::User> = users.iter_mut().filter(|u| !u.deleted).collect();
+                    let ids: Vec<String> = active.iter().map(|u| u.id.clone()).collect();
+                    let presences = api::users::get_presences(&client, &ids).await;
+                    for (user, result) in active.iter_mut().zip(presences) {
+                        user.presence = lookups.check(&format!("presence of {}", user.id), result)?;
                     }
-                };
-            }
-            UsersCommands::Info { user_id } => {
-                let user = api::users::get_user(&client, &user_id).await?;
+                }
+                summary = Some(output::summary::Summary::new().count(users.len(), "user", "users"));
 
-                final_output = match cli.format.as_str() {
-                    "json" => serde_json::to_string_pretty(&user)?,
-                    "yaml" => serde_yaml::to_string(&user)?,
-                    _ => {
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::user_formatter::format_user(&user, &mut writer)?;
-                        writer.into_string()?
+                final_output = out.format_users(&users)?;
+            }
+            UsersCommands::Diff { limit } => {
+                // Read the snapshot before the fresh list overwrites it
+                let cached = api::users::cached_users(&client).await?;
+                // Every page: a user left off a truncated list would look removed
+                let fresh = api::users::list_users(&client, limit, true, None, Some(usize::MAX)).await?;
+                let changes = output::user_formatter::diff_users(cached, fresh);
+
+                final_output = out.format_user_changes(&changes)?;
+            }
+            UsersCommands::Info { users } => {
+                let mut resolved = Vec::new();
+                for (identifier, result) in users.iter().zip(api::users::get_users(&client, &users).await) {
+                    if let Some(user) = lookups.check(&format!("user {}", identifier), result)? {
+                        resolved.push(user);
                     }
+                }
+                if resolved.is_empty() {
+                    anyhow::bail!("No users found: {}", lookups.failures().join("; "));
+                }
+                let users = resolved;
+                if let [user] = users.as_slice() {
+                    open_url = Some(user.profile_url());
+                }
+
+                // A single user keeps the detailed view and object output
+                final_output = match users.as_slice() {
+                    [user] => out.format_user(user)?,
+                    _ => out.format_users(&users)?,
                 };
             }
             UsersCommands::Profile { command } => match command {
-                ProfileCommands::Get { user_id } => {
-                    let profile = api::users::get_profile(&client, user_id.as_deref()).await?;
-
-                    final_output = match cli.format.as_str() {
-                        "json" => serde_json::to_string_pretty(&profile)?,
-                        "yaml" => serde_yaml::to_string(&profile)?,
-                        _ => {
-                            let mut writer = output::color::ColorWriter::new(cli.no_color);
-                            output::user_formatter::format_profile(&profile, &mut writer)?;
-                            writer.into_string()?
-                        }
-                    }
+                ProfileCommands::Get { user_id, all_fields: true } => {
+                    let profile: serde_json::Value = api::users::get_profile(&client, user_id.as_deref()).await?;
+
+                    final_output = out.format_profile_fields(&profile)?;
+                }
+                ProfileCommands::Get { user_id, all_fields: false } => {
+                    let profile: models::user::UserProfile =
+                        api::users::get_profile(&client, user_id.as_deref()).await?;
+
+                    final_output = out.format_profile(&profile)?;
                 }
             },
         },
         Commands::Conversations { command } => match command {
-            ConversationsCommands::List { include_archived, limit } => {
-                let channels = api::channels::list_channels(&client, include_archived, limit).await?;
-
-                final_output = match cli.format.as_str() {
-                    "json" => serde_json::to_string_pretty(&channels)?,
-                    "yaml" => serde_yaml::to_string(&channels)?,
-                    _ => {
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::channel_formatter::format_channels_list(&channels, &mut writer)?;
-                        writer.into_string()?
+            ConversationsCommands::List {
+                include_archived,
+                only_archived,
+                limit,
+                sort_channels_by_activity,
+            } => {
+                let mut channels =
+                    api::channels::list_channels(&client, include_archived || only_archived, limit, cli.count).await?;
+                if only_archived {
+                    channels.retain(|c| c.is_archived.unwrap_or(false));
+                }
+                summary = Some(output::summary::Summary::new().count(channels.len(), "channel", "channels"));
+
+                final_output = if sort_channels_by_activity {
+                    eprintln!(
+                        "Warning: --sort-channels-by-activity makes one conversations.history call per channel ({} calls)",
+                        channels.len()
+                    );
+                    let ids: Vec<String> = channels.iter().map(|c| c.id.clone()).collect();
+                    let latest = api::messages::latest_ts(&client, &ids).await;
+                    let mut entries = Vec::with_capacity(channels.len());
+                    for (channel, result) in channels.into_iter().zip(latest) {
+                        // Channels the token can't read still get listed, just without a time
+                        let last_activity = lookups.check(&format!("history of #{}", channel.name), result)?.flatten();
+                        entries.push(output::channel_formatter::ChannelActivity { channel, last_activity });
                     }
+                    output::channel_formatter::sort_by_activity(&mut entries);
+
+                    match out.serialized() {
+                        Some(serialized) => serialized.write(&entries)?,
+                        None => {
+                            let mut writer = new_writer();
+                            output::channel_formatter::format_channels_by_activity(&entries, &mut writer)?;
+                            writer.into_string()?
+                        }
+                    }
+                } else {
+                    out.format_channels(&channels)?
                 }
             }
-            ConversationsCommands::Info { channel } => {
+            ConversationsCommands::Info { channel, raw: true, .. } => {
+                let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
+                let raw = api::channels::get_channel_raw(&client, &channel_id).await?;
+                // Human output has no layout for arbitrary fields, so it gets the JSON too
+                final_output = match out.serialized() {
+                    Some(serialized) => serialized.write(&raw)?,
+                    None => output::serialize(&raw, "json", Some(true))?,
+                };
+            }
+            ConversationsCommands::Info {
+                channel,
+                history_stats,
+                days,
+                raw: false,
+            } => {
                 // Resolve channel name to ID if needed
                 let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
                 let channel_info = api::channels::get_channel(&client, &channel_id).await?;
 
-                final_output = match cli.format.as_str() {
-                    "json" => serde_json::to_string_pretty(&channel_info)?,
-                    "yaml" => serde_yaml::to_string(&channel_info)?,
-                    _ => {
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        // Reuse format_channels_list with a single-element vector
-                        output::channel_formatter::format_channels_list(&vec![channel_info], &mut writer)?;
+                let stats = if history_stats {
+                    let oldest = days_ago_ts(days)?;
+                    let messages = api::messages::list_all_messages(&client, &channel_id, Some(oldest)).await?;
+                    Some(output::channel_formatter::history_stats(&messages, days))
+                } else {
+                    None
+                };
+
+                final_output = match out.serialized() {
+                    Some(serialized) => match &stats {
+                        Some(stats) => {
+                            serialized.write(&serde_json::json!({ "channel": channel_info, "history_stats": stats }))?
+                        }
+                        None => serialized.write(&channel_info)?,
+                    },
+                    None => {
+                        // Whoever set the topic/purpose, plus the most active participants (cache-first)
+                        let user_ids = [
+                            channel_info.topic.as_ref().and_then(|t| t.set_by().0),
+                            channel_info.purpose.as_ref().and_then(|p| p.set_by().0),
+                        ]
+                        .into_iter()
+                        .flatten()
+                        .map(str::to_string)
+                        .chain(stats.iter().flat_map(|s| s.top_participants.iter().map(|p| p.user.clone())));
+                        let user_map = api::users::resolve_user_map(&client, user_ids, &mut lookups).await?;
+
+                        let mut writer = new_writer();
+                        output::channel_formatter::format_channel_info(&channel_info, &user_map, &mut writer)?;
+
+                        if let Some(stats) = &stats {
+                            writer.writeln()?;
+                            output::channel_formatter::format_history_stats(stats, &user_map, &mut writer)?;
+                        }
                         writer.into_string()?
                     }
                 }
             }
+            ConversationsCommands::History {
+                channel,
+                oldest,
+                warm: true,
+                ..
+            } => {
+                let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
+                let cached = api::messages::warm_cache(&client, &channel_id, oldest).await?;
+                eprintln!("Cached {} message{} from {}", cached, if cached == 1 { "" } else { "s" }, channel);
+                summary = Some(output::summary::Summary::new().count(cached, "message", "messages"));
+            }
             ConversationsCommands::History {
                 channel,
                 limit,
                 latest,
                 oldest,
+                exclude_bots,
+                mentions,
+                group_by_day,
+                since_last_run,
+                author_name_width,
+                accurate_links,
+                user_timezone_summary,
+                author_stats,
+                dedupe,
+                no_dedupe,
+                oneline,
+                participants: roster,
+                ..
             } => {
                 // Resolve channel name to ID if needed
                 let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
+                // Resolved up front so a bad name fails before fetching history
+                let mentioned_id = match &mentions {
+                    Some(user) => Some(api::users::resolve_user_to_id(&client, user).await?),
+                    None => None,
+                };
 
-                let messages =
-                    api::messages::list_messages(&client, &channel_id, limit, latest, oldest).await?;
+                let last_fetch = if since_last_run {
+                    api::messages::get_last_fetch_ts(&client, &channel_id).await?
+                } else {
+                    None
+                };
 
-                final_output = match cli.format.as_str() {
-                    "json" => serde_json::to_string_pretty(&messages)?,
-                    "yaml" => serde_yaml::to_string(&messages)?,
-                    _ => {
-                        // Fetch channel info for metadata
-                        let channel_info = api::channels::get_channel(&client, &channel_id).await?;
+                let mut messages = match last_fetch {
+                    // Page through everything since the last run so nothing is skipped
+                    Some(last_ts) => api::messages::list_all_messages(&client, &channel_id, Some(last_ts)).await?,
+                    // The roster covers every page back to --oldest, not just the first --limit messages
+                    None if roster && oldest.is_some() => {
+                        let mut all = api::messages::list_all_messages(&client, &channel_id, oldest).await?;
+                        if let Some(latest) = latest.as_deref().and_then(models::message::parse_ts) {
+                            all.retain(|m| models::message::parse_ts(&m.ts).is_some_and(|ts| ts <= latest));
+                        }
+                        all
+                    }
+                    None => api::messages::list_messages(&client, &channel_id, limit, latest, oldest).await?,
+                };
 
-                        // Build user lookup map - only fetch users mentioned in messages
-                        let mut user_map: std::collections::HashMap<String, models::user::User> =
-                            std::collections::HashMap::new();
+                if since_last_run {
+                    api::messages::record_last_fetch(&client, &channel_id, &messages).await?;
+                }
 
-                        for message in &messages {
-                            if let Some(user_id) = &message.user {
-                                if !user_map.contains_key(user_id) {
-                                    // Fetch individual user (cache-first)
-                                    if let Ok(user) = api::users::get_user(&client, user_id).await {
-                                        user_map.insert(user.id.clone(), user);
-                                    }
+                // Full paginated fetches are where overlapping pages repeat messages
+                if dedupe || (since_last_run && !no_dedupe) {
+                    models::message::dedupe_messages(&mut messages);
+                }
+
+                if exclude_bots {
+                    messages.retain(|m| !m.is_bot());
+                }
+                if let Some(user_id) = &mentioned_id {
+                    messages.retain(|m| m.mentions(user_id));
+                }
+
+                let participants: std::collections::HashSet<&String> =
+                    messages.iter().filter_map(|m| m.user.as_ref()).collect();
+                summary = Some(
+                    output::summary::Summary::new()
+                        .count(messages.len(), "message", "messages")
+                        .count(participants.len(), "participant", "participants"),
+                );
+
+                final_output = if roster {
+                    let mut counts = output::channel_formatter::participant_counts(&messages);
+                    let user_map =
+                        api::users::resolve_user_map(&client, counts.iter().map(|p| p.user.clone()), &mut lookups).await?;
+                    for participant in &mut counts {
+                        participant.name = user_map.get(&participant.user).map(|user| user.name.clone());
+                    }
+
+                    match out.serialized() {
+                        Some(serialized) => serialized.write(&counts)?,
+                        None => {
+                            let channel_info = api::channels::get_channel(&client, &channel_id).await?;
+                            let mut writer = new_writer();
+                            output::channel_formatter::format_participant_roster(&counts, &channel_info.name, &mut writer)?;
+                            writer.into_string()?
+                        }
+                    }
+                } else if author_stats {
+                    // Thread roots in the window; replies come from conversations.replies
+                    let roots: Vec<String> = messages
+                        .iter()
+                        .filter(|m| m.thread_ts.as_deref() == Some(m.ts.as_str()))
+                        .map(|m| m.ts.clone())
+                        .collect();
+                    let mut delays = Vec::new();
+                    for (thread_ts, result) in roots.iter().zip(api::messages::get_threads(&client, &channel_id, &roots).await) {
+                        if let Some(thread) = lookups.check(&format!("thread {}", thread_ts), result)? {
+                            delays.extend(output::response_time_formatter::first_reply_delay(&thread));
+                        }
+                    }
+                    let times = output::response_time_formatter::response_times(delays);
+
+                    match out.serialized() {
+                        Some(serialized) => serialized.write(&times)?,
+                        None => {
+                            let channel_info = api::channels::get_channel(&client, &channel_id).await?;
+                            let mut writer = new_writer();
+                            output::response_time_formatter::format_response_times(&times, &channel_info.name, &mut writer)?;
+                            writer.into_string()?
+                        }
+                    }
+                } else if user_timezone_summary {
+                    // Resolve every author (cache-first, concurrently) to read their tz
+                    let user_map =
+                        api::users::resolve_user_map(&client, participants.iter().map(|id| id.to_string()), &mut lookups)
+                            .await?;
+                    let tz_summary = output::timezone_formatter::tally_timezones(&messages, &user_map);
+
+                    match out.serialized() {
+                        Some(serialized) => serialized.write(&tz_summary)?,
+                        None => {
+                            let channel_info = api::channels::get_channel(&client, &channel_id).await?;
+                            let mut writer = new_writer();
+                            output::timezone_formatter::format_timezone_summary(
+                                &tz_summary,
+                                &channel_info.name,
+                                &mut writer,
+                            )?;
+                            writer.into_string()?
+                        }
+                    }
+                } else {
+                    match out.serialized() {
+                        Some(serialized) => serialized.write(&messages)?,
+                        // No channel header or thread metadata, so only the authors are looked up
+                        None if oneline => {
+                            let channel_info = api::channels::get_channel(&client, &channel_id).await?;
+                            let user_map = api::users::resolve_user_map(
+                                &client,
+                                participants.iter().map(|id| id.to_string()),
+                                &mut lookups,
+                            )
+                            .await?;
+
+                            let mut writer = new_writer();
+                            output::message_formatter::format_messages_oneline(
+                                &messages,
+                                Some(&channel_info.name),
+                                &user_map,
+                                &mut writer,
+                            )?;
+                            writer.into_string()?
+                        }
+                        None => {
+                            // Fetch channel info for metadata
+                            let channel_info = api::channels::get_channel(&client, &channel_id).await?;
+
+                            // Build thread metadata map, fetching the threads concurrently
+                            let mut thread_info: std::collections::HashMap<String, (usize, Vec<String>)> =
+                                std::collections::HashMap::new();
+                            // Roots in the page carry reply_count, so unchanged threads come from the cache
+                            let reply_counts: std::collections::HashMap<&str, u32> = messages
+                                .iter()
+                                .filter_map(|m| m.reply_count.map(|n| (m.ts.as_str(), n)))
+                                .collect();
+                            let mut threads: Vec<(String, Option<u32>)> = messages
+                                .iter()
+                                .filter_map(|m| m.thread_ts.as_ref())
+                                .map(|thread_ts| (thread_ts.clone(), reply_counts.get(thread_ts.as_str()).copied()))
+                                .collect();
+                            threads.sort();
+                            threads.dedup();
+                            // The transcript shows only the roots' reply_count
+                            if render.transcript {
+                                threads.clear();
+                            }
+                            for ((thread_ts, _), result) in threads
+                                .iter()
+                                .zip(api::messages::get_threads_cached(&client, &channel_id, &threads).await)
+                            {
+                                if let Some(thread_messages) = lookups.check(&format!("thread {}", thread_ts), result)? {
+                                    thread_info.insert(thread_ts.clone(), api::messages::get_thread_metadata(&thread_messages));
                                 }
                             }
-                        }
 
-                        // Build thread metadata map
-                        let mut thread_info: std::collections::HashMap<String, (usize, Vec<String>)> =
-                            std::collections::HashMap::new();
+                            // Then resolve every author and thread participant in one concurrent step
+                            let mut user_ids: Vec<String> = participants.iter().map(|id| id.to_string()).collect();
+                            user_ids.extend(thread_info.values().flat_map(|(_, ids)| ids.iter().cloned()));
+                            if render.transcript {
+                                // Mentions are rendered as names
+                                user_ids.extend(
+                                    messages.iter().flat_map(|m| m.mentioned_user_ids()).map(str::to_string),
+                                );
+                            }
+                            let user_map = api::users::resolve_user_map(&client, user_ids, &mut lookups).await?;
 
-                        // Identify unique threads
-                        let thread_timestamps: std::collections::HashSet<&String> = messages
-                            .iter()
-                            .filter_map(|m| m.thread_ts.as_ref())
-                            .collect();
+                            // Day separators read top to bottom, so put oldest first.
+                            // Only the human view is reordered; json/yaml keep Slack's order.
+                            if group_by_day {
+                                messages.sort_by_key(|m| models::message::parse_ts(&m.ts).unwrap_or_default());
+                            }
 
-                        // Fetch metadata for each thread
-                        for thread_ts in thread_timestamps {
-                            if let Ok(thread_messages) = api::messages::get_thread(&client, &channel_id, thread_ts).await {
-                                let (reply_count, participant_ids) = api::messages::get_thread_metadata(&thread_messages);
-                                thread_info.insert(thread_ts.clone(), (reply_count, participant_ids.clone()));
-
-                                // Also add participants to user_map
-                                for user_id in &participant_ids {
-                                    if !user_map.contains_key(user_id) {
-                                        if let Ok(user) = api::users::get_user(&client, user_id).await {
-                                            user_map.insert(user.id.clone(), user);
-                                        }
-                                    }
-                                }
+                            if accurate_links {
+                                api::messages::backfill_permalinks(
+                                    &client,
+                                    &channel_id,
+                                    &mut messages,
+                                    api::messages::MAX_PERMALINK_LOOKUPS,
+                                )
+                                .await;
                             }
-                        }
 
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::message_formatter::format_messages_with_thread_info(
-                            &messages,
-                            &channel_info,
-                            &user_map,
-                            &thread_info,
-                            &mut writer,
-                        )?;
-                        writer.into_string()?
+                            let mut writer = new_writer();
+                            output::message_formatter::format_messages_with_thread_info(
+                                &messages,
+                                &channel_info,
+                                &user_map,
+                                &thread_info,
+                                group_by_day,
+                                author_name_width,
+                                &mut writer,
+                            )?;
+                            writer.into_string()?
+                        }
                     }
                 };
             }
             ConversationsCommands::Replies {
                 channel,
                 message_ts,
+                thread_ts_from_permalink,
+                export,
             } => {
-                // Resolve channel name to ID if needed
-                let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
+                let (channel_id, message_ts) = match thread_ts_from_permalink {
+                    Some(url) => {
+                        let link = models::message::Permalink::parse(&url)
+                            .ok_or_else(|| anyhow::anyhow!("Not a Slack message permalink: {}", url))?;
+                        // Reply links usually carry the root; otherwise look it up
+                        let thread_ts = match link.thread_ts {
+                            Some(thread_ts) => thread_ts,
+                            None => api::messages::find_thread_ts(&client, &link.channel_id, &link.ts).await?,
+                        };
+                        (link.channel_id, thread_ts)
+                    }
+                    None => {
+                        // clap requires both positionals without a permalink
+                        let (Some(channel), Some(message_ts)) = (channel, message_ts) else {
+                            unreachable!("channel and message_ts are required without --thread-ts-from-permalink")
+                        };
+                        // Resolve channel name to ID if needed
+                        (api::channels::resolve_channel_id(&client, &channel).await?, message_ts)
+                    }
+                };
 
                 let messages = api::messages::get_thread(&client, &channel_id, &message_ts).await?;
 
-                final_output = match cli.format.as_str() {
-                    "json" => serde_json::to_string_pretty(&messages)?,
-                    "yaml" => serde_yaml::to_string(&messages)?,
-                    _ => {
-                        // Fetch channel info for metadata
-                        let channel_info = api::channels::get_channel(&client, &channel_id).await?;
-
-                        // Build user lookup map - only fetch users mentioned in thread
-                        let mut user_map: std::collections::HashMap<String, models::user::User> =
-                            std::collections::HashMap::new();
+                let participants: std::collections::HashSet<&String> =
+                    messages.iter().filter_map(|m| m.user.as_ref()).collect();
+                summary = Some(
+                    output::summary::Summary::new()
+                        .count(messages.len().saturating_sub(1), "reply", "replies")
+                        .count(participants.len(), "participant", "participants"),
+                );
 
-                        for message in &messages {
-                            if let Some(user_id) = &message.user {
-                                if !user_map.contains_key(user_id) {
-                                    // Fetch individual user (cache-first)
-                                    if let Ok(user) = api::users::get_user(&client, user_id).await {
-                                        user_map.insert(user.id.clone(), user);
-                                    }
-                                }
-                            }
+                final_output = if let Some(path) = export {
+                    let channel_info = api::channels::get_channel(&client, &channel_id).await?;
+                    let user_map =
+                        api::users::resolve_user_map(&client, participants.iter().map(|id| id.to_string()), &mut lookups)
+                            .await?;
+
+                    let transcript = output::thread_formatter::thread_transcript(&messages, &channel_info, &user_map, &render);
+                    let contents = match path.extension().and_then(|e| e.to_str()) {
+                        // Files are for archiving, so always pretty-print
+                        Some("json") => output::serialize(&transcript, "json", Some(true))?,
+                        Some("yaml" | "yml") => output::serialize(&transcript, "yaml", None)?,
+                        _ => output::thread_formatter::format_thread_markdown(&messages, &channel_info, &user_map, &render),
+                    };
+                    std::fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+                    format!(
+                        "Saved thread from #{} ({} repl{}) to {}\n",
+                        channel_info.name,
+                        messages.len().saturating_sub(1),
+                        if messages.len() == 2 { "y" } else { "ies" },
+                        path.display()
+                    )
+                } else {
+                    match out.serialized() {
+                        Some(serialized) => serialized.write(&messages)?,
+                        None => {
+                            // Fetch channel info for metadata
+                            let channel_info = api::channels::get_channel(&client, &channel_id).await?;
+
+                            // Only the users who posted in the thread
+                            let user_map = api::users::resolve_user_map(
+                                &client,
+                                participants.iter().map(|id| id.to_string()),
+                                &mut lookups,
+                            )
+                            .await?;
+
+                            let mut writer = new_writer();
+                            output::thread_formatter::format_thread(
+                                &messages,
+                                &channel_info,
+                                &user_map,
+                                &mut writer,
+                            )?;
+                            writer.into_string()?
                         }
-
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::thread_formatter::format_thread(
-                            &messages,
-                            &channel_info,
-                            &user_map,
-                            &mut writer,
-                        )?;
-                        writer.into_string()?
                     }
                 };
             }
@@ -227,21 +701,267 @@ async fn main() -> Result<()> {
                 // Fetch user details for each member
                 let mut users = Vec::new();
                 for user_id in &member_ids {
-                    if let Ok(user) = api::users::get_user(&client, user_id).await {
+                    if let Some(user) = lookups.check(&format!("user {}", user_id), api::users::get_user(&client, user_id).await)? {
                         users.push(user);
                     }
                 }
 
-                final_output = match cli.format.as_str() {
-                    "json" => serde_json::to_string_pretty(&users)?,
-                    "yaml" => serde_yaml::to_string(&users)?,
-                    _ => {
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::user_formatter::format_users_list(&users, &mut writer)?;
+                summary = Some(
+                    output::summary::Summary::new()
+                        .count(member_ids.len(), "member", "members")
+                        .count(users.len(), "user resolved", "users resolved"),
+                );
+
+                final_output = out.format_users(&users)?;
+            }
+            ConversationsCommands::MembersDiff {
+                channel_a,
+                channel_b,
+                ids_only,
+                limit,
+            } => {
+                let id_a = api::channels::resolve_channel_id(&client, &channel_a).await?;
+                let id_b = api::channels::resolve_channel_id(&client, &channel_b).await?;
+                let members_a = api::channels::get_members(&client, &id_a, limit).await?;
+                let members_b = api::channels::get_members(&client, &id_b, limit).await?;
+                let diff = output::user_formatter::MemberDiff::new(&members_a, &members_b);
+
+                let (only_a, only_b) = (format!("only in {}", channel_a), format!("only in {}", channel_b));
+                summary = Some(
+                    output::summary::Summary::new()
+                        .count(diff.only_a.len(), &only_a, &only_a)
+                        .count(diff.only_b.len(), &only_b, &only_b)
+                        .count(diff.both.len(), "in both", "in both"),
+                );
+
+                final_output = if ids_only {
+                    match out.serialized() {
+                        Some(serialized) => serialized.write(&diff)?,
+                        None => output::user_formatter::format_member_diff_ids(&diff),
+                    }
+                } else {
+                    let users = api::users::resolve_user_map(&client, diff.ids().cloned(), &mut lookups).await?;
+                    out.format_member_diff(&diff.resolve(users), &channel_a, &channel_b)?
+                };
+            }
+            ConversationsCommands::Grep {
+                channel,
+                pattern,
+                replace,
+                ignore_case,
+                days,
+            } => {
+                let regex = regex::RegexBuilder::new(&pattern)
+                    .case_insensitive(ignore_case)
+                    .build()
+                    .with_context(|| format!("Invalid pattern '{}'", pattern))?;
+                let oldest = days.map(days_ago_ts).transpose()?;
+
+                let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
+                let messages = api::messages::list_all_messages(&client, &channel_id, oldest).await?;
+                let found = output::grep_formatter::grep_messages(&messages, &channel_id, &regex, replace.as_deref(), &render);
+
+                summary = Some(
+                    output::summary::Summary::new()
+                        .count(messages.len(), "message scanned", "messages scanned")
+                        .count(found.len(), "match", "matches"),
+                );
+
+                final_output = match out.serialized() {
+                    Some(serialized) => serialized.write(&found)?,
+                    None => {
+                        let channel_info = api::channels::get_channel(&client, &channel_id).await?;
+                        let user_map =
+                            api::users::resolve_user_map(&client, found.iter().filter_map(|m| m.user.clone()), &mut lookups)
+                                .await?;
+
+                        let mut writer = new_writer();
+                        output::grep_formatter::format_grep_matches(
+                            &found,
+                            &regex,
+                            &channel_info.name,
+                            &user_map,
+                            &mut writer,
+                        )?;
+                        writer.into_string()?
+                    }
+                };
+            }
+            ConversationsCommands::Reactions {
+                channel,
+                days,
+                top,
+                emoji_only,
+            } => {
+                let oldest = days.map(days_ago_ts).transpose()?;
+
+                let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
+                let messages = api::messages::list_all_messages(&client, &channel_id, oldest).await?;
+
+                let mut board = output::reaction_formatter::tally_reactions(&messages, top, render.normalize_skin_tones);
+                if emoji_only {
+                    board.reactors.clear();
+                }
+
+                summary = Some(
+                    output::summary::Summary::new()
+                        .count(messages.len(), "message", "messages")
+                        .count(board.reactions.len(), "emoji", "emoji"),
+                );
+
+                final_output = match out.serialized() {
+                    Some(serialized) => serialized.write(&board)?,
+                    None => {
+                        let channel_info = api::channels::get_channel(&client, &channel_id).await?;
+
+                        // Resolve reactor names (cache-first)
+                        let user_map =
+                            api::users::resolve_user_map(&client, board.reactors.iter().map(|r| r.user.clone()), &mut lookups)
+                                .await?;
+
+                        let mut writer = new_writer();
+                        output::reaction_formatter::format_reaction_leaderboard(
+                            &board,
+                            &channel_info.name,
+                            &user_map,
+                            &mut writer,
+                        )?;
                         writer.into_string()?
                     }
                 }
             }
+            ConversationsCommands::OpenLast { channel } => {
+                let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
+
+                let messages = api::messages::list_messages(&client, &channel_id, 1, None, None).await?;
+                let latest = messages
+                    .first()
+                    .ok_or_else(|| anyhow::anyhow!("No messages found in {}", channel))?;
+                let permalink = api::chat::get_permalink(&client, &channel_id, &latest.ts).await?;
+
+                final_output = match out.serialized() {
+                    Some(serialized) => serialized.write(&serde_json::json!({
+                        "channel": channel_id,
+                        "ts": latest.ts,
+                        "permalink": permalink,
+                    }))?,
+                    None => format!("{}\n", permalink),
+                };
+                open_url = Some(permalink);
+            }
+            ConversationsCommands::Leave { matching, dry_run, yes } => {
+                let mut channels: Vec<models::channel::Channel> = api::channels::search_channels(&client, &matching, false)
+                    .await?
+                    .into_iter()
+                    .filter(|c| c.is_member == Some(true))
+                    .collect();
+                channels.sort_by(|a, b| a.name.cmp(&b.name));
+
+                let noun = if channels.len() == 1 { "channel" } else { "channels" };
+
+                if channels.is_empty() {
+                    println!("You're not in any channels matching '{}'", matching);
+                } else {
+                    println!("{} {} matching '{}':", channels.len(), noun, matching);
+                    for channel in &channels {
+                        println!("  #{} ({})", channel.name, channel.id);
+                    }
+
+                    if dry_run {
+                        println!("\nDry run: no channels were left");
+                    } else if !yes && !confirm(&format!("Leave {} {}?", channels.len(), noun))? {
+                        println!("Aborted");
+                    } else {
+                        // Rate limits are handled by the client's Retry-After backoff
+                        let mut failed = 0;
+                        for channel in &channels {
+                            match api::channels::leave_channel(&client, &channel.id).await {
+                                Ok(()) => println!("✓ Left #{}", channel.name),
+                                Err(e) => {
+                                    failed += 1;
+                                    eprintln!("✗ Failed to leave #{}: {}", channel.name, e);
+                                }
+                            }
+                        }
+
+                        println!("\nLeft {} of {} {}", channels.len() - failed, channels.len(), noun);
+                        if failed > 0 {
+                            anyhow::bail!("Failed to leave {} of {} {}", failed, channels.len(), noun);
+                        }
+                    }
+                }
+            }
+            ConversationsCommands::Create {
+                name,
+                private,
+                from_template,
+            } => {
+                // Read the template before creating anything so a bad file doesn't leave a bare channel
+                let template: models::template::ChannelTemplate = match from_template {
+                    Some(ref path) => {
+                        let contents = std::fs::read_to_string(path)
+                            .with_context(|| format!("Failed to read template {}", path.display()))?;
+                        toml::from_str(&contents).with_context(|| format!("Invalid template {}", path.display()))?
+                    }
+                    None => Default::default(),
+                };
+
+                let name = name.strip_prefix('#').unwrap_or(&name);
+                let channel = api::channels::create_channel(&client, name, private).await?;
+                println!("✓ Created #{} ({})", channel.name, channel.id);
+                open_url = Some(format!("https://slack.com/app_redirect?channel={}", channel.id));
+
+                // Apply each step in order; keep going past failures and report them at the end
+                let mut failed = 0;
+                let mut report = |step: String, result: anyhow::Result<()>| match result {
+                    Ok(()) => println!("✓ {}", step),
+                    Err(e) => {
+                        failed += 1;
+                        eprintln!("✗ {} failed: {}", step, e);
+                    }
+                };
+
+                if let Some(ref topic) = template.topic {
+                    report("Set topic".to_string(), api::channels::set_topic(&client, &channel.id, topic).await);
+                }
+                if let Some(ref purpose) = template.purpose {
+                    report(
+                        "Set purpose".to_string(),
+                        api::channels::set_purpose(&client, &channel.id, purpose).await,
+                    );
+                }
+                if !template.members.is_empty() {
+                    let mut user_ids = Vec::new();
+                    for member in &template.members {
+                        match api::users::resolve_user_to_id(&client, member).await {
+                            Ok(id) => user_ids.push(id),
+                            Err(e) => report(format!("Resolve member {}", member), Err(e)),
+                        }
+                    }
+                    if !user_ids.is_empty() {
+                        report(
+                            format!("Invite {} member{}", user_ids.len(), if user_ids.len() == 1 { "" } else { "s" }),
+                            api::channels::invite_users(&client, &channel.id, &user_ids).await,
+                        );
+                    }
+                }
+                if let Some(ref message) = template.message {
+                    report(
+                        "Post welcome message".to_string(),
+                        api::chat::post_message(&client, &channel.id, message, None).await.map(|_| ()),
+                    );
+                }
+                for bookmark in &template.bookmarks {
+                    report(
+                        format!("Add bookmark '{}'", bookmark.title),
+                        api::bookmarks::add_bookmark(&client, &channel.id, &bookmark.title, &bookmark.link).await,
+                    );
+                }
+
+                if failed > 0 {
+                    anyhow::bail!("Created #{} but {} template step(s) failed", channel.name, failed);
+                }
+            }
         },
         Commands::Search { search_type } => match search_type {
             SearchType::Messages {
@@ -253,8 +973,12 @@ async fn main() -> Result<()> {
                 after,
                 before,
                 during,
+                after_ts,
+                before_ts,
                 page,
                 limit,
+                enrich,
+                oneline,
             } => {
                 // Validate --during if provided
                 if let Some(ref d) = during {
@@ -293,31 +1017,74 @@ async fn main() -> Result<()> {
                     during.as_deref(),
                 );
 
-                let response = api::search::search_messages(&client, &search_query, Some(limit), Some(page)).await?;
+                let mut response =
+                    api::search::search_messages_up_to(&client, &search_query, limit, page, cli.count).await?;
 
                 // Cache search result messages for offline access
                 api::search::cache_search_messages(&client, &response.messages.matches).await;
 
-                match cli.format.as_str() {
-                    "json" => final_output = serde_json::to_string_pretty(&response)?,
-                    "yaml" => final_output = serde_yaml::to_string(&response)?,
-                    _ => {
-                        // Build user lookup map from search results
-                        let mut user_map: std::collections::HashMap<String, models::user::User> =
-                            std::collections::HashMap::new();
+                // Second-precise window applied client-side to the fetched page
+                api::search::filter_by_ts_range(&mut response.messages.matches, after_ts, before_ts);
+
+                summary = Some(search_messages_summary(&response.messages));
 
+                match out.serialized() {
+                    Some(serialized) if enrich => {
+                        // Resolve each distinct author and channel once (cache-first)
+                        let user_map = api::users::resolve_user_map(
+                            &client,
+                            response.messages.matches.iter().filter_map(|m| m.user.clone()),
+                            &mut lookups,
+                        )
+                        .await?;
+
+                        let mut channel_map: std::collections::HashMap<String, models::channel::Channel> =
+                            std::collections::HashMap::new();
                         for message in &response.messages.matches {
-                            if let Some(user_id) = &message.user {
-                                if !user_map.contains_key(user_id) {
-                                    if let Ok(user) = api::users::get_user(&client, user_id).await {
-                                        user_map.insert(user.id.clone(), user);
+                            if let Some(channel_id) = message.channel.as_ref().map(|c| c.id()) {
+                                if !channel_map.contains_key(channel_id) {
+                                    if let Some(channel) = lookups.check(
+                                        &format!("channel {}", channel_id),
+                                        api::channels::get_channel(&client, channel_id).await,
+                                    )? {
+                                        channel_map.insert(channel.id.clone(), channel);
                                     }
                                 }
                             }
                         }
 
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::search_formatter::format_search_messages(&response, &user_map, &mut writer)?;
+                        let enriched =
+                            output::search_formatter::enrich_search_messages(&response, &user_map, &channel_map)?;
+                        final_output = serialized.write(&enriched)?;
+                    }
+                    Some(serialized) => final_output = serialized.write(&response)?,
+                    None => {
+                        // Build user lookup map from search results
+                        let user_map = api::users::resolve_user_map(
+                            &client,
+                            response.messages.matches.iter().filter_map(|m| m.user.clone()),
+                            &mut lookups,
+                        )
+                        .await?;
+
+                        // Older workspaces omit pagination; derive it so the next-page hint still shows
+                        let total = response.messages.total;
+                        response
+                            .messages
+                            .pagination
+                            .get_or_insert_with(|| models::search::SearchPagination::from_total(total, page, limit));
+
+                        let mut writer = new_writer();
+                        if oneline {
+                            output::message_formatter::format_messages_oneline(
+                                &response.messages.matches,
+                                None,
+                                &user_map,
+                                &mut writer,
+                            )?;
+                        } else {
+                            output::search_formatter::format_search_messages(&response, &user_map, &mut writer)?;
+                        }
                         final_output = writer.into_string()?;
                     }
                 }
@@ -364,13 +1131,23 @@ async fn main() -> Result<()> {
                     during.as_deref(),
                 );
 
-                let response = api::search::search_files(&client, &search_query, Some(limit), Some(page)).await?;
-
-                match cli.format.as_str() {
-                    "json" => final_output = serde_json::to_string_pretty(&response)?,
-                    "yaml" => final_output = serde_yaml::to_string(&response)?,
-                    _ => {
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
+                let mut response = api::search::search_files_up_to(&client, &search_query, limit, page, cli.count).await?;
+                summary = Some(output::summary::Summary::new().count(
+                    response.files.total as usize,
+                    "file",
+                    "files",
+                ));
+
+                match out.serialized() {
+                    Some(serialized) => final_output = serialized.write(&response)?,
+                    None => {
+                        let total = response.files.total;
+                        response
+                            .files
+                            .pagination
+                            .get_or_insert_with(|| models::search::SearchPagination::from_total(total, page, limit));
+
+                        let mut writer = new_writer();
                         output::search_formatter::format_search_files(&response, &mut writer)?;
                         final_output = writer.into_string()?;
                     }
@@ -379,6 +1156,9 @@ async fn main() -> Result<()> {
             SearchType::All {
                 query,
                 channel,
+                interleave,
+                messages_only,
+                files_only,
                 page,
                 limit,
             } => {
@@ -403,26 +1183,51 @@ async fn main() -> Result<()> {
                 // Cache search result messages for offline access
                 api::search::cache_search_messages(&client, &response.messages.matches).await;
 
-                match cli.format.as_str() {
-                    "json" => final_output = serde_json::to_string_pretty(&response)?,
-                    "yaml" => final_output = serde_yaml::to_string(&response)?,
-                    _ => {
+                summary = Some(if files_only {
+                    output::summary::Summary::new().count(response.files.total as usize, "file", "files")
+                } else if messages_only {
+                    search_messages_summary(&response.messages)
+                } else {
+                    search_messages_summary(&response.messages).count(
+                        response.files.total as usize,
+                        "total file",
+                        "total files",
+                    )
+                });
+
+                match out.serialized() {
+                    _ if files_only => {
+                        let response = response.into_files();
+                        final_output = match out.serialized() {
+                            Some(serialized) => serialized.write(&response)?,
+                            None => {
+                                // Files need no user lookups
+                                let mut writer = new_writer();
+                                output::search_formatter::format_search_files(&response, &mut writer)?;
+                                writer.into_string()?
+                            }
+                        };
+                    }
+                    Some(serialized) if messages_only => final_output = serialized.write(&response.into_messages())?,
+                    Some(serialized) if interleave => final_output = serialized.write(&response.timeline())?,
+                    Some(serialized) => final_output = serialized.write(&response)?,
+                    None => {
                         // Build user lookup map from search results
-                        let mut user_map: std::collections::HashMap<String, models::user::User> =
-                            std::collections::HashMap::new();
+                        let user_map = api::users::resolve_user_map(
+                            &client,
+                            response.messages.matches.iter().filter_map(|m| m.user.clone()),
+                            &mut lookups,
+                        )
+                        .await?;
 
-                        for message in &response.messages.matches {
-                            if let Some(user_id) = &message.user {
-                                if !user_map.contains_key(user_id) {
-                                    if let Ok(user) = api::users::get_user(&client, user_id).await {
-                                        user_map.insert(user.id.clone(), user);
-                                    }
-                                }
-                            }
+                        let mut writer = new_writer();
+                        if messages_only {
+                            output::search_formatter::format_search_messages(&response.into_messages(), &user_map, &mut writer)?;
+                        } else if interleave {
+                            output::search_formatter::format_search_all_interleaved(&response, &user_map, &mut writer)?;
+                        } else {
+                            output::search_formatter::format_search_all(&response, &user_map, &mut writer)?;
                         }
-
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::search_formatter::format_search_all(&response, &user_map, &mut writer)?;
                         final_output = writer.into_string()?;
                     }
                 }
@@ -430,63 +1235,106 @@ async fn main() -> Result<()> {
             SearchType::Channels {
                 query,
                 include_archived,
+                only_archived,
+                public_only,
+                private_only,
             } => {
-                let channels = api::channels::search_channels(&client, &query, include_archived).await?;
-
-                match cli.format.as_str() {
-                    "json" => final_output = serde_json::to_string_pretty(&channels)?,
-                    "yaml" => final_output = serde_yaml::to_string(&channels)?,
-                    _ => {
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::search_formatter::format_channel_search_results(&query, &channels, &mut writer)?;
-                        final_output = writer.into_string()?;
-                    }
+                let mut channels =
+                    api::channels::search_channels(&client, &query, include_archived || only_archived).await?;
+                if only_archived {
+                    channels.retain(|c| c.is_archived.unwrap_or(false));
+                }
+                if public_only || private_only {
+                    channels.retain(|c| c.is_private.unwrap_or(false) == private_only);
                 }
+                summary = Some(output::summary::Summary::new().count(channels.len(), "channel", "channels"));
+
+                final_output = out.format_channel_search(&query, &channels)?;
             }
         },
         Commands::Files { command } => match command {
-            FilesCommands::List { limit, user, channel } => {
-                let files = api::files::list_files(&client, limit, user.as_deref(), channel.as_deref()).await?;
-
-                final_output = match cli.format.as_str() {
-                    "json" => serde_json::to_string_pretty(&files)?,
-                    "yaml" => serde_yaml::to_string(&files)?,
-                    _ => {
-                        // Build user lookup map
-                        let mut user_map: std::collections::HashMap<String, models::user::User> =
-                            std::collections::HashMap::new();
-
-                        for file in &files {
-                            if !user_map.contains_key(&file.user) {
-                                if let Ok(user) = api::users::get_user(&client, &file.user).await {
-                                    user_map.insert(user.id.clone(), user);
-                                }
-                            }
+            FilesCommands::List {
+                limit,
+                user,
+                channel,
+                group_by,
+                unused,
+                older_than,
+            } => {
+                let files = api::files::list_files(&client, limit, user.as_deref(), channel.as_deref(), cli.count).await?;
+                summary = Some(output::summary::Summary::new().count(files.len(), "file", "files"));
+
+                final_output = if unused {
+                    let now = chrono::Utc::now().timestamp();
+                    let report = output::file_formatter::unused_files(&files, older_than, now);
+                    summary = Some(output::summary::Summary::new().count(report.files.len(), "unused file", "unused files"));
+                    out.format_unused_files(&report, now)?
+                } else if let Some(by) = group_by {
+                    let labels = match by {
+                        output::file_formatter::FileGroupBy::User => {
+                            api::users::resolve_user_map(&client, files.iter().map(|f| f.user.clone()), &mut lookups)
+                                .await?
+                                .into_iter()
+                                .map(|(id, user)| (id, format!("@{}", user.name)))
+                                .collect()
+                        }
+                        output::file_formatter::FileGroupBy::Channel => {
+                            api::files::resolve_conversation_labels(&client, &files).await
                         }
+                    };
+                    let groups = output::file_formatter::group_files(&files, by, &labels);
 
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::file_formatter::format_files_list(&files, &user_map, &mut writer)?;
-                        writer.into_string()?
+                    out.format_file_groups(&groups, by)?
+                } else {
+                    match out.serialized() {
+                        Some(serialized) => serialized.write(&files)?,
+                        None => {
+                            // Build user lookup map
+                            let user_map =
+                                api::users::resolve_user_map(&client, files.iter().map(|f| f.user.clone()), &mut lookups)
+                                    .await?;
+
+                            let mut writer = new_writer();
+                            output::file_formatter::format_files_list(
+                                &files,
+                                &user_map,
+                                &std::collections::HashMap::new(),
+                                &mut writer,
+                            )?;
+                            writer.into_string()?
+                        }
                     }
-                }
+                };
             }
-            FilesCommands::Info { file_id } => {
+            FilesCommands::Info {
+                file_id,
+                channels,
+                versions,
+            } => {
                 let file = api::files::get_file(&client, &file_id).await?;
+                open_url = file.permalink.clone();
+
+                final_output = match out.serialized() {
+                    Some(serialized) => serialized.write(&file)?,
+                    None => {
+                        // The uploader, plus revision editors with --versions
+                        let mut user_ids = vec![file.user.clone()];
+                        if versions {
+                            user_ids.extend(file.revisions().into_iter().filter_map(|v| v.user));
+                        }
+                        let user_map = api::users::resolve_user_map(&client, user_ids, &mut lookups).await?;
 
-                final_output = match cli.format.as_str() {
-                    "json" => serde_json::to_string_pretty(&file)?,
-                    "yaml" => serde_yaml::to_string(&file)?,
-                    _ => {
-                        // Build user lookup map for the single file uploader
-                        let mut user_map: std::collections::HashMap<String, models::user::User> =
-                            std::collections::HashMap::new();
+                        let shared_in = if channels {
+                            Some(api::files::resolve_shared_in(&client, &file).await)
+                        } else {
+                            None
+                        };
 
-                        if let Ok(user) = api::users::get_user(&client, &file.user).await {
-                            user_map.insert(user.id.clone(), user);
+                        let mut writer = new_writer();
+                        output::file_formatter::format_file(&file, &user_map, shared_in, &mut writer)?;
+                        if versions {
+                            output::file_formatter::format_file_versions(&file, &user_map, &mut writer)?;
                         }
-
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::file_formatter::format_file(&file, &user_map, &mut writer)?;
                         writer.into_string()?
                     }
                 }
@@ -498,13 +1346,19 @@ async fn main() -> Result<()> {
                 let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
 
                 let pins = api::pins::list_pins(&client, &channel_id).await?;
-
-                final_output = match cli.format.as_str() {
-                    "json" => serde_json::to_string_pretty(&pins)?,
-                    "yaml" => serde_yaml::to_string(&pins)?,
-                    _ => {
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::pin_formatter::format_pins_list(&pins, &mut writer)?;
+                summary = Some(output::summary::Summary::new().count(pins.len(), "pinned item", "pinned items"));
+
+                final_output = match out.serialized() {
+                    Some(serialized) => serialized.write(&pins)?,
+                    None => {
+                        // Resolve pinners and message authors to names (cache-first)
+                        let user_ids = pins.iter().flat_map(|p| {
+                            std::iter::once(p.created_by.clone()).chain(p.message.as_ref().and_then(|m| m.user.clone()))
+                        });
+                        let user_map = api::users::resolve_user_map(&client, user_ids, &mut lookups).await?;
+
+                        let mut writer = new_writer();
+                        output::pin_formatter::format_pins_list(&pins, &user_map, &mut writer)?;
                         writer.into_string()?
                     }
                 }
@@ -527,7 +1381,12 @@ async fn main() -> Result<()> {
             }
         },
         Commands::Reactions { command } => match command {
-            ReactionsCommands::Add { channel, message_ts, emoji } => {
+            ReactionsCommands::Add { channel, message_ts, emoji, validate } => {
+                let emoji = api::emoji::reaction_name(&emoji)?;
+                if validate {
+                    api::emoji::validate_emoji(&client, &emoji).await?;
+                }
+
                 // Resolve channel name to ID if needed
                 let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
 
@@ -535,17 +1394,40 @@ async fn main() -> Result<()> {
 
                 println!("✓ Reaction :{}: added successfully", emoji);
             }
-            ReactionsCommands::Remove { channel, message_ts, emoji } => {
+            ReactionsCommands::Remove { channel, message_ts, emoji, all } => {
                 // Resolve channel name to ID if needed
                 let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
 
-                api::reactions::remove_reaction(&client, &channel_id, &message_ts, &emoji).await?;
+                if all {
+                    let user_id = api::auth::current_user_id(&client).await?;
+                    let result =
+                        api::reactions::remove_my_reactions(&client, &channel_id, &message_ts, &user_id).await?;
 
-                println!("✓ Reaction :{}: removed successfully", emoji);
+                    let names: Vec<String> = result.removed.iter().map(|n| format!(":{}:", n)).collect();
+                    match result.removed.len() {
+                        0 => println!("No reactions of yours to remove"),
+                        1 => println!("✓ Removed 1 reaction ({})", names.join(" ")),
+                        n => println!("✓ Removed {} reactions ({})", n, names.join(" ")),
+                    }
+                    if !result.already_gone.is_empty() {
+                        println!("  {} already removed elsewhere", result.already_gone.len());
+                    }
+                } else if let Some(emoji) = emoji {
+                    let emoji = api::emoji::reaction_name(&emoji)?;
+                    api::reactions::remove_reaction(&client, &channel_id, &message_ts, &emoji).await?;
+
+                    println!("✓ Reaction :{}: removed successfully", emoji);
+                }
             }
         },
         Commands::Chat { command } => match command {
-            ChatCommands::Post { channel, text, thread_ts } => {
+            ChatCommands::Post {
+                channel,
+                text,
+                thread_ts,
+                file,
+                yes,
+            } => {
                 // Resolve channel name to ID if needed
                 let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
 
@@ -559,29 +1441,215 @@ async fn main() -> Result<()> {
                     text.clone()
                 };
 
-                let ts = api::chat::post_message(&client, &channel_id, &message_text, thread_ts.as_deref()).await?;
+                // Fuzzy channel resolution makes it easy to pick the wrong channel, so show
+                // what is about to go where. Scripts (stdin not a terminal, or text read
+                // from stdin) and --yes skip the prompt.
+                let interactive = !yes && text != "-" && atty::is(atty::Stream::Stdin);
+                if interactive && !confirm_post(&client, &channel_id, &message_text, file.as_deref(), &new_writer()).await? {
+                    println!("Aborted");
+                } else if let Some(path) = file {
+                    let bytes = std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+                    let filename = path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| path.display().to_string());
+                    let uploaded = api::files::upload_file(
+                        &client,
+                        &channel_id,
+                        &filename,
+                        bytes,
+                        &message_text,
+                        thread_ts.as_deref(),
+                    )
+                    .await?;
+
+                    println!("✓ File uploaded and shared");
+                    println!("File ID: {}", uploaded.file_id);
+                    match uploaded.ts {
+                        Some(ts) => println!("Message timestamp: {}", ts),
+                        None => println!("Message timestamp: not reported yet (see `clack files info {}`)", uploaded.file_id),
+                    }
+                } else {
+                    let ts = api::chat::post_message(&client, &channel_id, &message_text, thread_ts.as_deref()).await?;
 
-                println!("✓ Message posted successfully");
-                println!("Message timestamp: {}", ts);
+                    println!("✓ Message posted successfully");
+                    println!("Message timestamp: {}", ts);
+                }
+            }
+            ChatCommands::Permalink { channel, message_ts } => {
+                let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
+                let permalink = api::chat::get_permalink(&client, &channel_id, &message_ts).await?;
+
+                final_output = match out.serialized() {
+                    Some(serialized) => serialized.write(&serde_json::json!({
+                        "channel": channel_id,
+                        "ts": message_ts,
+                        "permalink": permalink,
+                    }))?,
+                    None => format!("{}\n", permalink),
+                };
+                open_url = Some(permalink);
             }
         },
         Commands::Auth { auth_type } => match auth_type {
-            AuthType::Test => {
+            AuthType::Test { .. } => {
                 let auth_response = api::auth::test_auth(&client).await?;
 
-                final_output = match cli.format.as_str() {
-                    "json" => serde_json::to_string_pretty(&auth_response)?,
-                    "yaml" => serde_yaml::to_string(&auth_response)?,
-                    _ => {
-                        let mut writer = output::color::ColorWriter::new(cli.no_color);
-                        output::auth_formatter::format_auth_test(&auth_response, &mut writer)?;
-                        writer.into_string()?
+                final_output = out.format_auth_test(&auth_response)?;
+            }
+        },
+        Commands::Export { command } => match command {
+            ExportCommands::Threads { channel, output_dir } => {
+                // --format picks the per-file format; the human default means Markdown
+                let extension = match cli.format.as_str() {
+                    "human" | "markdown" => "md",
+                    "json" => "json",
+                    "yaml" => "yaml",
+                    other => anyhow::bail!("export threads writes markdown, json or yaml, not '{}'", other),
+                };
+
+                let channel_id = api::channels::resolve_channel_id(&client, &channel).await?;
+                let channel_info = api::channels::get_channel(&client, &channel_id).await?;
+                let history = api::messages::list_all_messages(&client, &channel_id, None).await?;
+                let parents: Vec<&models::message::Message> = history
+                    .iter()
+                    .filter(|m| m.thread_ts.as_deref() == Some(m.ts.as_str()))
+                    .collect();
+
+                std::fs::create_dir_all(&output_dir)
+                    .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+
+                let mut user_map: std::collections::HashMap<String, models::user::User> =
+                    std::collections::HashMap::new();
+                let mut written = 0;
+                let mut skipped = 0;
+                for parent in parents.iter().copied() {
+                    let path = output_dir.join(format!("{}.{}", parent.ts, extension));
+                    if path.exists() {
+                        skipped += 1;
+                        continue;
                     }
+
+                    let Some(thread) = lookups.check(
+                        &format!("thread {}", parent.ts),
+                        api::messages::get_thread(&client, &channel_id, &parent.ts).await,
+                    )?
+                    else {
+                        continue;
+                    };
+
+                    let contents = if extension == "md" {
+                        let missing: Vec<String> = thread
+                            .iter()
+                            .filter_map(|m| m.user.clone())
+                            .filter(|id| !user_map.contains_key(id))
+                            .collect();
+                        user_map.extend(api::users::resolve_user_map(&client, missing, &mut lookups).await?);
+                        output::thread_formatter::format_thread_markdown(&thread, &channel_info, &user_map, &render)
+                    } else {
+                        // Files are for archiving, so always pretty-print
+                        output::serialize(&thread, &cli.format, Some(true))?
+                    };
+                    std::fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+                    written += 1;
                 }
+
+                summary = Some(
+                    output::summary::Summary::new()
+                        .count(written, "thread exported", "threads exported")
+                        .count(skipped, "already present", "already present"),
+                );
+                final_output = format!(
+                    "Exported {} of {} thread{} from #{} to {} ({} already exported)\n",
+                    written,
+                    parents.len(),
+                    if parents.len() == 1 { "" } else { "s" },
+                    channel_info.name,
+                    output_dir.display(),
+                    skipped
+                );
             }
         },
+        Commands::Warm { channels, oldest } => {
+            let mut total = 0;
+            for channel in &channels {
+                let channel_id = api::channels::resolve_channel_id(&client, channel).await?;
+                let cached = api::messages::warm_cache(&client, &channel_id, oldest.clone()).await?;
+                eprintln!("Cached {} message{} from {}", cached, if cached == 1 { "" } else { "s" }, channel);
+                total += cached;
+            }
+            summary = Some(
+                output::summary::Summary::new()
+                    .count(total, "message", "messages")
+                    .count(channels.len(), "channel", "channels"),
+            );
+        }
+        Commands::Cache { command } => {
+            let pool = client.cache_pool().ok_or_else(|| {
+                anyhow::anyhow!("Cache is not available (disabled with --no-cache or failed to initialize)")
+            })?;
+            let mut conn = cache::get_connection(pool).await?;
+            let workspace_id = client
+                .workspace_id()
+                .ok_or_else(|| anyhow::anyhow!("Workspace ID not initialized"))?;
+
+            match command {
+                CacheCommands::Export { file } => {
+                    let export = cache::operations::export_workspace(&mut conn, workspace_id)?;
+                    std::fs::write(&file, serde_json::to_string_pretty(&export)?)?;
+
+                    println!(
+                        "✓ Exported {} users, {} conversations, {} messages to {}",
+                        export.users.len(),
+                        export.conversations.len(),
+                        export.messages.len(),
+                        file.display()
+                    );
+                }
+                CacheCommands::Import { file } => {
+                    let contents = std::fs::read_to_string(&file)?;
+                    let value: serde_json::Value = serde_json::from_str(&contents)
+                        .map_err(|e| anyhow::anyhow!("Invalid cache export {}: {}", file.display(), e))?;
+                    let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+                    if version != cache::operations::CACHE_EXPORT_VERSION as u64 {
+                        anyhow::bail!(
+                            "Unsupported cache export version {} in {} (expected {}); re-export it with this version of clack",
+                            version,
+                            file.display(),
+                            cache::operations::CACHE_EXPORT_VERSION
+                        );
+                    }
+                    let export: cache::operations::CacheExport = serde_json::from_value(value)
+                        .map_err(|e| anyhow::anyhow!("Invalid cache export {}: {}", file.display(), e))?;
+
+                    if export.workspace_id != workspace_id {
+                        eprintln!(
+                            "Warning: export is from workspace {} but the current workspace is {}; \
+                             entries are imported under {} and won't be used until that workspace is active",
+                            export.workspace_id, workspace_id, export.workspace_id
+                        );
+                    }
+
+                    let stats = cache::operations::import_workspace(&mut conn, &export)?;
+
+                    println!(
+                        "✓ Imported {} users, {} conversations, {} messages from {}",
+                        export.users.len(),
+                        export.conversations.len(),
+                        export.messages.len(),
+                        file.display()
+                    );
+                    if stats.skipped > 0 {
+                        println!("  {} entries skipped because the local cache is newer", stats.skipped);
+                    }
+                }
+            }
+        }
         Commands::Stream {
             interval,
+            state_file,
+            prefix,
+            skip_initial,
             stream_type,
         } => {
             // For streaming, use human-compact if default "human" format is specified
@@ -599,6 +1667,8 @@ async fn main() -> Result<()> {
                         to,
                         channel,
                         has,
+                        exclude_bots,
+                        ..
                     } => {
                         // Resolve user identifiers to IDs
                         let resolved_from = if let Some(ref user) = from {
@@ -646,13 +1716,24 @@ async fn main() -> Result<()> {
                             &search_query,
                             interval,
                             effective_format,
-                            cli.no_color,
+                            new_writer,
+                            state_file.as_deref(),
+                            exclude_bots,
+                            prefix.as_deref(),
+                            skip_initial,
                         )
                         .await?;
                     }
                 },
             }
         }
+        Commands::Version { .. } => unreachable!("handled before the client is created"),
+    }
+
+    if let Some(path) = &json_path {
+        let value: serde_json::Value = serde_json::from_str(&final_output)
+            .context("--json-path is not supported by this command (it produced no json output)")?;
+        final_output = output::jsonpath::render(&output::jsonpath::select(&value, path));
     }
 
     // Output with pager if enabled
@@ -662,9 +1743,110 @@ async fn main() -> Result<()> {
         output_dest.finish()?;
     }
 
+    if cli.open {
+        match open_url {
+            Some(url) if atty::is(atty::Stream::Stdout) => {
+                eprintln!("Opening {}", url);
+                open::that(&url)?;
+            }
+            Some(_) => eprintln!("Warning: --open ignored because stdout is not a terminal"),
+            None => eprintln!("Warning: --open has no URL to open for this command"),
+        }
+    }
+
+    if let Some(warning) = lookups.warning() {
+        eprintln!("{}", warning);
+    }
+
+    // Summary goes to stderr so it never pollutes piped json/yaml output
+    if cli.summary {
+        if let Some(summary) = summary {
+            eprintln!("{}", summary);
+        }
+    }
+    if cli.summary || matches!(log_level, logging::LogLevel::Debug | logging::LogLevel::Trace) {
+        if let Some(stats) = client.rate_limit_stats() {
+            eprintln!("{}", stats);
+        }
+    }
+
     Ok(())
 }
 
+/// Build the --summary line for message search results.
+///
+/// `total` is Slack's count for the whole query; the shown, channel and user counts
+/// describe the fetched page after any client-side --after-ts/--before-ts filter.
+fn search_messages_summary(matches: &models::search::SearchMessagesMatches) -> output::summary::Summary {
+    let channels: std::collections::HashSet<&str> = matches
+        .matches
+        .iter()
+        .filter_map(|m| m.channel.as_ref().map(|c| c.id()))
+        .collect();
+    let users: std::collections::HashSet<&String> =
+        matches.matches.iter().filter_map(|m| m.user.as_ref()).collect();
+
+    output::summary::Summary::new()
+        .count(matches.total as usize, "total match", "total matches")
+        .count(matches.matches.len(), "shown", "shown")
+        .count(channels.len(), "channel", "channels")
+        .count(users.len(), "user", "users")
+}
+
+/// Slack timestamp for `days` days ago, as the `oldest` bound for `--days` options
+fn days_ago_ts(days: u32) -> Result<String> {
+    let since = chrono::Utc::now()
+        .checked_sub_signed(chrono::Duration::days(days as i64))
+        .ok_or_else(|| anyhow::anyhow!("--days {} reaches further back than any date", days))?;
+    Ok(since.timestamp().to_string())
+}
+
+/// Show where `chat post` is about to send and what, then ask to confirm
+async fn confirm_post(
+    client: &api::client::SlackClient,
+    channel_id: &str,
+    text: &str,
+    file: Option<&std::path::Path>,
+    writer: &output::color::ColorWriter,
+) -> anyhow::Result<bool> {
+    let target = match api::channels::get_channel(client, channel_id).await {
+        Ok(channel) => match channel.user {
+            Some(user_id) => match api::users::get_user(client, &user_id).await {
+                Ok(user) => format!("@{}", user.name),
+                Err(_) => format!("@{}", user_id),
+            },
+            None if !channel.name.is_empty() => format!("#{}", channel.name),
+            None => channel_id.to_string(),
+        },
+        Err(_) => channel_id.to_string(),
+    };
+
+    println!("Post to {} ({}):", target, channel_id);
+    for line in writer.message_text(text).lines() {
+        println!("  {}", line);
+    }
+    if let Some(path) = file {
+        println!("  + {}", path.display());
+    }
+    confirm("Send?")
+}
+
+/// Ask a yes/no question on the terminal; refuses when stdin isn't interactive
+fn confirm(prompt: &str) -> anyhow::Result<bool> {
+    use std::io::Write;
+
+    if !atty::is(atty::Stream::Stdin) {
+        anyhow::bail!("Refusing to continue without confirmation (stdin is not a terminal). Pass --yes to proceed.");
+    }
+
+    print!("{} [y/N] ", prompt);
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
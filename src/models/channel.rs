@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Channel {
     pub id: String,
+    /// Empty for DMs, which have no name
+    #[serde(default)]
     pub name: String,
     pub is_channel: Option<bool>,
     pub is_group: Option<bool>,
@@ -10,19 +12,54 @@ pub struct Channel {
     pub is_mpim: Option<bool>,
     pub is_private: Option<bool>,
     pub is_archived: Option<bool>,
+    // Whether the authenticated user is in the channel (conversations.list/info only)
+    pub is_member: Option<bool>,
     pub topic: Option<ChannelTopic>,
     pub purpose: Option<ChannelPurpose>,
     pub num_members: Option<u32>,
+    /// The other person in a DM
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ChannelTopic {
     pub value: String,
+    /// User who last set it; Slack sends "" when it was never set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    /// Unix time it was last set, 0 when never set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_set: Option<i64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ChannelPurpose {
     pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_set: Option<i64>,
+}
+
+/// Who set a topic or purpose and when, ignoring Slack's empty placeholders
+fn set_by(creator: &Option<String>, last_set: Option<i64>) -> (Option<&str>, Option<i64>) {
+    (
+        creator.as_deref().filter(|c| !c.is_empty()),
+        last_set.filter(|&t| t > 0),
+    )
+}
+
+impl ChannelTopic {
+    pub fn set_by(&self) -> (Option<&str>, Option<i64>) {
+        set_by(&self.creator, self.last_set)
+    }
+}
+
+impl ChannelPurpose {
+    pub fn set_by(&self) -> (Option<&str>, Option<i64>) {
+        set_by(&self.creator, self.last_set)
+    }
 }
 
 #[derive(Debug, Deserialize)]
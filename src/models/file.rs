@@ -21,6 +21,56 @@ pub struct File {
     pub channels: Option<Vec<String>>,
     pub groups: Option<Vec<String>>,
     pub ims: Option<Vec<String>>,
+    /// Last edit time, for files edited in Slack (posts, snippets, canvases)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_editor: Option<String>,
+    /// Earlier revisions, when `files.info` returns them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub versions: Option<Vec<FileVersion>>,
+}
+
+/// One revision of a file (`files info --versions`)
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FileVersion {
+    pub timestamp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+}
+
+impl File {
+    /// IDs of the conversations the file is shared in, flagged `true` for DMs
+    pub fn shared_conversations(&self) -> impl Iterator<Item = (&String, bool)> {
+        let channels = self.channels.iter().chain(self.groups.iter()).flatten();
+        let ims = self.ims.iter().flatten();
+        channels.map(|id| (id, false)).chain(ims.map(|id| (id, true)))
+    }
+
+    /// Known revisions, oldest first. Uses `versions` when Slack sends them, otherwise
+    /// the upload and the last edit. Empty for a file that was never edited.
+    pub fn revisions(&self) -> Vec<FileVersion> {
+        let mut revisions = match (&self.versions, self.updated) {
+            (Some(versions), _) if !versions.is_empty() => versions.clone(),
+            (_, Some(updated)) if updated > self.created => vec![
+                FileVersion {
+                    timestamp: self.created,
+                    user: Some(self.user.clone()),
+                    size: None,
+                },
+                FileVersion {
+                    timestamp: updated,
+                    user: self.last_editor.clone(),
+                    size: Some(self.size),
+                },
+            ],
+            _ => Vec::new(),
+        };
+        revisions.sort_by_key(|v| v.timestamp);
+        revisions
+    }
 }
 
 #[derive(Debug, Deserialize)]
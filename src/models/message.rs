@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Message {
@@ -10,6 +11,97 @@ pub struct Message {
     // Channel can be either a string (conversations.history) or object (search)
     pub channel: Option<MessageChannel>,
     pub permalink: Option<String>,
+    pub bot_id: Option<String>,
+    pub subtype: Option<String>,
+    // Present once the message has been edited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited: Option<MessageEdited>,
+    /// Number of replies, on thread roots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_count: Option<u32>,
+}
+
+impl Message {
+    /// Returns true if the message was posted by a bot or integration
+    pub fn is_bot(&self) -> bool {
+        self.bot_id.is_some() || self.subtype.as_deref() == Some("bot_message")
+    }
+
+    /// Returns true if the text @-mentions the user (`<@U123>` or `<@U123|name>`)
+    pub fn mentions(&self, user_id: &str) -> bool {
+        let tag = format!("<@{}", user_id);
+        self.text
+            .match_indices(&tag)
+            .any(|(i, _)| matches!(self.text[i + tag.len()..].chars().next(), Some('>' | '|')))
+    }
+
+    /// IDs of every user @-mentioned in the text, in order of appearance
+    pub fn mentioned_user_ids(&self) -> Vec<&str> {
+        self.text
+            .split("<@")
+            .skip(1)
+            .filter_map(|rest| rest.find(['>', '|']).map(|end| &rest[..end]))
+            .filter(|id| !id.is_empty())
+            .collect()
+    }
+
+    /// ts of the latest edit, or zero for unedited messages
+    fn edited_at(&self) -> (i64, u32) {
+        self.edited
+            .as_ref()
+            .and_then(|e| parse_ts(&e.ts))
+            .unwrap_or_default()
+    }
+}
+
+/// Split a Slack ts ("1717250400.000100") into whole seconds and microseconds.
+///
+/// Compare these instead of an `f64`: at current epoch values a double keeps only
+/// about 7 digits after the point, so neighbouring ts can round together. Returns
+/// `None` for anything that isn't digits with at most six after the point.
+pub fn parse_ts(ts: &str) -> Option<(i64, u32)> {
+    let (secs, frac) = ts.split_once('.').unwrap_or((ts, ""));
+    if frac.len() > 6 || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let secs = secs.parse().ok()?;
+    // "0001" means 100 microseconds, not 1
+    let micros = format!("{:0<6}", frac).parse().ok()?;
+    Some((secs, micros))
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MessageEdited {
+    pub user: Option<String>,
+    pub ts: String,
+}
+
+/// Collapse repeated copies of a message, keyed by (channel, ts), keeping the first
+/// position. Of two copies the more recently edited one wins; on a tie the later copy
+/// (the most recently fetched) does. Returns how many duplicates were dropped.
+pub fn dedupe_messages(messages: &mut Vec<Message>) -> usize {
+    let total = messages.len();
+    let mut positions: HashMap<(Option<String>, String), usize> = HashMap::new();
+    let mut kept: Vec<Message> = Vec::with_capacity(total);
+
+    for msg in std::mem::take(messages) {
+        let key = (msg.channel.as_ref().map(|c| c.id().to_string()), msg.ts.clone());
+        match positions.get(&key) {
+            Some(&i) => {
+                if msg.edited_at() >= kept[i].edited_at() {
+                    kept[i] = msg;
+                }
+            }
+            None => {
+                positions.insert(key, kept.len());
+                kept.push(msg);
+            }
+        }
+    }
+
+    let dropped = total - kept.len();
+    *messages = kept;
+    dropped
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -39,6 +131,44 @@ impl MessageChannel {
 pub struct Reaction {
     pub name: String,
     pub count: u32,
+    // IDs of users who reacted (may be truncated by Slack on busy messages)
+    pub users: Option<Vec<String>>,
+}
+
+/// The parts of a message permalink,
+/// `https://acme.slack.com/archives/C123/p1700000000000100?thread_ts=1699999999.000200`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Permalink {
+    pub channel_id: String,
+    /// ts of the linked message, which is a reply's own ts when it links into a thread
+    pub ts: String,
+    /// Root ts, present on links Slack generates for thread replies
+    pub thread_ts: Option<String>,
+}
+
+impl Permalink {
+    /// `None` unless the URL has an `/archives/<channel>/p<16 digits>` path
+    pub fn parse(url: &str) -> Option<Self> {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let mut segments = path.split("/archives/").nth(1)?.split('/');
+        let channel_id = segments.next().filter(|c| !c.is_empty())?.to_string();
+        let digits = segments.next()?.strip_prefix('p')?;
+        if digits.len() != 16 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let thread_ts = query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("thread_ts="))
+            .filter(|ts| !ts.is_empty())
+            .map(str::to_string);
+
+        Some(Permalink {
+            channel_id,
+            ts: format!("{}.{}", &digits[..10], &digits[10..]),
+            thread_ts,
+        })
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -46,12 +176,75 @@ pub struct MessagesResponse {
     pub ok: bool,
     pub messages: Vec<Message>,
     pub error: Option<String>,
+    pub response_metadata: Option<crate::models::channel::ResponseMetadata>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_ts() {
+        assert_eq!(parse_ts("1717250400.000100"), Some((1717250400, 100)));
+        assert_eq!(parse_ts("1717250400.0001"), Some((1717250400, 100)));
+        assert_eq!(parse_ts("1717250400"), Some((1717250400, 0)));
+        // One microsecond apart, which an f64 can't tell apart
+        assert!(parse_ts("1717250400.000101") > parse_ts("1717250400.000100"));
+        assert_eq!(parse_ts("1717250400.0000001"), None);
+        assert_eq!(parse_ts("soon"), None);
+        assert_eq!(parse_ts("1.-5"), None);
+    }
+
+    #[test]
+    fn test_mentions() {
+        let msg = |text: &str| -> Message { serde_json::from_value(serde_json::json!({"ts": "1.0", "text": text})).unwrap() };
+        assert!(msg("ping <@U123> please").mentions("U123"));
+        assert!(msg("cc <@U999> <@U123|alice>").mentions("U123"));
+        assert!(!msg("<@U1234> is someone else").mentions("U123"));
+        assert!(!msg("U123 without the markup").mentions("U123"));
+        assert_eq!(msg("cc <@U999> <@U123|alice> <#C1>").mentioned_user_ids(), vec!["U999", "U123"]);
+    }
+
+    #[test]
+    fn test_parse_permalink() {
+        let link = Permalink::parse("https://acme.slack.com/archives/C123/p1700000000000100").unwrap();
+        assert_eq!(link.channel_id, "C123");
+        assert_eq!(link.ts, "1700000000.000100");
+        assert_eq!(link.thread_ts, None);
+
+        let reply =
+            Permalink::parse("https://acme.slack.com/archives/C123/p1700000300000100?thread_ts=1700000000.000100&cid=C123")
+                .unwrap();
+        assert_eq!(reply.ts, "1700000300.000100");
+        assert_eq!(reply.thread_ts.as_deref(), Some("1700000000.000100"));
+
+        assert!(Permalink::parse("https://acme.slack.com/archives/C123").is_none());
+        assert!(Permalink::parse("https://acme.slack.com/archives/C123/p17000").is_none());
+        assert!(Permalink::parse("general").is_none());
+    }
+
+    #[test]
+    fn test_dedupe_messages_keeps_latest_edit() {
+        let msg = |ts: &str, text: &str, edited: Option<&str>| -> Message {
+            let mut value = serde_json::json!({"ts": ts, "text": text});
+            if let Some(edited_ts) = edited {
+                value["edited"] = serde_json::json!({"user": "U1", "ts": edited_ts});
+            }
+            serde_json::from_value(value).unwrap()
+        };
+        let mut messages = vec![
+            msg("3.0", "third", None),
+            msg("2.0", "second (edited)", Some("5.0")),
+            msg("1.0", "first", None),
+            msg("2.0", "second", None),
+            msg("3.0", "third (refetched)", None),
+        ];
+
+        assert_eq!(dedupe_messages(&mut messages), 2);
+        let texts: Vec<&str> = messages.iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, vec!["third (refetched)", "second (edited)", "first"]);
+    }
+
     #[test]
     fn test_message_channel_deserialize_string() {
         // Test deserialization of channel as string (conversations.history format)
@@ -179,6 +372,33 @@ mod tests {
         assert_eq!(reactions[1].count, 3);
     }
 
+    #[test]
+    fn test_message_is_bot() {
+        let human: Message = serde_json::from_str(
+            r#"{"ts": "1768596285.399169", "user": "U123", "text": "hi"}"#,
+        )
+        .unwrap();
+        assert!(!human.is_bot());
+
+        let with_bot_id: Message = serde_json::from_str(
+            r#"{"ts": "1768596285.399169", "user": "U123", "text": "hi", "bot_id": "B123"}"#,
+        )
+        .unwrap();
+        assert!(with_bot_id.is_bot());
+
+        let bot_subtype: Message = serde_json::from_str(
+            r#"{"ts": "1768596285.399169", "text": "deploy done", "subtype": "bot_message"}"#,
+        )
+        .unwrap();
+        assert!(bot_subtype.is_bot());
+
+        let other_subtype: Message = serde_json::from_str(
+            r#"{"ts": "1768596285.399169", "user": "U123", "text": "joined", "subtype": "channel_join"}"#,
+        )
+        .unwrap();
+        assert!(!other_subtype.is_bot());
+    }
+
     #[test]
     fn test_messages_response_deserialize_mixed_channels() {
         // Test MessagesResponse with a mix of string and object channels
@@ -3,5 +3,6 @@ pub mod file;
 pub mod message;
 pub mod pin;
 pub mod search;
+pub mod template;
 pub mod user;
 pub mod workspace;
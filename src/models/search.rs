@@ -70,3 +70,134 @@ pub struct SearchAllResponse {
     pub files: SearchFilesMatches,
     pub error: Option<String>,
 }
+
+/// One row of a merged message/file timeline; serializes with a `type` tag
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SearchHit<'a> {
+    Message(&'a Message),
+    File(&'a FileResult),
+}
+
+impl SearchPagination {
+    /// Derive paging info from a total and the requested page, for responses without `pagination`
+    pub fn from_total(total_count: u32, page: u32, per_page: u32) -> Self {
+        let per_page = per_page.max(1);
+        let page = page.max(1);
+        let page_count = total_count.div_ceil(per_page);
+        let first = ((page - 1) * per_page + 1).min(total_count);
+        let last = (page * per_page).min(total_count);
+        SearchPagination {
+            total_count,
+            page,
+            per_page,
+            page_count,
+            first,
+            last,
+        }
+    }
+
+    /// Whether pages after this one exist
+    pub fn has_more(&self) -> bool {
+        self.page < self.page_count
+    }
+}
+
+impl SearchHit<'_> {
+    /// Unix timestamp (seconds) used to order the timeline
+    pub fn timestamp(&self) -> f64 {
+        match self {
+            SearchHit::Message(msg) => msg.ts.parse().unwrap_or(0.0),
+            SearchHit::File(file) => file.timestamp as f64,
+        }
+    }
+}
+
+impl SearchAllResponse {
+    /// Just the message half, shaped like a `search.messages` response (`--messages-only`)
+    pub fn into_messages(self) -> SearchMessagesResponse {
+        SearchMessagesResponse {
+            ok: self.ok,
+            query: self.query,
+            messages: self.messages,
+            error: self.error,
+        }
+    }
+
+    /// Just the file half, shaped like a `search.files` response (`--files-only`)
+    pub fn into_files(self) -> SearchFilesResponse {
+        SearchFilesResponse {
+            ok: self.ok,
+            query: self.query,
+            files: self.files,
+            error: self.error,
+        }
+    }
+
+    /// Message and file matches merged into one list, newest first. Only the current page of
+    /// each half is merged, since search.all pages messages and files independently.
+    pub fn timeline(&self) -> Vec<SearchHit<'_>> {
+        let mut hits: Vec<SearchHit<'_>> = self
+            .messages
+            .matches
+            .iter()
+            .map(SearchHit::Message)
+            .chain(self.files.matches.iter().map(SearchHit::File))
+            .collect();
+        hits.sort_by(|a, b| b.timestamp().total_cmp(&a.timestamp()));
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeline_interleaves_by_timestamp() {
+        let response: SearchAllResponse = serde_json::from_value(serde_json::json!({
+            "ok": true,
+            "query": "deploy",
+            "messages": {"total": 2, "matches": [
+                {"ts": "1700000300.000100", "text": "newest message"},
+                {"ts": "1700000100.000100", "text": "oldest message"}
+            ]},
+            "files": {"total": 1, "matches": [{
+                "id": "F1", "created": 1700000200, "timestamp": 1700000200, "name": "deploy.log",
+                "title": "deploy.log", "mimetype": "text/plain", "filetype": "text",
+                "pretty_type": "Plain Text", "user": "U1", "size": 10
+            }]}
+        }))
+        .unwrap();
+
+        let timeline = response.timeline();
+        let order: Vec<String> = timeline
+            .iter()
+            .map(|hit| match hit {
+                SearchHit::Message(msg) => msg.text.clone(),
+                SearchHit::File(file) => file.name.clone(),
+            })
+            .collect();
+        assert_eq!(order, vec!["newest message", "deploy.log", "oldest message"]);
+
+        let json = serde_json::to_value(&timeline).unwrap();
+        assert_eq!(json[1]["type"], "file");
+        assert_eq!(json[1]["name"], "deploy.log");
+        assert_eq!(json[0]["type"], "message");
+    }
+
+    #[test]
+    fn test_pagination_from_total() {
+        let pagination = SearchPagination::from_total(45, 2, 20);
+        assert_eq!(pagination.page_count, 3);
+        assert_eq!((pagination.first, pagination.last), (21, 40));
+        assert!(pagination.has_more());
+
+        let last_page = SearchPagination::from_total(45, 3, 20);
+        assert_eq!((last_page.first, last_page.last), (41, 45));
+        assert!(!last_page.has_more());
+
+        let empty = SearchPagination::from_total(0, 1, 20);
+        assert_eq!((empty.page_count, empty.first, empty.last), (0, 0, 0));
+    }
+}
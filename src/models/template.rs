@@ -0,0 +1,61 @@
+use serde::Deserialize;
+
+/// Channel setup read from a TOML file (`conversations create --from-template`)
+///
+/// ```toml
+/// topic = "Project Falcon"
+/// purpose = "Coordination for the Falcon launch"
+/// members = ["@alice", "U0123ABCD"]
+/// message = "Welcome! Start with the runbook bookmark."
+///
+/// [[bookmarks]]
+/// title = "Runbook"
+/// link = "https://wiki.example.com/falcon"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelTemplate {
+    pub topic: Option<String>,
+    pub purpose: Option<String>,
+    /// User IDs or @names to invite
+    #[serde(default)]
+    pub members: Vec<String>,
+    /// First message posted to the new channel
+    pub message: Option<String>,
+    #[serde(default)]
+    pub bookmarks: Vec<TemplateBookmark>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateBookmark {
+    pub title: String,
+    pub link: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_channel_template() {
+        let template: ChannelTemplate = toml::from_str(
+            r#"
+            topic = "Project Falcon"
+            members = ["@alice", "U0123ABCD"]
+
+            [[bookmarks]]
+            title = "Runbook"
+            link = "https://wiki.example.com/falcon"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(template.topic.as_deref(), Some("Project Falcon"));
+        assert!(template.purpose.is_none());
+        assert_eq!(template.members, vec!["@alice", "U0123ABCD"]);
+        assert_eq!(template.bookmarks[0].title, "Runbook");
+
+        // Typos fail loudly instead of silently skipping a step
+        assert!(toml::from_str::<ChannelTemplate>("topics = \"x\"").is_err());
+    }
+}
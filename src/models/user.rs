@@ -1,3 +1,4 @@
+use super::channel::ResponseMetadata;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -11,6 +12,54 @@ pub struct User {
     pub is_admin: Option<bool>,
     pub is_owner: Option<bool>,
     pub tz: Option<String>,
+    /// "active" or "away"; only filled in by `users list --presence`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence: Option<String>,
+
+    /// Org-level membership, only sent on Enterprise Grid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enterprise_user: Option<EnterpriseUser>,
+}
+
+/// A user's Enterprise Grid org record (`enterprise_user` in users.info/users.list)
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EnterpriseUser {
+    pub id: Option<String>,
+    pub enterprise_id: String,
+    pub enterprise_name: Option<String>,
+    #[serde(default)]
+    pub is_admin: bool,
+    #[serde(default)]
+    pub is_owner: bool,
+    #[serde(default)]
+    pub is_primary_owner: bool,
+    /// IDs of the org's workspaces the user belongs to
+    #[serde(default)]
+    pub teams: Vec<String>,
+}
+
+impl User {
+    /// Link that opens this user's profile in Slack
+    pub fn profile_url(&self) -> String {
+        // Team ID would need to be fetched separately for a team-specific URL
+        format!("https://slack.com/app_redirect?channel={}", self.id)
+    }
+
+    /// A person rather than a bot, integration or Slackbot
+    pub fn is_human(&self) -> bool {
+        !self.is_bot && self.id != "USLACKBOT"
+    }
+
+    /// Whether `profile.email` is at `domain` (`corp.com` or `@corp.com`) or one of its
+    /// subdomains, ignoring case. Users without an email never match.
+    pub fn has_email_domain(&self, domain: &str) -> bool {
+        let domain = domain.trim_start_matches('@').to_ascii_lowercase();
+        let Some((_, email_domain)) = self.profile.email.as_deref().and_then(|email| email.rsplit_once('@')) else {
+            return false;
+        };
+        let email_domain = email_domain.to_ascii_lowercase();
+        email_domain == domain || email_domain.ends_with(&format!(".{}", domain))
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -27,6 +76,7 @@ pub struct UsersListResponse {
     pub ok: bool,
     pub members: Vec<User>,
     pub error: Option<String>,
+    pub response_metadata: Option<ResponseMetadata>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,8 +87,43 @@ pub struct UserInfoResponse {
 }
 
 #[derive(Debug, Deserialize)]
-pub struct UserProfileResponse {
+pub struct PresenceResponse {
     pub ok: bool,
-    pub profile: UserProfile,
+    pub presence: Option<String>,
     pub error: Option<String>,
 }
+
+/// `P` is `UserProfile`, or `serde_json::Value` to keep every field Slack sends
+#[derive(Debug, Deserialize)]
+pub struct UserProfileResponse<P = UserProfile> {
+    pub ok: bool,
+    pub profile: P,
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(id: &str, email: Option<&str>, is_bot: bool) -> User {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "name": id, "deleted": false, "is_bot": is_bot, "profile": {"email": email}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_has_email_domain() {
+        assert!(user("U1", Some("alice@Corp.com"), false).has_email_domain("corp.com"));
+        assert!(user("U1", Some("alice@eu.corp.com"), false).has_email_domain("@corp.com"));
+        assert!(!user("U1", Some("alice@notcorp.com"), false).has_email_domain("corp.com"));
+        assert!(!user("B1", None, true).has_email_domain("corp.com"));
+    }
+
+    #[test]
+    fn test_is_human() {
+        assert!(user("U1", None, false).is_human());
+        assert!(!user("B1", None, true).is_human());
+        assert!(!user("USLACKBOT", None, false).is_human());
+    }
+}
@@ -12,3 +12,21 @@ pub struct AuthTestResponse {
     pub is_enterprise_install: Option<bool>,
     pub error: Option<String>,
 }
+
+/// Outcome of auth.test for one token from `auth test --tokens-file`
+#[derive(Debug, Serialize)]
+pub struct TokenCheck {
+    /// Masked: type prefix and last four characters only
+    pub token: String,
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
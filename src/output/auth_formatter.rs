@@ -1,4 +1,4 @@
-use crate::models::workspace::AuthTestResponse;
+use crate::models::workspace::{AuthTestResponse, TokenCheck};
 use crate::output::color::ColorWriter;
 use std::io::Result;
 use termcolor::Color;
@@ -35,3 +35,72 @@ pub fn format_auth_test(auth: &AuthTestResponse, writer: &mut ColorWriter) -> Re
 
     Ok(())
 }
+
+/// One row per token: masked token, workspace, user, and whether it still works
+pub fn format_token_checks(checks: &[TokenCheck], writer: &mut ColorWriter) -> Result<()> {
+    let valid = checks.iter().filter(|c| c.valid).count();
+    writer.print_header(&format!("Tokens ({} valid, {} invalid)", valid, checks.len() - valid))?;
+    writer.print_separator()?;
+
+    let workspace = |c: &TokenCheck| match (&c.team, &c.team_id) {
+        (Some(team), Some(id)) => format!("{} ({})", team, id),
+        _ => "-".to_string(),
+    };
+    let token_width = checks.iter().map(|c| c.token.chars().count()).max().unwrap_or(0);
+    let workspace_width = checks.iter().map(|c| workspace(c).chars().count()).max().unwrap_or(0);
+    let user_width = checks.iter().map(|c| c.user.as_deref().unwrap_or("-").chars().count() + 1).max().unwrap_or(0);
+
+    for check in checks {
+        writer.print_colored(&format!("{:<width$}", check.token, width = token_width), Color::Yellow)?;
+        writer.write(&format!("  {:<width$}", workspace(check), width = workspace_width))?;
+        let user = check.user.as_deref().map(|u| format!("@{}", u)).unwrap_or_else(|| "-".to_string());
+        writer.write(&format!("  {:<width$}  ", user, width = user_width))?;
+        if check.valid {
+            writer.print_colored("valid", Color::Green)?;
+        } else {
+            writer.print_colored("invalid", Color::Red)?;
+            if let Some(error) = &check.error {
+                writer.write(&format!(" ({})", error))?;
+            }
+        }
+        writer.writeln()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_token_checks() {
+        let checks = vec![
+            TokenCheck {
+                token: "xoxb-...1234".to_string(),
+                valid: true,
+                team: Some("Team A".to_string()),
+                team_id: Some("TA".to_string()),
+                user: Some("bot".to_string()),
+                user_id: Some("UA".to_string()),
+                error: None,
+            },
+            TokenCheck {
+                token: "xoxb-...9999".to_string(),
+                valid: false,
+                team: None,
+                team_id: None,
+                user: None,
+                user_id: None,
+                error: Some("Slack API error: token_revoked".to_string()),
+            },
+        ];
+
+        let mut writer = ColorWriter::new(true);
+        format_token_checks(&checks, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("Tokens (1 valid, 1 invalid)"));
+        assert!(output.contains("xoxb-...1234  Team A (TA)  @bot  valid"));
+        assert!(output.contains("xoxb-...9999  -            -     invalid (Slack API error: token_revoked)"));
+    }
+}
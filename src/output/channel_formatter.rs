@@ -1,61 +1,308 @@
 use crate::models::channel::Channel;
+use crate::models::message::{parse_ts, Message};
+use crate::models::user::User;
 use crate::output::color::ColorWriter;
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::collections::HashMap;
 use std::io::Result;
 use termcolor::Color;
 
+/// How many of the busiest authors `--history-stats` names
+pub const TOP_PARTICIPANTS: usize = 3;
+
+#[derive(Debug, Serialize)]
+pub struct ParticipantCount {
+    pub user: String,
+    /// Resolved handle, filled in for the `history --participants` roster
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub messages: usize,
+}
+
+/// Activity over the last `days` days of a channel's history (`conversations info --history-stats`)
+#[derive(Debug, Serialize)]
+pub struct HistoryStats {
+    pub days: u32,
+    pub messages: usize,
+    pub participants: usize,
+    /// ts of the newest message in the window
+    pub last_activity: Option<String>,
+    pub top_participants: Vec<ParticipantCount>,
+}
+
+/// Messages per author, busiest first (ties by user ID); messages without a user are skipped
+pub fn participant_counts(messages: &[Message]) -> Vec<ParticipantCount> {
+    let mut per_user: HashMap<&str, usize> = HashMap::new();
+    for user in messages.iter().filter_map(|m| m.user.as_deref()) {
+        *per_user.entry(user).or_default() += 1;
+    }
+
+    let mut counts: Vec<ParticipantCount> = per_user
+        .iter()
+        .map(|(user, messages)| ParticipantCount {
+            user: user.to_string(),
+            name: None,
+            messages: *messages,
+        })
+        .collect();
+    // Sort by count descending, then ID so ties are stable
+    counts.sort_by(|a, b| b.messages.cmp(&a.messages).then_with(|| a.user.cmp(&b.user)));
+    counts
+}
+
+/// Count messages, distinct authors and the newest ts in a window of top-level history
+pub fn history_stats(messages: &[Message], days: u32) -> HistoryStats {
+    let mut top_participants = participant_counts(messages);
+    let participants = top_participants.len();
+    top_participants.truncate(TOP_PARTICIPANTS);
+
+    let last_activity = messages
+        .iter()
+        .max_by_key(|m| parse_ts(&m.ts).unwrap_or_default())
+        .map(|m| m.ts.clone());
+
+    HistoryStats {
+        days,
+        messages: messages.len(),
+        participants,
+        last_activity,
+        top_participants,
+    }
+}
+
+/// A channel and the ts of its newest message (`conversations list --sort-channels-by-activity`)
+#[derive(Debug, Serialize)]
+pub struct ChannelActivity {
+    #[serde(flatten)]
+    pub channel: Channel,
+    pub last_activity: Option<String>,
+}
+
+/// Most recently active first; channels with no messages (or no access) last, by name
+pub fn sort_by_activity(channels: &mut [ChannelActivity]) {
+    let ts = |c: &ChannelActivity| c.last_activity.as_deref().and_then(parse_ts);
+    channels.sort_by(|a, b| match (ts(a), ts(b)) {
+        (Some(a), Some(b)) => b.cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.channel.name.cmp(&b.channel.name),
+    });
+}
+
+fn local_time(ts: &str) -> String {
+    let ts_float: f64 = ts.parse().unwrap_or(0.0);
+    let local: DateTime<Local> = DateTime::from_timestamp(ts_float as i64, 0).unwrap_or_default().into();
+    local.format("%Y-%m-%d %H:%M").to_string()
+}
+
 pub fn format_channels_list(channels: &[Channel], writer: &mut ColorWriter) -> Result<()> {
     writer.print_header(&format!("Channels ({})", channels.len()))?;
     writer.print_separator()?;
 
+    if channels.is_empty() {
+        return writer.print_empty("channels");
+    }
+
     // Sort channels by name for easier reading
     let mut sorted_channels = channels.to_vec();
     sorted_channels.sort_by(|a, b| a.name.cmp(&b.name));
 
     for (i, channel) in sorted_channels.iter().enumerate() {
-        // Channel name with # prefix
-        writer.print_colored(&format!("#{}", channel.name), Color::Cyan)?;
-        writer.write(" ")?;
+        write_channel(channel, None, writer)?;
 
-        // Channel ID in yellow
-        writer.print_colored(&format!("({})", channel.id), Color::Yellow)?;
+        // Add spacing between channels
+        if i < sorted_channels.len() - 1 {
+            writer.writeln()?;
+        }
+    }
 
-        // Privacy indicator
-        if channel.is_private == Some(true) {
-            writer.write(" ")?;
-            writer.print_colored("🔒 Private", Color::Blue)?;
+    Ok(())
+}
+
+/// Channels in the given order, each with its last-activity time
+pub fn format_channels_by_activity(channels: &[ChannelActivity], writer: &mut ColorWriter) -> Result<()> {
+    writer.print_header(&format!("Channels by last activity ({})", channels.len()))?;
+    writer.print_separator()?;
+
+    if channels.is_empty() {
+        return writer.print_empty("channels");
+    }
+
+    for (i, entry) in channels.iter().enumerate() {
+        write_channel(&entry.channel, None, writer)?;
+        writer.write("  ")?;
+        writer.print_colored("Last activity: ", Color::Blue)?;
+        match &entry.last_activity {
+            Some(ts) => writer.print_colored(&local_time(ts), Color::Yellow)?,
+            None => writer.write("unknown")?,
         }
+        writer.writeln()?;
 
-        // Archived indicator
-        if channel.is_archived == Some(true) {
-            writer.write(" ")?;
-            writer.print_colored("📦 Archived", Color::White)?;
+        if i < channels.len() - 1 {
+            writer.writeln()?;
         }
+    }
 
-        writer.writeln()?;
+    Ok(())
+}
 
-        // Topic on second line if present
-        if let Some(topic) = &channel.topic {
-            if !topic.value.is_empty() {
-                writer.write("  ")?;
-                writer.print_colored("Topic: ", Color::Blue)?;
-                writer.write(&topic.value)?;
-                writer.writeln()?;
-            }
+/// One channel for `conversations info`: the list block plus the purpose, with
+/// who set the topic and purpose and when. `users` resolves the setters' names.
+pub fn format_channel_info(channel: &Channel, users: &HashMap<String, User>, writer: &mut ColorWriter) -> Result<()> {
+    writer.print_header("Channels (1)")?;
+    writer.print_separator()?;
+    write_channel(channel, Some(users), writer)
+}
+
+/// " (set by @name on 2024-06-01)", or just the parts Slack reported
+fn set_by_label(set_by: (Option<&str>, Option<i64>), users: &HashMap<String, User>) -> String {
+    let mut label = String::new();
+    if let Some(creator) = set_by.0 {
+        match users.get(creator) {
+            Some(user) => label.push_str(&format!(" by @{}", user.name)),
+            None => label.push_str(&format!(" by {}", creator)),
         }
+    }
+    if let Some(last_set) = set_by.1 {
+        let local: DateTime<Local> = DateTime::from_timestamp(last_set, 0).unwrap_or_default().into();
+        label.push_str(&format!(" on {}", local.format("%Y-%m-%d")));
+    }
+    if label.is_empty() {
+        label
+    } else {
+        format!(" (set{})", label)
+    }
+}
 
-        // Member count if available
-        if let Some(num_members) = channel.num_members {
+/// `users` is given for the info view, which adds the purpose and who set what
+fn write_channel(channel: &Channel, users: Option<&HashMap<String, User>>, writer: &mut ColorWriter) -> Result<()> {
+    // Channel name with # prefix, then the ID in yellow (--id-format)
+    if writer.options().id_format.shows_name() {
+        writer.print_colored(&format!("#{}", channel.name), Color::Cyan)?;
+    }
+    writer.print_id(&channel.id)?;
+
+    // Privacy indicator
+    if channel.is_private == Some(true) {
+        writer.write(" ")?;
+        let label = writer.glyphs().labelled(writer.glyphs().private, "Private");
+        writer.print_colored(&label, Color::Blue)?;
+    }
+
+    // Archived indicator
+    if channel.is_archived == Some(true) {
+        writer.write(" ")?;
+        let label = writer.glyphs().labelled(writer.glyphs().archived, "Archived");
+        writer.print_colored(&label, Color::White)?;
+    }
+
+    writer.writeln()?;
+
+    // Topic on second line if present
+    if let Some(topic) = &channel.topic {
+        if !topic.value.is_empty() {
             writer.write("  ")?;
-            writer.print_colored(&format!("{} members", num_members), Color::Green)?;
+            writer.print_colored("Topic: ", Color::Blue)?;
+            writer.write(&topic.value)?;
+            if let Some(users) = users {
+                writer.print_colored(&set_by_label(topic.set_by(), users), Color::White)?;
+            }
             writer.writeln()?;
         }
+    }
 
-        // Add spacing between channels
-        if i < sorted_channels.len() - 1 {
+    if let Some(users) = users {
+        if let Some(purpose) = channel.purpose.as_ref().filter(|p| !p.value.is_empty()) {
+            writer.write("  ")?;
+            writer.print_colored("Purpose: ", Color::Blue)?;
+            writer.write(&purpose.value)?;
+            writer.print_colored(&set_by_label(purpose.set_by(), users), Color::White)?;
             writer.writeln()?;
         }
     }
 
+    // Member count if available
+    if let Some(num_members) = channel.num_members {
+        writer.write("  ")?;
+        writer.print_colored(&format!("{} members", num_members), Color::Green)?;
+        writer.writeln()?;
+    }
+
+    Ok(())
+}
+
+pub fn format_history_stats(
+    stats: &HistoryStats,
+    users: &HashMap<String, User>,
+    writer: &mut ColorWriter,
+) -> Result<()> {
+    writer.print_header(&format!(
+        "Activity in the last {} day{}",
+        stats.days,
+        if stats.days == 1 { "" } else { "s" }
+    ))?;
+    writer.print_separator()?;
+
+    writer.print_colored("Messages: ", Color::Blue)?;
+    writer.write(&stats.messages.to_string())?;
+    writer.writeln()?;
+
+    writer.print_colored("Participants: ", Color::Blue)?;
+    writer.write(&stats.participants.to_string())?;
+    writer.writeln()?;
+
+    if !stats.top_participants.is_empty() {
+        let names: Vec<String> = stats
+            .top_participants
+            .iter()
+            .map(|p| match users.get(&p.user) {
+                Some(user) => format!("@{} ({})", user.name, p.messages),
+                None => format!("{} ({})", p.user, p.messages),
+            })
+            .collect();
+        writer.print_colored("Most active: ", Color::Blue)?;
+        writer.print_colored(&names.join(", "), Color::Cyan)?;
+        writer.writeln()?;
+    }
+
+    writer.print_colored("Last activity: ", Color::Blue)?;
+    match &stats.last_activity {
+        Some(ts) => writer.print_colored(&local_time(ts), Color::Yellow)?,
+        None => writer.write("none in this window")?,
+    }
+    writer.writeln()?;
+
+    Ok(())
+}
+
+/// Who posted in a channel's history window, busiest first (`conversations history --participants`)
+pub fn format_participant_roster(
+    roster: &[ParticipantCount],
+    channel_name: &str,
+    writer: &mut ColorWriter,
+) -> Result<()> {
+    writer.print_header(&format!("Participants in #{} ({})", channel_name, roster.len()))?;
+    writer.print_separator()?;
+
+    if roster.is_empty() {
+        return writer.print_empty("participants");
+    }
+
+    let count_width = roster.iter().map(|p| p.messages.to_string().len()).max().unwrap_or(1);
+    for participant in roster {
+        writer.write(&format!("{:>width$} ", participant.messages, width = count_width))?;
+        writer.write(if participant.messages == 1 { "message  " } else { "messages " })?;
+        match &participant.name {
+            Some(name) if writer.options().id_format.shows_name() => {
+                writer.print_colored(&format!("@{}", name), Color::Cyan)?;
+                writer.print_id(&participant.user)?;
+            }
+            _ => writer.print_colored(&participant.user, Color::Yellow)?,
+        }
+        writer.writeln()?;
+    }
+
     Ok(())
 }
 
@@ -74,16 +321,50 @@ mod tests {
             is_mpim: None,
             is_private: Some(is_private),
             is_archived: Some(false),
+            is_member: None,
             topic: Some(ChannelTopic {
                 value: format!("{} discussion", name),
+                creator: None,
+                last_set: None,
             }),
             purpose: Some(ChannelPurpose {
                 value: format!("Purpose for {}", name),
+                creator: None,
+                last_set: None,
             }),
             num_members: Some(42),
+            user: None,
         }
     }
 
+    #[test]
+    fn test_sort_and_format_channels_by_activity() {
+        let entry = |name: &str, ts: Option<&str>| ChannelActivity {
+            channel: create_test_channel(name, false),
+            last_activity: ts.map(str::to_string),
+        };
+        let mut channels = vec![
+            entry("quiet", Some("1700000000.000100")),
+            entry("locked", None),
+            entry("busy", Some("1700090000.000100")),
+            entry("empty", None),
+        ];
+        sort_by_activity(&mut channels);
+        let names: Vec<&str> = channels.iter().map(|c| c.channel.name.as_str()).collect();
+        assert_eq!(names, ["busy", "quiet", "empty", "locked"]);
+
+        let json = serde_json::to_value(&channels[0]).unwrap();
+        assert_eq!(json["name"], "busy");
+        assert_eq!(json["last_activity"], "1700090000.000100");
+
+        let mut writer = ColorWriter::new(true);
+        format_channels_by_activity(&channels, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("Channels by last activity (4)"));
+        assert!(output.find("#busy").unwrap() < output.find("#quiet").unwrap());
+        assert!(output.contains("#locked (CLOCKED)\n  Topic: locked discussion\n  42 members\n  Last activity: unknown"));
+    }
+
     #[test]
     fn test_format_channels_list() {
         let channels = vec![
@@ -103,8 +384,74 @@ mod tests {
 
         let mut writer = ColorWriter::new(true);
         format_channels_list(&channels, &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().ends_with("No channels found\n"));
 
-        // Test passes if no panic
+        let mut writer = ColorWriter::new(true);
+        format_channels_by_activity(&[], &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().ends_with("No channels found\n"));
+    }
+
+    #[test]
+    fn test_format_channel_info_shows_who_set_topic_and_purpose() {
+        let mut channel = create_test_channel("general", false);
+        let topic = channel.topic.as_mut().unwrap();
+        topic.creator = Some("U1".to_string());
+        topic.last_set = Some(1717243200); // 2024-06-01 12:00 UTC
+        // Never-set placeholders from Slack
+        let purpose = channel.purpose.as_mut().unwrap();
+        purpose.creator = Some(String::new());
+        purpose.last_set = Some(0);
+
+        let users: HashMap<String, User> = [serde_json::from_value::<User>(serde_json::json!({
+            "id": "U1", "name": "alice", "deleted": false, "is_bot": false, "profile": {}
+        }))
+        .unwrap()]
+        .into_iter()
+        .map(|u| (u.id.clone(), u))
+        .collect();
+
+        let mut writer = ColorWriter::new(true);
+        format_channel_info(&channel, &users, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("Topic: general discussion (set by @alice on 2024-06-0"));
+        assert!(output.contains("Purpose: Purpose for general\n"));
+
+        // The plain list leaves both out
+        let mut writer = ColorWriter::new(true);
+        format_channels_list(&[channel], &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(!output.contains("set by"));
+        assert!(!output.contains("Purpose:"));
+    }
+
+    #[test]
+    fn test_history_stats() {
+        let messages: Vec<Message> = [
+            ("1700000300.000000", Some("U2")),
+            ("1700000200.000000", Some("U1")),
+            ("1700000900.000000", Some("U1")),
+            ("1700000100.000000", None),
+        ]
+        .iter()
+        .map(|(ts, user)| serde_json::from_value(serde_json::json!({"ts": ts, "user": user, "text": "hi"})).unwrap())
+        .collect();
+
+        let stats = history_stats(&messages, 7);
+        assert_eq!(stats.messages, 4);
+        assert_eq!(stats.participants, 2);
+        assert_eq!(stats.last_activity.as_deref(), Some("1700000900.000000"));
+        assert_eq!(stats.top_participants[0].user, "U1");
+        assert_eq!(stats.top_participants[0].messages, 2);
+
+        let mut writer = ColorWriter::new(true);
+        format_history_stats(&stats, &HashMap::new(), &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("Activity in the last 7 days"));
+        assert!(output.contains("Most active: U1 (2), U2 (1)"));
+
+        let empty = history_stats(&[], 30);
+        assert_eq!(empty.participants, 0);
+        assert!(empty.last_activity.is_none());
     }
 
     #[test]
@@ -116,4 +463,25 @@ mod tests {
 
         // Should show private indicator
     }
+
+    #[test]
+    fn test_participant_roster() {
+        let messages: Vec<Message> = [Some("U2"), Some("U1"), Some("U1"), None, Some("U3")]
+            .iter()
+            .map(|user| serde_json::from_value(serde_json::json!({"ts": "1.0", "user": user, "text": "hi"})).unwrap())
+            .collect();
+
+        let mut roster = participant_counts(&messages);
+        let order: Vec<(&str, usize)> = roster.iter().map(|p| (p.user.as_str(), p.messages)).collect();
+        assert_eq!(order, vec![("U1", 2), ("U2", 1), ("U3", 1)]);
+        roster[0].name = Some("alice".to_string());
+
+        let mut writer = ColorWriter::new(true);
+        format_participant_roster(&roster, "general", &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.starts_with("Participants in #general (3)\n"));
+        assert!(output.contains("2 messages @alice (U1)\n"));
+        assert!(output.contains("1 message  U2\n"));
+        assert!(!output.contains("hi"));
+    }
 }
@@ -1,27 +1,142 @@
+use crate::output::glyphs::Glyphs;
+use crate::output::render::RenderOptions;
 use std::io::{self, Write};
 use termcolor::{Buffer, Color, ColorSpec, WriteColor};
 
+/// When to emit ANSI colors (`--color`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and NO_COLOR is unset
+    #[default]
+    Auto,
+    /// Always emit color codes, even when piped
+    Always,
+    /// Never emit color codes
+    Never,
+}
+
+impl ColorChoice {
+    /// Resolve to whether colors should be written for this run
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => std::env::var("NO_COLOR").is_err() && atty::is(atty::Stream::Stdout),
+        }
+    }
+}
+
+/// Whether Slack IDs are shown next to names in human output (`--id-format`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum IdFormat {
+    /// Name followed by the ID, e.g. `#general (C123)`
+    #[default]
+    Show,
+    /// Just the name
+    Hide,
+    /// Just the ID, for scripting without json
+    Only,
+}
+
+impl IdFormat {
+    pub fn shows_name(self) -> bool {
+        self != IdFormat::Only
+    }
+
+    /// `name (ID)`, `name` or `ID`
+    pub fn label(self, name: &str, id: &str) -> String {
+        match self {
+            IdFormat::Show => format!("{} ({})", name, id),
+            IdFormat::Hide => name.to_string(),
+            IdFormat::Only => id.to_string(),
+        }
+    }
+}
+
 pub struct ColorWriter {
     buffer: Buffer,
     no_color: bool,
+    options: RenderOptions,
 }
 
 impl ColorWriter {
+    /// Create a writer; callers resolve `--color`/NO_COLOR via `ColorChoice::enabled`
     pub fn new(no_color: bool) -> Self {
-        let colors_enabled = !no_color && std::env::var("NO_COLOR").is_err();
-
         Self {
             buffer: Buffer::ansi(), // Use ANSI buffer for color codes
-            no_color: !colors_enabled,
+            no_color,
+            options: RenderOptions::default(),
         }
     }
 
+    /// Render with the given display settings (`Cli::render_options`)
+    pub fn with_options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Display settings every formatter reads from
+    pub fn options(&self) -> &RenderOptions {
+        &self.options
+    }
+
+    /// Glyph table for decorative symbols; formatters take every symbol from here
+    pub fn glyphs(&self) -> &'static Glyphs {
+        self.options.glyphs()
+    }
+
+    /// Message text with skin-toned emoji normalized if requested and custom
+    /// shortcodes replaced from `--emoji-map-file` (no truncation)
+    pub fn emoji_text<'a>(&self, text: &'a str) -> std::borrow::Cow<'a, str> {
+        let text = if self.options.normalize_skin_tones {
+            crate::output::emoji::normalize_skin_tones(text)
+        } else {
+            std::borrow::Cow::Borrowed(text)
+        };
+        match crate::output::emoji::map_shortcodes(&text, &self.options.emoji_map) {
+            std::borrow::Cow::Borrowed(_) => text,
+            std::borrow::Cow::Owned(mapped) => std::borrow::Cow::Owned(mapped),
+        }
+    }
+
+    /// A message body as it should be displayed: emoji normalized, whitespace
+    /// tidied with `--trim`, then truncated to `--max-text-length`
+    pub fn message_text(&self, text: &str) -> String {
+        let text = self.emoji_text(text);
+        let text = if self.options.trim {
+            std::borrow::Cow::Owned(crate::output::text::trim_whitespace(&text))
+        } else {
+            text
+        };
+        crate::output::text::truncate(&text, self.options.max_text_length).into_owned()
+    }
+
+    /// A reaction emoji as it should be displayed: `:name:` (skin tone stripped
+    /// if requested), or its `--emoji-map-file` display string
+    pub fn emoji_label(&self, name: &str) -> String {
+        let name = if self.options.normalize_skin_tones {
+            crate::output::emoji::strip_skin_tone(name)
+        } else {
+            name
+        };
+        crate::output::emoji::shortcode_label(name, &self.options.emoji_map)
+    }
+
     /// Get the buffer contents as a string
     pub fn into_string(self) -> Result<String, std::io::Error> {
         String::from_utf8(self.buffer.into_inner())
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
     }
 
+    /// Get the buffer contents, ending with a color reset when colors are on.
+    /// For output written out in pieces (streaming), so the terminal is never left colored.
+    pub fn finish(mut self) -> Result<String, std::io::Error> {
+        if !self.no_color {
+            self.buffer.reset()?;
+        }
+        self.into_string()
+    }
+
     /// Print text in a specific color
     pub fn print_colored(&mut self, text: &str, color: Color) -> io::Result<()> {
         if !self.no_color {
@@ -72,16 +187,34 @@ impl ColorWriter {
         Ok(())
     }
 
+    /// The line every list view prints when it has nothing to show: "No users found"
+    pub fn print_empty(&mut self, things: &str) -> io::Result<()> {
+        writeln!(self.buffer, "No {} found", things)
+    }
+
+    /// The ID part after a name the caller printed only if `options().id_format.shows_name()`:
+    /// " (U123)" normally, "U123" on its own with `--id-format only`, nothing with `hide`
+    pub fn print_id(&mut self, id: &str) -> io::Result<()> {
+        match self.options.id_format {
+            IdFormat::Show => {
+                write!(self.buffer, " ")?;
+                self.print_colored(&format!("({})", id), Color::Yellow)
+            }
+            IdFormat::Hide => Ok(()),
+            IdFormat::Only => self.print_colored(id, Color::Yellow),
+        }
+    }
+
     /// Print error message
     pub fn print_error(&mut self, text: &str) -> io::Result<()> {
-        self.print_colored("✗ ", Color::Red)?;
+        self.print_colored(&format!("{} ", self.glyphs().error), Color::Red)?;
         writeln!(self.buffer, "{}", text)?;
         Ok(())
     }
 
     /// Print separator line
     pub fn print_separator(&mut self) -> io::Result<()> {
-        self.print_colored(&"─".repeat(80), Color::White)?;
+        self.print_colored(&self.glyphs().rule.repeat(80), Color::White)?;
         writeln!(self.buffer)?;
         Ok(())
     }
@@ -96,3 +229,81 @@ impl ColorWriter {
         writeln!(self.buffer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_text_normalizes_skin_tones() {
+        let text = "nice :+1::skin-tone-4: work";
+        assert_eq!(ColorWriter::new(true).message_text(text), text);
+
+        let writer = ColorWriter::new(true).with_options(RenderOptions { normalize_skin_tones: true, ..Default::default() });
+        assert_eq!(writer.message_text(text), "nice :+1: work");
+        assert_eq!(writer.emoji_label("wave::skin-tone-2"), ":wave:");
+    }
+
+    #[test]
+    fn test_emoji_map_applies_to_text_and_reactions() {
+        let emoji_map = std::sync::Arc::new(std::collections::HashMap::from([(
+            "shipit".to_string(),
+            "[shipit]".to_string(),
+        )]));
+        let writer = ColorWriter::new(true).with_options(RenderOptions { emoji_map, ..Default::default() });
+        assert_eq!(writer.message_text("lgtm :shipit: :tada:"), "lgtm [shipit] :tada:");
+        assert_eq!(writer.emoji_label("shipit"), "[shipit]");
+        assert_eq!(writer.emoji_label("tada"), ":tada:");
+    }
+
+    #[test]
+    fn test_message_text_trims_before_truncating() {
+        let text = "\n   hello\n\n\n   world   \n";
+        assert_eq!(ColorWriter::new(true).message_text(text), text);
+
+        let writer = ColorWriter::new(true).with_options(RenderOptions {
+            trim: true,
+            max_text_length: Some(10),
+            ..Default::default()
+        });
+        assert_eq!(writer.message_text(text), "hello\n\n...");
+    }
+
+    #[test]
+    fn test_color_choice_always_and_never() {
+        assert!(ColorChoice::Always.enabled());
+        assert!(!ColorChoice::Never.enabled());
+    }
+
+    #[test]
+    fn test_color_writer_respects_no_color() {
+        let mut writer = ColorWriter::new(false);
+        writer.print_colored("hi", Color::Red).unwrap();
+        assert!(writer.into_string().unwrap().contains("\x1b["));
+
+        let mut writer = ColorWriter::new(true);
+        writer.print_colored("hi", Color::Red).unwrap();
+        assert_eq!(writer.into_string().unwrap(), "hi");
+    }
+
+    #[test]
+    fn test_ascii_separator() {
+        let mut writer = ColorWriter::new(true).with_options(RenderOptions { ascii: true, ..Default::default() });
+        writer.print_separator().unwrap();
+        writer.print_error("nope").unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.is_ascii());
+        assert!(output.starts_with(&"-".repeat(80)));
+    }
+
+    #[test]
+    fn test_finish_ends_with_reset() {
+        let mut writer = ColorWriter::new(false);
+        writer.print_colored("hi", Color::Red).unwrap();
+        assert!(writer.finish().unwrap().ends_with("\x1b[0m"));
+
+        let mut writer = ColorWriter::new(true);
+        writer.write("hi").unwrap();
+        assert_eq!(writer.finish().unwrap(), "hi");
+    }
+}
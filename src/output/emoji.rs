@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+
+const SKIN_TONE_MARKER: &str = "::skin-tone-";
+
+/// Base name of a reaction emoji, e.g. `wave::skin-tone-3` -> `wave`
+pub fn strip_skin_tone(name: &str) -> &str {
+    match name.find(SKIN_TONE_MARKER) {
+        Some(idx) => &name[..idx],
+        None => name,
+    }
+}
+
+/// Rewrite skin-toned shortcodes in message text, e.g. `:wave::skin-tone-3:` -> `:wave:`
+pub fn normalize_skin_tones(text: &str) -> Cow<'_, str> {
+    if !text.contains(SKIN_TONE_MARKER) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(idx) = rest.find(SKIN_TONE_MARKER) {
+        out.push_str(&rest[..idx]);
+        // Drop "::skin-tone-N" and keep the closing colon
+        let after = &rest[idx + SKIN_TONE_MARKER.len()..];
+        let digits = after.chars().take_while(|c| c.is_ascii_digit()).count();
+        rest = &after[digits..];
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// Load an `--emoji-map-file`: a JSON object of shortcode -> display string,
+/// e.g. `{"shipit": "[shipit]"}`. Keys may be written with or without colons.
+pub fn load_emoji_map(path: &Path) -> Result<HashMap<String, String>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read emoji map file {}", path.display()))?;
+    let map: HashMap<String, String> = serde_json::from_str(&contents)
+        .with_context(|| format!("Emoji map file {} must be a JSON object of shortcode to string", path.display()))?;
+    Ok(map.into_iter().map(|(name, display)| (name.trim_matches(':').to_string(), display)).collect())
+}
+
+fn is_shortcode_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+' | '\'')
+}
+
+/// Display form of a reaction emoji: its `emoji_map` entry, else `:name:`.
+/// A skin-toned variant falls back to its base emoji's entry.
+pub fn shortcode_label(name: &str, emoji_map: &HashMap<String, String>) -> String {
+    emoji_map
+        .get(name)
+        .or_else(|| emoji_map.get(strip_skin_tone(name)))
+        .cloned()
+        .unwrap_or_else(|| format!(":{}:", name))
+}
+
+/// Replace mapped `:shortcode:`s in message text with their display string;
+/// unmapped ones are left as written
+pub fn map_shortcodes<'a>(text: &'a str, emoji_map: &HashMap<String, String>) -> Cow<'a, str> {
+    if emoji_map.is_empty() || !text.contains(':') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut changed = false;
+    let mut rest = text;
+    while let Some(start) = rest.find(':') {
+        let after = &rest[start + 1..];
+        let len = after.find(|c| !is_shortcode_char(c)).unwrap_or(after.len());
+        if len == 0 || !after[len..].starts_with(':') {
+            out.push_str(&rest[..=start]);
+            rest = after;
+            continue;
+        }
+
+        let name = &after[..len];
+        let mut tail = &after[len + 1..];
+        match emoji_map.get(name) {
+            Some(display) => {
+                out.push_str(&rest[..start]);
+                out.push_str(display);
+                // A skin tone has nothing left to apply to once the emoji is replaced
+                if let Some(tone) = tail.strip_prefix(&SKIN_TONE_MARKER[1..]) {
+                    let digits = tone.chars().take_while(|c| c.is_ascii_digit()).count();
+                    if digits > 0 && tone[digits..].starts_with(':') {
+                        tail = &tone[digits + 1..];
+                    }
+                }
+                changed = true;
+            }
+            None => out.push_str(&rest[..start + len + 2]),
+        }
+        rest = tail;
+    }
+
+    if !changed {
+        return Cow::Borrowed(text);
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_skin_tone() {
+        assert_eq!(strip_skin_tone("wave::skin-tone-3"), "wave");
+        assert_eq!(strip_skin_tone("+1::skin-tone-6"), "+1");
+        assert_eq!(strip_skin_tone("tada"), "tada");
+    }
+
+    #[test]
+    fn test_normalize_skin_tones_in_text() {
+        assert_eq!(
+            normalize_skin_tones("hi :wave::skin-tone-3: and :+1::skin-tone-2::tada:"),
+            "hi :wave: and :+1::tada:"
+        );
+        assert!(matches!(normalize_skin_tones("no tones :wave:"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_map_shortcodes() {
+        let map = HashMap::from([
+            ("shipit".to_string(), "[shipit]".to_string()),
+            ("wave".to_string(), "o/".to_string()),
+        ]);
+        assert_eq!(map_shortcodes(":shipit: it :tada:", &map), "[shipit] it :tada:");
+        assert_eq!(map_shortcodes("hi :wave::skin-tone-3: there", &map), "hi o/ there");
+        assert_eq!(map_shortcodes(":tada::shipit:", &map), ":tada:[shipit]");
+        assert_eq!(map_shortcodes("at 10:30, see https://x.io", &map), "at 10:30, see https://x.io");
+        assert!(matches!(map_shortcodes(":tada:", &map), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_shortcode_label() {
+        let map = HashMap::from([("shipit".to_string(), "[shipit]".to_string())]);
+        assert_eq!(shortcode_label("shipit", &map), "[shipit]");
+        assert_eq!(shortcode_label("shipit::skin-tone-2", &map), "[shipit]");
+        assert_eq!(shortcode_label("tada", &map), ":tada:");
+    }
+
+    #[test]
+    fn test_load_emoji_map_trims_colons() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("emoji.json");
+        std::fs::write(&path, r#"{":shipit:": "[shipit]", "party_parrot": "<parrot>"}"#).unwrap();
+        let map = load_emoji_map(&path).unwrap();
+        assert_eq!(map["shipit"], "[shipit]");
+        assert_eq!(map["party_parrot"], "<parrot>");
+
+        std::fs::write(&path, "[1, 2]").unwrap();
+        assert!(load_emoji_map(&path).is_err());
+    }
+}
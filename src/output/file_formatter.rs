@@ -1,14 +1,179 @@
 use crate::models::file::File;
 use crate::models::user::User;
 use crate::output::color::ColorWriter;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::io::Result;
 use termcolor::Color;
 
-pub fn format_files_list(files: &[File], users: &HashMap<String, User>, writer: &mut ColorWriter) -> Result<()> {
+/// How `files list --group-by` buckets files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum FileGroupBy {
+    /// By uploader
+    User,
+    /// By every conversation the file is shared in
+    Channel,
+}
+
+/// Label for files that aren't shared in any conversation
+pub const NOT_SHARED: &str = "(not shared)";
+
+/// File count and cumulative size for one uploader or conversation
+#[derive(Debug, Serialize)]
+pub struct FileGroup {
+    /// User or conversation ID; `None` for unshared files
+    pub id: Option<String>,
+    pub name: String,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Group files and total them, largest first. `labels` maps user or conversation IDs to
+/// display names; unknown IDs are shown as-is. With [`FileGroupBy::Channel`] a file shared
+/// in several conversations counts towards each of them.
+pub fn group_files(files: &[File], by: FileGroupBy, labels: &HashMap<String, String>) -> Vec<FileGroup> {
+    let mut groups: HashMap<Option<&str>, (usize, u64)> = HashMap::new();
+
+    for file in files {
+        let keys: Vec<Option<&str>> = match by {
+            FileGroupBy::User => vec![Some(file.user.as_str())],
+            FileGroupBy::Channel => {
+                let ids: Vec<Option<&str>> = file.shared_conversations().map(|(id, _)| Some(id.as_str())).collect();
+                if ids.is_empty() {
+                    vec![None]
+                } else {
+                    ids
+                }
+            }
+        };
+        for key in keys {
+            let entry = groups.entry(key).or_default();
+            entry.0 += 1;
+            entry.1 += file.size;
+        }
+    }
+
+    let mut groups: Vec<FileGroup> = groups
+        .into_iter()
+        .map(|(id, (files, bytes))| FileGroup {
+            name: match id {
+                Some(id) => labels.get(id).cloned().unwrap_or_else(|| id.to_string()),
+                None => NOT_SHARED.to_string(),
+            },
+            id: id.map(str::to_string),
+            files,
+            bytes,
+        })
+        .collect();
+    // Largest first, then name so ties are stable
+    groups.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+    groups
+}
+
+pub fn format_file_groups(groups: &[FileGroup], by: FileGroupBy, writer: &mut ColorWriter) -> Result<()> {
+    let heading = match by {
+        FileGroupBy::User => "Files by uploader",
+        FileGroupBy::Channel => "Files by conversation",
+    };
+    writer.print_header(&format!("{} ({} groups)", heading, groups.len()))?;
+    writer.print_separator()?;
+
+    if groups.is_empty() {
+        return writer.print_empty("files");
+    }
+
+    let name_width = groups.iter().map(|g| g.name.chars().count()).max().unwrap_or(0);
+    for group in groups {
+        writer.print_colored(&format!("{:<width$}", group.name, width = name_width), Color::Cyan)?;
+        writer.write(&format!(
+            "  {:>5} file{}  {:>10}",
+            group.files,
+            if group.files == 1 { " " } else { "s" },
+            format_size(group.bytes)
+        ))?;
+        writer.writeln()?;
+    }
+
+    if by == FileGroupBy::Channel {
+        writer.writeln()?;
+        writer.write("Files shared in several conversations are counted in each")?;
+        writer.writeln()?;
+    }
+
+    Ok(())
+}
+
+/// Files `files list --unused` reports as safe to clean up, and their total size
+#[derive(Debug, Serialize)]
+pub struct UnusedFiles<'a> {
+    pub files: Vec<&'a File>,
+    pub reclaimable_bytes: u64,
+}
+
+/// Files that aren't shared in any channel, private channel or DM. With `older_than`
+/// (days), only those uploaded at least that long before `now` (Unix seconds). Largest first.
+pub fn unused_files(files: &[File], older_than: Option<u64>, now: i64) -> UnusedFiles<'_> {
+    let cutoff = older_than.map(|days| now - (days * 86_400) as i64);
+    let mut unused: Vec<&File> = files
+        .iter()
+        .filter(|file| file.shared_conversations().next().is_none())
+        .filter(|file| cutoff.is_none_or(|cutoff| (file.created as i64) <= cutoff))
+        .collect();
+    unused.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+    UnusedFiles {
+        reclaimable_bytes: unused.iter().map(|f| f.size).sum(),
+        files: unused,
+    }
+}
+
+/// The `files list --unused` report: one line per file, with its age relative to `now`
+pub fn format_unused_files(unused: &UnusedFiles, now: i64, writer: &mut ColorWriter) -> Result<()> {
+    writer.print_header(&format!(
+        "Unused files ({}, {} reclaimable)",
+        unused.files.len(),
+        format_size(unused.reclaimable_bytes)
+    ))?;
+    writer.print_separator()?;
+
+    if unused.files.is_empty() {
+        return writer.print_empty("unused files");
+    }
+
+    for file in &unused.files {
+        if writer.options().id_format.shows_name() {
+            writer.print_colored(&file.name, Color::Cyan)?;
+        }
+        writer.print_id(&file.id)?;
+        let days = (now - file.created as i64).max(0) / 86_400;
+        writer.write(&format!(
+            "  {}, {} day{} old",
+            format_size(file.size),
+            days,
+            if days == 1 { "" } else { "s" }
+        ))?;
+        writer.writeln()?;
+    }
+
+    writer.writeln()?;
+    writer.write("Not shared in any channel or DM; a file can still be linked from a message")?;
+    writer.writeln()?;
+    Ok(())
+}
+
+/// Format files; `shared_in` maps file IDs to resolved `#channel`/`@user` labels
+pub fn format_files_list(
+    files: &[File],
+    users: &HashMap<String, User>,
+    shared_in: &HashMap<String, Vec<String>>,
+    writer: &mut ColorWriter,
+) -> Result<()> {
     writer.print_header(&format!("Files ({})", files.len()))?;
     writer.print_separator()?;
 
+    if files.is_empty() {
+        return writer.print_empty("files");
+    }
+
     for (i, file) in files.iter().enumerate() {
         // File name and type
         writer.print_colored(&file.name, Color::Cyan)?;
@@ -38,10 +203,22 @@ pub fn format_files_list(files: &[File], users: &HashMap<String, User>, writer:
         }
         writer.write(" on ")?;
         let datetime = chrono::DateTime::from_timestamp(file.created as i64, 0)
-            .unwrap_or_else(|| chrono::Utc::now());
+            .unwrap_or_else(chrono::Utc::now);
         writer.write(&datetime.format("%Y-%m-%d %H:%M:%S").to_string())?;
         writer.writeln()?;
 
+        // Where the file is shared, when resolved
+        if let Some(labels) = shared_in.get(&file.id) {
+            writer.write("  ")?;
+            writer.print_colored("Shared in: ", Color::Blue)?;
+            if labels.is_empty() {
+                writer.write("(not shared)")?;
+            } else {
+                writer.write(&labels.join(", "))?;
+            }
+            writer.writeln()?;
+        }
+
         // Permalink
         if let Some(ref permalink) = file.permalink {
             writer.write("  ")?;
@@ -59,8 +236,48 @@ pub fn format_files_list(files: &[File], users: &HashMap<String, User>, writer:
     Ok(())
 }
 
-pub fn format_file(file: &File, users: &HashMap<String, User>, writer: &mut ColorWriter) -> Result<()> {
-    format_files_list(&vec![file.clone()], users, writer)
+pub fn format_file(
+    file: &File,
+    users: &HashMap<String, User>,
+    shared_in: Option<Vec<String>>,
+    writer: &mut ColorWriter,
+) -> Result<()> {
+    let shared_in: HashMap<String, Vec<String>> = shared_in.map(|labels| (file.id.clone(), labels)).into_iter().collect();
+    format_files_list(std::slice::from_ref(file), users, &shared_in, writer)
+}
+
+/// Revision list for `files info --versions`, printed after the file details
+pub fn format_file_versions(file: &File, users: &HashMap<String, User>, writer: &mut ColorWriter) -> Result<()> {
+    let revisions = file.revisions();
+    writer.write("  ")?;
+    writer.print_colored("Versions: ", Color::Blue)?;
+    if revisions.len() < 2 {
+        writer.write("single version")?;
+        writer.writeln()?;
+        return Ok(());
+    }
+    writer.write(&revisions.len().to_string())?;
+    writer.writeln()?;
+
+    for (i, version) in revisions.iter().enumerate() {
+        let datetime = chrono::DateTime::from_timestamp(version.timestamp as i64, 0).unwrap_or_default();
+        writer.write(&format!("    {}. {}", i + 1, datetime.format("%Y-%m-%d %H:%M:%S")))?;
+        if let Some(id) = &version.user {
+            match users.get(id) {
+                Some(user) => writer.write(&format!(" by @{} ({})", user.name, id))?,
+                None => writer.write(&format!(" by {}", id))?,
+            }
+        }
+        if let Some(size) = version.size {
+            writer.write(&format!(", {}", format_size(size)))?;
+        }
+        if i == revisions.len() - 1 {
+            writer.print_colored(" (current)", Color::Green)?;
+        }
+        writer.writeln()?;
+    }
+
+    Ok(())
 }
 
 fn format_size(bytes: u64) -> String {
@@ -82,7 +299,7 @@ fn format_size(bytes: u64) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::file::File;
+    use crate::models::file::{File, FileVersion};
 
     fn create_test_file() -> File {
         File {
@@ -105,6 +322,9 @@ mod tests {
             channels: None,
             groups: None,
             ims: None,
+            updated: None,
+            last_editor: None,
+            versions: None,
         }
     }
 
@@ -113,7 +333,95 @@ mod tests {
         let files = vec![create_test_file()];
         let users = HashMap::new();
         let mut writer = ColorWriter::new(true);
-        format_files_list(&files, &users, &mut writer).unwrap();
+        format_files_list(&files, &users, &HashMap::new(), &mut writer).unwrap();
+    }
+
+    #[test]
+    fn test_format_empty_files_list() {
+        let mut writer = ColorWriter::new(true);
+        format_files_list(&[], &HashMap::new(), &HashMap::new(), &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.starts_with("Files (0)\n"));
+        assert!(output.ends_with("No files found\n"));
+    }
+
+    #[test]
+    fn test_format_file_shared_in() {
+        let file = create_test_file();
+        let users = HashMap::new();
+
+        let mut writer = ColorWriter::new(true);
+        format_file(&file, &users, Some(vec!["#general".to_string(), "@alice".to_string()]), &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().contains("Shared in: #general, @alice"));
+
+        // Not requested: no line at all
+        let mut writer = ColorWriter::new(true);
+        format_file(&file, &users, None, &mut writer).unwrap();
+        assert!(!writer.into_string().unwrap().contains("Shared in"));
+    }
+
+    #[test]
+    fn test_format_file_versions() {
+        let mut file = create_test_file();
+        let users = HashMap::new();
+
+        let mut writer = ColorWriter::new(true);
+        format_file_versions(&file, &users, &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().contains("Versions: single version"));
+        // Revision fields stay out of json/yaml when Slack doesn't send them
+        assert!(!serde_json::to_string(&file).unwrap().contains("versions"));
+
+        // Edited in Slack without a revision list: upload plus last edit
+        file.updated = Some(1234570000);
+        file.last_editor = Some("U456".to_string());
+        let mut writer = ColorWriter::new(true);
+        format_file_versions(&file, &users, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("Versions: 2"));
+        assert!(output.contains("1. 2009-02-13 23:31:30 by U123\n"));
+        assert!(output.contains("2. 2009-02-14 00:06:40 by U456, 1.00 KB (current)"));
+
+        file.versions = Some(vec![
+            FileVersion { timestamp: 1234580000, user: None, size: Some(2048) },
+            FileVersion { timestamp: 1234567890, user: Some("U123".to_string()), size: Some(512) },
+        ]);
+        assert_eq!(file.revisions()[0].timestamp, 1234567890);
+        let mut writer = ColorWriter::new(true);
+        format_file_versions(&file, &users, &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().contains("2. 2009-02-14 02:53:20, 2.00 KB (current)"));
+    }
+
+    #[test]
+    fn test_group_files_by_user_and_channel() {
+        let mut big = create_test_file();
+        big.id = "F2".to_string();
+        big.user = "U456".to_string();
+        big.size = 4096;
+        big.channels = Some(vec!["C1".to_string()]);
+        big.ims = Some(vec!["D1".to_string()]);
+        let mut shared = create_test_file();
+        shared.channels = Some(vec!["C1".to_string()]);
+        let files = vec![create_test_file(), big, shared];
+        let labels: HashMap<String, String> = [("U456", "@bob"), ("C1", "#general")]
+            .into_iter()
+            .map(|(id, label)| (id.to_string(), label.to_string()))
+            .collect();
+
+        let by_user = group_files(&files, FileGroupBy::User, &labels);
+        let by_user: Vec<(&str, usize, u64)> = by_user.iter().map(|g| (g.name.as_str(), g.files, g.bytes)).collect();
+        assert_eq!(by_user, vec![("@bob", 1, 4096), ("U123", 2, 2048)]);
+
+        let by_channel = group_files(&files, FileGroupBy::Channel, &labels);
+        let rows: Vec<(&str, usize, u64)> = by_channel.iter().map(|g| (g.name.as_str(), g.files, g.bytes)).collect();
+        assert_eq!(rows, vec![("#general", 2, 5120), ("D1", 1, 4096), (NOT_SHARED, 1, 1024)]);
+        assert_eq!(by_channel[2].id, None);
+
+        let mut writer = ColorWriter::new(true);
+        format_file_groups(&by_channel, FileGroupBy::Channel, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("Files by conversation (3 groups)"));
+        assert!(output.contains("5.00 KB"));
+        assert!(output.contains("counted in each"));
     }
 
     #[test]
@@ -123,4 +431,33 @@ mod tests {
         assert_eq!(format_size(1572864), "1.50 MB");
         assert_eq!(format_size(1610612736), "1.50 GB");
     }
+
+    #[test]
+    fn test_unused_files() {
+        let now = 1234567890 + 100 * 86_400;
+        let mut shared = create_test_file();
+        shared.channels = Some(vec!["C1".to_string()]);
+        let mut recent = create_test_file();
+        recent.id = "F2".to_string();
+        recent.created = now as u64 - 86_400;
+        recent.size = 4096;
+        let files = vec![shared, create_test_file(), recent];
+
+        let unused = unused_files(&files, None, now);
+        let ids: Vec<&str> = unused.files.iter().map(|f| f.id.as_str()).collect();
+        assert_eq!(ids, ["F2", "F123"]);
+        assert_eq!(unused.reclaimable_bytes, 5120);
+
+        let unused = unused_files(&files, Some(30), now);
+        assert_eq!(unused.files.len(), 1);
+        let mut writer = ColorWriter::new(true);
+        format_unused_files(&unused, now, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.starts_with("Unused files (1, 1.00 KB reclaimable)\n"));
+        assert!(output.contains("test.txt (F123)  1.00 KB, 100 days old\n"));
+
+        let mut writer = ColorWriter::new(true);
+        format_unused_files(&unused_files(&[], None, now), now, &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().ends_with("No unused files found\n"));
+    }
 }
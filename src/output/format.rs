@@ -0,0 +1,218 @@
+use crate::models::channel::Channel;
+use crate::models::user::{User, UserProfile};
+use crate::models::workspace::{AuthTestResponse, TokenCheck};
+use crate::output::color::ColorWriter;
+use crate::output::file_formatter::{FileGroup, FileGroupBy, UnusedFiles};
+use crate::output::user_formatter::{MemberDiff, UserChanges};
+use crate::output::{auth_formatter, channel_formatter, file_formatter, search_formatter, user_formatter};
+use anyhow::Result;
+
+/// Renders command results in one `--format`.
+///
+/// `main.rs` picks an implementation with [`select`] and calls the method for
+/// the result it has, instead of matching on the format string itself. Adding
+/// a format means adding one implementation. Results whose human view needs
+/// extra lookups (names, channel info) branch on [`OutputFormat::serialized`]
+/// so the lookups only happen when they will be shown.
+pub trait OutputFormat {
+    /// The serializer for json/yaml, `None` for human output
+    fn serialized(&self) -> Option<&Serialized> {
+        None
+    }
+
+    fn format_users(&self, users: &[User]) -> Result<String>;
+    fn format_user(&self, user: &User) -> Result<String>;
+    fn format_user_changes(&self, changes: &UserChanges) -> Result<String>;
+    fn format_profile(&self, profile: &UserProfile) -> Result<String>;
+    /// The raw `users.profile.get` object (`--all-fields`)
+    fn format_profile_fields(&self, profile: &serde_json::Value) -> Result<String>;
+    fn format_channels(&self, channels: &[Channel]) -> Result<String>;
+    fn format_channel_search(&self, query: &str, channels: &[Channel]) -> Result<String>;
+    /// `a` and `b` label the two channels as the user typed them
+    fn format_member_diff(&self, diff: &MemberDiff<User>, a: &str, b: &str) -> Result<String>;
+    fn format_file_groups(&self, groups: &[FileGroup], by: FileGroupBy) -> Result<String>;
+    /// `now` (Unix seconds) is what file ages are measured against
+    fn format_unused_files(&self, unused: &UnusedFiles, now: i64) -> Result<String>;
+    fn format_auth_test(&self, auth: &AuthTestResponse) -> Result<String>;
+    fn format_token_checks(&self, checks: &[TokenCheck]) -> Result<String>;
+}
+
+/// The implementation for `--format`: json and yaml serialize, anything else is human
+pub fn select(
+    format: &str,
+    pretty: Option<bool>,
+    new_writer: impl Fn() -> ColorWriter + 'static,
+) -> Box<dyn OutputFormat> {
+    match format {
+        "json" => Box::new(Serialized { format: "json", pretty }),
+        "yaml" => Box::new(Serialized { format: "yaml", pretty: None }),
+        _ => Box::new(Human::new(new_writer)),
+    }
+}
+
+/// Colored terminal output through the formatter functions
+pub struct Human {
+    new_writer: Box<dyn Fn() -> ColorWriter>,
+}
+
+impl Human {
+    /// `new_writer` builds a writer with this run's color and text settings
+    pub fn new(new_writer: impl Fn() -> ColorWriter + 'static) -> Self {
+        Self {
+            new_writer: Box::new(new_writer),
+        }
+    }
+
+    fn draw(&self, format: impl FnOnce(&mut ColorWriter) -> std::io::Result<()>) -> Result<String> {
+        let mut writer = (self.new_writer)();
+        format(&mut writer)?;
+        Ok(writer.into_string()?)
+    }
+}
+
+impl OutputFormat for Human {
+    fn format_users(&self, users: &[User]) -> Result<String> {
+        self.draw(|w| user_formatter::format_users_list(users, w))
+    }
+
+    fn format_user(&self, user: &User) -> Result<String> {
+        self.draw(|w| user_formatter::format_user(user, w))
+    }
+
+    fn format_user_changes(&self, changes: &UserChanges) -> Result<String> {
+        self.draw(|w| user_formatter::format_user_changes(changes, w))
+    }
+
+    fn format_profile(&self, profile: &UserProfile) -> Result<String> {
+        self.draw(|w| user_formatter::format_profile(profile, w))
+    }
+
+    fn format_profile_fields(&self, profile: &serde_json::Value) -> Result<String> {
+        self.draw(|w| user_formatter::format_profile_all_fields(profile, w))
+    }
+
+    fn format_channels(&self, channels: &[Channel]) -> Result<String> {
+        self.draw(|w| channel_formatter::format_channels_list(channels, w))
+    }
+
+    fn format_channel_search(&self, query: &str, channels: &[Channel]) -> Result<String> {
+        self.draw(|w| search_formatter::format_channel_search_results(query, channels, w))
+    }
+
+    fn format_member_diff(&self, diff: &MemberDiff<User>, a: &str, b: &str) -> Result<String> {
+        self.draw(|w| user_formatter::format_member_diff(diff, a, b, w))
+    }
+
+    fn format_file_groups(&self, groups: &[FileGroup], by: FileGroupBy) -> Result<String> {
+        self.draw(|w| file_formatter::format_file_groups(groups, by, w))
+    }
+
+    fn format_unused_files(&self, unused: &UnusedFiles, now: i64) -> Result<String> {
+        self.draw(|w| file_formatter::format_unused_files(unused, now, w))
+    }
+
+    fn format_auth_test(&self, auth: &AuthTestResponse) -> Result<String> {
+        self.draw(|w| auth_formatter::format_auth_test(auth, w))
+    }
+
+    fn format_token_checks(&self, checks: &[TokenCheck]) -> Result<String> {
+        self.draw(|w| auth_formatter::format_token_checks(checks, w))
+    }
+}
+
+/// JSON or YAML. JSON is pretty on a terminal unless `--pretty`/`--compact` says otherwise.
+pub struct Serialized {
+    /// `"json"` or `"yaml"`
+    pub format: &'static str,
+    pub pretty: Option<bool>,
+}
+
+impl Serialized {
+    /// Serialize any result, including the ones without a method on [`OutputFormat`]
+    pub fn write<T: serde::Serialize + ?Sized>(&self, value: &T) -> Result<String> {
+        super::serialize(value, self.format, self.pretty)
+    }
+}
+
+impl OutputFormat for Serialized {
+    fn serialized(&self) -> Option<&Serialized> {
+        Some(self)
+    }
+
+    fn format_users(&self, users: &[User]) -> Result<String> {
+        self.write(users)
+    }
+
+    fn format_user(&self, user: &User) -> Result<String> {
+        self.write(user)
+    }
+
+    fn format_user_changes(&self, changes: &UserChanges) -> Result<String> {
+        self.write(changes)
+    }
+
+    fn format_profile(&self, profile: &UserProfile) -> Result<String> {
+        self.write(profile)
+    }
+
+    fn format_profile_fields(&self, profile: &serde_json::Value) -> Result<String> {
+        self.write(profile)
+    }
+
+    fn format_channels(&self, channels: &[Channel]) -> Result<String> {
+        self.write(channels)
+    }
+
+    fn format_channel_search(&self, _query: &str, channels: &[Channel]) -> Result<String> {
+        self.write(channels)
+    }
+
+    fn format_member_diff(&self, diff: &MemberDiff<User>, _a: &str, _b: &str) -> Result<String> {
+        self.write(diff)
+    }
+
+    fn format_file_groups(&self, groups: &[FileGroup], _by: FileGroupBy) -> Result<String> {
+        self.write(groups)
+    }
+
+    fn format_unused_files(&self, unused: &UnusedFiles, _now: i64) -> Result<String> {
+        self.write(unused)
+    }
+
+    fn format_auth_test(&self, auth: &AuthTestResponse) -> Result<String> {
+        self.write(auth)
+    }
+
+    fn format_token_checks(&self, checks: &[TokenCheck]) -> Result<String> {
+        self.write(checks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn users() -> Vec<User> {
+        vec![serde_json::from_value(serde_json::json!({
+            "id": "U1", "name": "alice", "deleted": false, "is_bot": false, "profile": {}
+        }))
+        .unwrap()]
+    }
+
+    #[test]
+    fn test_select_by_format() {
+        let new_writer = || ColorWriter::new(true);
+
+        let json = select("json", Some(false), new_writer).format_users(&users()).unwrap();
+        assert!(json.starts_with(r#"[{"id":"U1","name":"alice""#));
+
+        let yaml = select("yaml", None, new_writer).format_users(&users()).unwrap();
+        assert!(yaml.starts_with("- id: U1\n  name: alice\n"));
+
+        // Any other format (human, human-compact) goes through the formatters
+        for format in ["human", "human-compact"] {
+            let human = select(format, None, new_writer).format_users(&users()).unwrap();
+            assert!(human.contains("@alice (U1)"));
+        }
+    }
+}
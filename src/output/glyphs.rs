@@ -0,0 +1,116 @@
+//! Every decorative glyph used in human output, in one place so `--ascii` can swap them.
+
+/// Decorative symbols for human output
+#[derive(Debug, PartialEq, Eq)]
+pub struct Glyphs {
+    /// True for the plain-ASCII table
+    pub ascii: bool,
+    pub pin: &'static str,
+    pub thread: &'static str,
+    pub link: &'static str,
+    pub private: &'static str,
+    pub archived: &'static str,
+    /// Horizontal rule character for separators
+    pub rule: &'static str,
+    pub error: &'static str,
+    pub active: &'static str,
+    pub away: &'static str,
+    pub email: &'static str,
+    /// Multiplication sign for counts ("×12")
+    pub times: &'static str,
+    /// Before/after separator ("old → new")
+    pub arrow: &'static str,
+    /// Marks text cut short ("first words…")
+    pub ellipsis: &'static str,
+}
+
+pub const UNICODE: Glyphs = Glyphs {
+    ascii: false,
+    pin: "📌",
+    thread: "💬",
+    link: "🔗",
+    private: "🔒",
+    archived: "📦",
+    rule: "─",
+    error: "✗",
+    active: "●",
+    away: "○",
+    email: "✉",
+    times: "×",
+    arrow: "→",
+    ellipsis: "…",
+};
+
+pub const ASCII: Glyphs = Glyphs {
+    ascii: true,
+    pin: "[PIN]",
+    thread: "[thread]",
+    link: "link:",
+    private: "[private]",
+    archived: "[archived]",
+    rule: "-",
+    error: "x",
+    active: "*",
+    away: "o",
+    email: "email:",
+    times: "x",
+    arrow: "->",
+    ellipsis: "...",
+};
+
+impl Glyphs {
+    /// A glyph in front of a label that already says the same thing ("🔒 Private").
+    /// ASCII output keeps just the label rather than doubling it up.
+    pub fn labelled(&self, glyph: &str, label: &str) -> String {
+        if self.ascii {
+            label.to_string()
+        } else {
+            format!("{} {}", glyph, label)
+        }
+    }
+}
+
+/// Whether the locale (LC_ALL, then LC_CTYPE, then LANG) can display UTF-8.
+/// An unset locale is assumed to be fine; "C", "POSIX" and non-UTF-8 charsets are not.
+pub fn locale_supports_utf8() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty());
+    match locale {
+        Some(locale) => is_utf8_locale(&locale),
+        None => true,
+    }
+}
+
+fn is_utf8_locale(locale: &str) -> bool {
+    let locale = locale.to_ascii_lowercase();
+    locale.contains("utf-8") || locale.contains("utf8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_utf8_locale() {
+        assert!(is_utf8_locale("en_US.UTF-8"));
+        assert!(is_utf8_locale("C.utf8"));
+        assert!(!is_utf8_locale("C"));
+        assert!(!is_utf8_locale("POSIX"));
+        assert!(!is_utf8_locale("de_DE.ISO-8859-1"));
+    }
+
+    #[test]
+    fn test_ascii_table_is_ascii() {
+        for glyph in [
+            ASCII.pin, ASCII.thread, ASCII.link, ASCII.private, ASCII.archived, ASCII.rule, ASCII.error,
+            ASCII.active, ASCII.away, ASCII.email, ASCII.times,
+            ASCII.arrow,
+        ] {
+            assert!(glyph.is_ascii(), "{}", glyph);
+        }
+        assert_eq!(ASCII.labelled(ASCII.private, "Private"), "Private");
+        assert_eq!(UNICODE.labelled(UNICODE.private, "Private"), "🔒 Private");
+    }
+}
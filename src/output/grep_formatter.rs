@@ -0,0 +1,143 @@
+use crate::models::message::Message;
+use crate::models::user::User;
+use crate::output::color::ColorWriter;
+use crate::output::render::RenderOptions;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Result;
+use termcolor::Color;
+
+/// One message matched by `conversations grep`
+#[derive(Debug, Serialize)]
+pub struct GrepMatch {
+    pub ts: String,
+    pub user: Option<String>,
+    pub text: String,
+    /// Each matched substring, in order
+    pub matches: Vec<String>,
+    /// The text with every match replaced, when `--replace` was given. Never sent to Slack.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaced: Option<String>,
+    pub permalink: String,
+}
+
+/// Messages whose text matches `pattern`, in the order given. `replace` is a regex
+/// replacement template (`$1`, `${name}`, `$$` for a literal `$`).
+pub fn grep_messages(
+    messages: &[Message],
+    channel_id: &str,
+    pattern: &Regex,
+    replace: Option<&str>,
+    options: &RenderOptions,
+) -> Vec<GrepMatch> {
+    messages
+        .iter()
+        .filter(|msg| pattern.is_match(&msg.text))
+        .map(|msg| GrepMatch {
+            ts: msg.ts.clone(),
+            user: msg.user.clone(),
+            text: msg.text.clone(),
+            matches: pattern.find_iter(&msg.text).map(|m| m.as_str().to_string()).collect(),
+            replaced: replace.map(|template| pattern.replace_all(&msg.text, template).into_owned()),
+            permalink: crate::output::message_formatter::message_url(msg, channel_id, options),
+        })
+        .collect()
+}
+
+pub fn format_grep_matches(
+    matches: &[GrepMatch],
+    pattern: &Regex,
+    channel_name: &str,
+    users: &HashMap<String, User>,
+    writer: &mut ColorWriter,
+) -> Result<()> {
+    writer.print_header(&format!(
+        "{} message{} in #{} matching /{}/",
+        matches.len(),
+        if matches.len() == 1 { "" } else { "s" },
+        channel_name,
+        pattern.as_str()
+    ))?;
+    writer.print_separator()?;
+
+    for (i, found) in matches.iter().enumerate() {
+        let author = match found.user.as_deref() {
+            Some(user_id) => users.get(user_id).map(|u| format!("@{}", u.name)).unwrap_or_else(|| user_id.to_string()),
+            None => "<system>".to_string(),
+        };
+        let ts_float: f64 = found.ts.parse().unwrap_or(0.0);
+        let time: chrono::DateTime<chrono::Local> =
+            chrono::DateTime::from_timestamp(ts_float as i64, 0).unwrap_or_default().into();
+
+        writer.print_colored(&author, Color::Green)?;
+        writer.write(&format!(" {}", time.format("%Y-%m-%d %H:%M")))?;
+        writer.writeln()?;
+
+        // Full text with each match highlighted; matches aren't truncated away
+        writer.write("  ")?;
+        let mut last = 0;
+        for m in pattern.find_iter(&found.text) {
+            writer.write(&found.text[last..m.start()].replace('\n', "\n  "))?;
+            writer.print_colored(&m.as_str().replace('\n', "\n  "), Color::Red)?;
+            last = m.end();
+        }
+        writer.write(&found.text[last..].replace('\n', "\n  "))?;
+        writer.writeln()?;
+
+        if let Some(ref replaced) = found.replaced {
+            writer.print_colored("  Would become: ", Color::Blue)?;
+            writer.write(&replaced.replace('\n', "\n  "))?;
+            writer.writeln()?;
+        }
+
+        writer.write(&format!("  {} ", writer.glyphs().link))?;
+        writer.write(&found.permalink)?;
+        writer.writeln()?;
+
+        if i < matches.len() - 1 {
+            writer.writeln()?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(ts: &str, user: &str, text: &str) -> Message {
+        serde_json::from_value(serde_json::json!({"ts": ts, "user": user, "text": text})).unwrap()
+    }
+
+    #[test]
+    fn test_grep_messages_with_replacement_preview() {
+        let messages = vec![
+            message("1700000000.000100", "U1", "deploy to staging.example.com today"),
+            message("1700000060.000100", "U2", "nothing to see"),
+            message("1700000120.000100", "U1", "staging.example.com and staging.example.com"),
+        ];
+        let pattern = Regex::new(r"(\w+)\.example\.com").unwrap();
+
+        let found = grep_messages(&messages, "C1", &pattern, Some("$1.example.org"), &RenderOptions::default());
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].matches, vec!["staging.example.com"]);
+        assert_eq!(found[0].replaced.as_deref(), Some("deploy to staging.example.org today"));
+        assert_eq!(found[1].matches.len(), 2);
+        assert_eq!(found[1].permalink, "https://slack.com/archives/C1/p1700000120000100");
+
+        // Without --replace there's no preview, and json leaves the field out
+        let found = grep_messages(&messages, "C1", &pattern, None, &RenderOptions::default());
+        assert!(found[0].replaced.is_none());
+        assert!(serde_json::to_value(&found[0]).unwrap().get("replaced").is_none());
+
+        let users = HashMap::new();
+        let mut writer = ColorWriter::new(true);
+        let preview = grep_messages(&messages, "C1", &pattern, Some("$1.example.org"), &RenderOptions::default());
+        format_grep_matches(&preview, &pattern, "general", &users, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("2 messages in #general matching"));
+        assert!(output.contains("Would become: deploy to staging.example.org today"));
+    }
+}
@@ -0,0 +1,174 @@
+//! Minimal JSONPath evaluator for `--json-path`.
+//!
+//! Supported subset:
+//! - `$` - the root (optional; `messages[*].ts` means `$.messages[*].ts`)
+//! - `.name` and `['name']` / `["name"]` - object member
+//! - `[N]` - array element, negative N counts from the end
+//! - `[*]` and `.*` - every element of an array or every value of an object
+//!
+//! Filters, slices, recursive descent (`..`) and unions are not supported.
+
+use anyhow::Result;
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Key(String),
+    Index(i64),
+    Wildcard,
+}
+
+/// Parse an expression such as `$.messages[*].ts` into path segments
+pub fn parse(expr: &str) -> Result<Vec<Segment>> {
+    let expr = expr.trim();
+    let rest = expr.strip_prefix('$').unwrap_or(expr);
+    let chars: Vec<char> = rest.chars().collect();
+    let mut segments = Vec::new();
+    let mut i = 0;
+
+    // A leading bare name (`messages[0]`) reads like `$.messages[0]`
+    let mut expect_name = !rest.is_empty() && !rest.starts_with(['.', '[']);
+
+    while i < chars.len() || expect_name {
+        if expect_name {
+            let start = i;
+            while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                i += 1;
+            }
+            let name: String = chars[start..i].iter().collect();
+            match name.as_str() {
+                "" => anyhow::bail!("Invalid --json-path '{}': expected a member name at position {}", expr, start + 1),
+                "*" => segments.push(Segment::Wildcard),
+                _ => segments.push(Segment::Key(name)),
+            }
+            expect_name = false;
+            continue;
+        }
+
+        match chars[i] {
+            '.' => {
+                if chars.get(i + 1) == Some(&'.') {
+                    anyhow::bail!("Invalid --json-path '{}': recursive descent (..) is not supported", expr);
+                }
+                i += 1;
+                expect_name = true;
+            }
+            '[' => {
+                let close = chars[i..]
+                    .iter()
+                    .position(|&c| c == ']')
+                    .map(|p| i + p)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid --json-path '{}': unclosed '['", expr))?;
+                let inner: String = chars[i + 1..close].iter().collect();
+                let inner = inner.trim();
+                let quoted = inner
+                    .strip_prefix('\'')
+                    .and_then(|s| s.strip_suffix('\''))
+                    .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')));
+
+                if inner == "*" {
+                    segments.push(Segment::Wildcard);
+                } else if let Some(name) = quoted {
+                    segments.push(Segment::Key(name.to_string()));
+                } else {
+                    let index = inner.parse::<i64>().map_err(|_| {
+                        anyhow::anyhow!(
+                            "Invalid --json-path '{}': [{}] must be an index, * or a quoted name",
+                            expr,
+                            inner
+                        )
+                    })?;
+                    segments.push(Segment::Index(index));
+                }
+                i = close + 1;
+            }
+            c => anyhow::bail!("Invalid --json-path '{}': unexpected '{}' at position {}", expr, c, i + 1),
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Every value the path matches, in document order. Missing members and out-of-range
+/// indexes match nothing rather than failing.
+pub fn select<'a>(root: &'a Value, path: &[Segment]) -> Vec<&'a Value> {
+    let mut current = vec![root];
+    for segment in path {
+        let mut next = Vec::new();
+        for value in current {
+            match (segment, value) {
+                (Segment::Key(name), Value::Object(map)) => next.extend(map.get(name)),
+                (Segment::Index(index), Value::Array(items)) => {
+                    let resolved = if *index < 0 { items.len() as i64 + index } else { *index };
+                    if resolved >= 0 {
+                        next.extend(items.get(resolved as usize));
+                    }
+                }
+                (Segment::Wildcard, Value::Array(items)) => next.extend(items.iter()),
+                (Segment::Wildcard, Value::Object(map)) => next.extend(map.values()),
+                _ => {}
+            }
+        }
+        current = next;
+    }
+    current
+}
+
+/// Render matches one per line: strings bare, everything else as compact JSON
+pub fn render(matches: &[&Value]) -> String {
+    let mut out = String::new();
+    for value in matches {
+        match value {
+            Value::String(s) => out.push_str(s),
+            other => out.push_str(&other.to_string()),
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_supported_subset() {
+        assert_eq!(
+            parse("$.messages[*].ts").unwrap(),
+            vec![Segment::Key("messages".into()), Segment::Wildcard, Segment::Key("ts".into())]
+        );
+        assert_eq!(
+            parse("messages[-1]['user']").unwrap(),
+            vec![Segment::Key("messages".into()), Segment::Index(-1), Segment::Key("user".into())]
+        );
+        assert_eq!(parse("$").unwrap(), vec![]);
+        assert!(parse("$..ts").is_err());
+        assert!(parse("$.messages[?(@.user)]").is_err());
+        assert!(parse("$.messages[0").is_err());
+        assert!(parse("$.").is_err());
+    }
+
+    #[test]
+    fn test_select_and_render() {
+        let value = json!({
+            "messages": [
+                {"ts": "1.1", "user": "U1", "reply_count": 2},
+                {"ts": "2.2", "user": "U2"},
+            ],
+            "has_more": false
+        });
+
+        let ts = select(&value, &parse("$.messages[*].ts").unwrap());
+        assert_eq!(render(&ts), "1.1\n2.2\n");
+
+        let last = select(&value, &parse("$.messages[-1].user").unwrap());
+        assert_eq!(render(&last), "U2\n");
+
+        // Missing members are skipped, non-strings stay JSON
+        let counts = select(&value, &parse("$.messages[*].reply_count").unwrap());
+        assert_eq!(render(&counts), "2\n");
+        assert_eq!(render(&select(&value, &parse("$.has_more").unwrap())), "false\n");
+        assert!(select(&value, &parse("$.messages[5]").unwrap()).is_empty());
+    }
+}
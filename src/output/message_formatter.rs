@@ -1,22 +1,91 @@
 use crate::models::channel::Channel;
-use crate::models::message::Message;
+use crate::models::message::{parse_ts, Message};
 use crate::models::user::User;
-use crate::output::color::ColorWriter;
-use chrono::{DateTime, Local};
+use crate::output::color::{ColorWriter, IdFormat};
+use crate::output::render::RenderOptions;
+use chrono::{DateTime, Local, NaiveDate};
 use std::collections::HashMap;
 use std::io::Result;
+use std::str::FromStr;
 use termcolor::Color;
 use textwrap::wrap;
 
+/// Width of the author column in message headers (`--author-name-width`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthorWidth {
+    /// Pad to the longest author shown
+    Auto,
+    /// Pad to a fixed number of characters; longer handles overflow
+    Fixed(usize),
+}
+
+impl FromStr for AuthorWidth {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(AuthorWidth::Auto);
+        }
+        s.parse()
+            .map(AuthorWidth::Fixed)
+            .map_err(|_| format!("expected a width in characters or 'auto', got '{}'", s))
+    }
+}
+
+/// How reactions are shown under messages (`--reactions`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReactionDisplay {
+    /// Every emoji with its count, e.g. `:thumbsup:5 :heart:3`
+    #[default]
+    Full,
+    /// The most-used emoji and the total, e.g. `:thumbsup: ×8 reactions`
+    Compact,
+    /// No reaction line
+    Off,
+}
+
+/// The reaction line for a message in the writer's `--reactions` mode, or `None` to print nothing
+pub(crate) fn reaction_line(msg: &Message, writer: &ColorWriter) -> Option<String> {
+    let reactions = msg.reactions.as_deref().filter(|r| !r.is_empty())?;
+    match writer.options().reaction_display {
+        ReactionDisplay::Off => None,
+        ReactionDisplay::Full => Some(
+            reactions
+                .iter()
+                .map(|r| format!("{}{}", writer.emoji_label(&r.name), r.count))
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        ReactionDisplay::Compact => {
+            let total: u32 = reactions.iter().map(|r| r.count).sum();
+            // First listed wins ties, matching Slack's own ordering
+            let top = reactions.iter().fold(&reactions[0], |best, r| if r.count > best.count { r } else { best });
+            Some(format!(
+                "{} {}{} reaction{}",
+                writer.emoji_label(&top.name),
+                writer.glyphs().times,
+                total,
+                if total == 1 { "" } else { "s" }
+            ))
+        }
+    }
+}
+
 pub fn format_messages_with_thread_info(
     messages: &[Message],
     channel: &Channel,
     users: &HashMap<String, User>,
     thread_info: &HashMap<String, (usize, Vec<String>)>, // Map of thread_ts -> (reply_count, participants)
+    group_by_day: bool,
+    author_width: Option<AuthorWidth>,
     writer: &mut ColorWriter,
 ) -> Result<()> {
+    if writer.options().transcript {
+        return format_transcript(messages, channel, users, writer);
+    }
+
     // Channel metadata summary
-    writer.print_header(&format!("#{} ({})", channel.name, channel.id))?;
+    writer.print_header(&writer.options().id_format.label(&format!("#{}", channel.name), &channel.id))?;
 
     // Topic if present
     if let Some(topic) = &channel.topic {
@@ -49,8 +118,38 @@ pub fn format_messages_with_thread_info(
     writer.print_header(&format!("Messages ({})", messages.len()))?;
     writer.print_separator()?;
 
+    if messages.is_empty() {
+        return writer.print_empty("messages");
+    }
+
+    let author_width = author_width.map(|width| match width {
+        AuthorWidth::Fixed(n) => n,
+        AuthorWidth::Auto => messages
+            .iter()
+            .map(|m| author_label(m, users, writer.options().id_format).chars().count())
+            .max()
+            .unwrap_or(0),
+    });
+
+    // Expects oldest-first order so each date gets a single separator
+    let mut current_day: Option<NaiveDate> = None;
+
     for (i, msg) in messages.iter().enumerate() {
-        format_message(msg, &channel.name, &channel.id, users, thread_info, writer)?;
+        if group_by_day {
+            let day = local_time(&msg.ts).date_naive();
+            if current_day != Some(day) {
+                if current_day.is_some() {
+                    writer.writeln()?;
+                }
+                let rule = writer.glyphs().rule.repeat(2);
+                writer.print_bold(&format!("{} {} {}", rule, day.format("%Y-%m-%d"), rule))?;
+                writer.writeln()?;
+                writer.writeln()?;
+                current_day = Some(day);
+            }
+        }
+
+        format_message(msg, &channel.name, &channel.id, users, thread_info, author_width, writer)?;
 
         if i < messages.len() - 1 {
             writer.writeln()?;
@@ -60,36 +159,119 @@ pub fn format_messages_with_thread_info(
     Ok(())
 }
 
-/// Backward compatibility wrapper - formats messages without thread info
-pub fn format_messages(
+/// Continuation indent in transcripts, the width of "HH:MM "
+const TRANSCRIPT_INDENT: &str = "      ";
+
+/// Chat-log view for `--format transcript`: one channel header, then messages
+/// oldest first under date separators, without the per-message channel prefix,
+/// thread participants or links. Mentions and links read as they do in Slack.
+pub fn format_transcript(
     messages: &[Message],
     channel: &Channel,
     users: &HashMap<String, User>,
     writer: &mut ColorWriter,
 ) -> Result<()> {
-    let empty_thread_info = HashMap::new();
-    format_messages_with_thread_info(messages, channel, users, &empty_thread_info, writer)
+    writer.print_header(&writer.options().id_format.label(&format!("#{}", channel.name), &channel.id))?;
+    if messages.is_empty() {
+        return writer.print_empty("messages");
+    }
+
+    let mut ordered: Vec<&Message> = messages.iter().collect();
+    ordered.sort_by_key(|m| parse_ts(&m.ts).unwrap_or_default());
+
+    let wrap_width = crate::output::width::get_wrap_width();
+    let mut current_day: Option<NaiveDate> = None;
+    for msg in ordered {
+        let time = local_time(&msg.ts);
+        let day = time.date_naive();
+        if current_day != Some(day) {
+            writer.writeln()?;
+            let rule = writer.glyphs().rule.repeat(2);
+            writer.print_bold(&format!("{} {} {}", rule, day.format("%Y-%m-%d"), rule))?;
+            writer.writeln()?;
+            current_day = Some(day);
+        }
+
+        let time_str = if writer.options().raw_ts { msg.ts.clone() } else { time.format("%H:%M").to_string() };
+        writer.print_colored(&time_str, Color::Yellow)?;
+        writer.write(" ")?;
+        let author = author_label(msg, users, writer.options().id_format);
+        writer.print_colored(&author, if msg.user.is_some() { Color::Cyan } else { Color::White })?;
+        writer.write(": ")?;
+
+        // Wrapped and later lines are indented past the "HH:MM " column
+        let text = writer.message_text(&crate::output::text::render_mrkdwn(&msg.text, users));
+        let first_indent = " ".repeat(time_str.chars().count() + author.chars().count() + 3);
+        for (n, line) in text.lines().enumerate() {
+            let options = textwrap::Options::new(wrap_width)
+                .initial_indent(if n == 0 { &first_indent } else { TRANSCRIPT_INDENT })
+                .subsequent_indent(TRANSCRIPT_INDENT);
+            for (i, wrapped) in wrap(line, options).iter().enumerate() {
+                if n > 0 || i > 0 {
+                    writer.writeln()?;
+                }
+                let skip = if n == 0 && i == 0 { first_indent.len() } else { 0 };
+                writer.write(wrapped.get(skip..).unwrap_or_default())?;
+            }
+        }
+        writer.writeln()?;
+
+        if let Some(line) = reaction_line(msg, writer) {
+            writer.write(&format!("{}{}", TRANSCRIPT_INDENT, line))?;
+            writer.writeln()?;
+        }
+        if let Some(count) = msg.reply_count.filter(|n| *n > 0) {
+            writer.print_colored(
+                &format!("{}{} {} repl{}", TRANSCRIPT_INDENT, writer.glyphs().thread, count, if count == 1 { "y" } else { "ies" }),
+                Color::Blue,
+            )?;
+            writer.writeln()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `@name` for known users, the raw ID otherwise (and with `--id-format only`), or
+/// `<system>` for messages without a user
+fn author_label(msg: &Message, users: &HashMap<String, User>, id_format: IdFormat) -> String {
+    match &msg.user {
+        Some(user_id) => match users.get(user_id) {
+            Some(user) if id_format.shows_name() => format!("@{}", user.name),
+            _ => user_id.clone(),
+        },
+        None => "<system>".to_string(),
+    }
+}
+
+/// Convert a Slack timestamp to local time
+fn local_time(ts: &str) -> DateTime<Local> {
+    let ts_float: f64 = ts.parse().unwrap_or(0.0);
+    DateTime::from_timestamp(ts_float as i64, 0).unwrap_or_default().into()
 }
 
+#[allow(clippy::too_many_arguments)]
 fn format_message(
     msg: &Message,
     channel_name: &str,
     channel_id: &str,
     users: &HashMap<String, User>,
     thread_info: &HashMap<String, (usize, Vec<String>)>,
+    author_width: Option<usize>,
     writer: &mut ColorWriter,
 ) -> Result<()> {
     // Parse timestamp and convert to local timezone
-    let ts_float: f64 = msg.ts.parse().unwrap_or(0.0);
-    let dt_utc = DateTime::from_timestamp(ts_float as i64, 0).unwrap_or_default();
-    let dt_local: DateTime<Local> = dt_utc.into();
+    let dt_local = local_time(&msg.ts);
 
     // Calculate time difference
     let now = Local::now();
     let duration = now.signed_duration_since(dt_local);
 
     // Format timestamp based on age
-    let time_str = if duration.num_hours() < 24 {
+    let time_str = if writer.options().raw_ts {
+        // --raw-ts: Slack's own ts, for correlating with other API data
+        msg.ts.clone()
+    } else if duration.num_hours() < 24 {
         // Less than 1 day old - use "N units ago"
         if duration.num_minutes() < 1 {
             "just now".to_string()
@@ -114,19 +296,19 @@ fn format_message(
     };
 
     // Channel name in green
-    writer.print_colored(&format!("#{}", channel_name), Color::Green)?;
+    let channel_label = if writer.options().id_format.shows_name() { channel_name } else { channel_id };
+    writer.print_colored(&format!("#{}", channel_label), Color::Green)?;
     writer.write(" ")?;
 
     // User handle (name) in cyan, or ID if user not found
-    if let Some(user_id) = &msg.user {
-        if let Some(user) = users.get(user_id) {
-            writer.print_colored(&format!("@{}", user.name), Color::Cyan)?;
-        } else {
-            // Fallback to ID if user not in map
-            writer.print_colored(user_id, Color::Cyan)?;
-        }
-    } else {
-        writer.print_colored("<system>", Color::White)?;
+    let author = author_label(msg, users, writer.options().id_format);
+    let author_color = if msg.user.is_some() { Color::Cyan } else { Color::White };
+    writer.print_colored(&author, author_color)?;
+
+    // Pad outside the colored span so escape codes don't count toward the width
+    if let Some(width) = author_width {
+        let padding = width.saturating_sub(author.chars().count());
+        writer.write(&" ".repeat(padding))?;
     }
     writer.write(" ")?;
 
@@ -136,25 +318,19 @@ fn format_message(
 
     // Message text wrapped dynamically to terminal width
     let wrap_width = crate::output::width::get_wrap_width();
-    let wrapped = wrap(&msg.text, wrap_width);
+    let text = writer.message_text(&msg.text);
+    let wrapped = wrap(&text, wrap_width);
     for line in wrapped {
         writer.write("  ")?;
         writer.write(&line)?;
         writer.writeln()?;
     }
 
-    // Reactions if present
-    if let Some(reactions) = &msg.reactions {
-        if !reactions.is_empty() {
-            writer.write("  ")?;
-            for (i, reaction) in reactions.iter().enumerate() {
-                if i > 0 {
-                    writer.write(" ")?;
-                }
-                writer.write(&format!(":{}:{}", reaction.name, reaction.count))?;
-            }
-            writer.writeln()?;
-        }
+    // Reactions if present, as --reactions asks
+    if let Some(line) = reaction_line(msg, writer) {
+        writer.write("  ")?;
+        writer.write(&line)?;
+        writer.writeln()?;
     }
 
     // Thread indicator
@@ -164,7 +340,7 @@ fn format_message(
         // Get thread metadata if available
         if let Some((reply_count, participant_ids)) = thread_info.get(thread_ts) {
             writer.print_colored(
-                &format!("💬 Part of thread ({} replies)", reply_count),
+                &format!("{} Part of thread ({} replies)", writer.glyphs().thread, reply_count),
                 Color::Blue
             )?;
             writer.writeln()?;
@@ -186,23 +362,27 @@ fn format_message(
             }
         } else {
             // Fallback to simple indicator
-            writer.print_colored("💬 Part of thread", Color::Blue)?;
+            writer.print_colored(&format!("{} Part of thread", writer.glyphs().thread), Color::Blue)?;
             writer.writeln()?;
         }
     }
 
-    // Message URL with actual channel ID
-    let msg_ts = msg.ts.replace('.', "");
-    writer.write("  🔗 ")?;
-    writer.write(&format!(
-        "https://slack.com/archives/{}/p{}",
-        channel_id, msg_ts
-    ))?;
+    // Message URL: the canonical permalink when known, else one built from the channel ID
+    writer.write(&format!("  {} ", writer.glyphs().link))?;
+    writer.write(&message_url(msg, channel_id, writer.options()))?;
     writer.writeln()?;
 
     Ok(())
 }
 
+/// Canonical permalink if the message carries one, otherwise a constructed archive URL
+pub fn message_url(msg: &Message, channel_id: &str, options: &RenderOptions) -> String {
+    match &msg.permalink {
+        Some(permalink) => permalink.clone(),
+        None => options.archive_url(channel_id, &msg.ts),
+    }
+}
+
 /// Format a single message in compact single-line format.
 /// Includes: timestamp, channel, user, truncated text, and permalink.
 /// Used for streaming output and human-compact format.
@@ -217,10 +397,12 @@ pub fn format_message_compact(
     let dt_local: DateTime<Local> = dt_utc.into();
 
     // Timestamp prefix
-    writer.print_colored(
-        &format!("[{}] ", dt_local.format("%Y-%m-%d %H:%M")),
-        Color::White,
-    )?;
+    let time_str = if writer.options().raw_ts {
+        msg.ts.clone()
+    } else {
+        dt_local.format("%Y-%m-%d %H:%M").to_string()
+    };
+    writer.print_colored(&format!("[{}] ", time_str), Color::White)?;
 
     // Channel
     if let Some(channel) = &msg.channel {
@@ -245,15 +427,9 @@ pub fn format_message_compact(
     writer.write(": ")?;
 
     // Message text (single line, truncated if needed)
-    let text = msg.text.replace('\n', " ");
-    let max_len = 80;
-    let truncated = if text.chars().count() > max_len {
-        let truncated_text: String = text.chars().take(max_len - 3).collect();
-        format!("{}...", truncated_text)
-    } else {
-        text
-    };
-    writer.write(&truncated)?;
+    let text = writer.emoji_text(&msg.text).replace('\n', " ");
+    let max_len = writer.options().max_text_length.map_or(80, |n| n.min(80));
+    writer.write(&crate::output::text::truncate(&text, Some(max_len)))?;
 
     // Permalink (always include for compact format)
     if let Some(permalink) = &msg.permalink {
@@ -265,6 +441,52 @@ pub fn format_message_compact(
     Ok(())
 }
 
+/// Characters of message text kept by `--oneline`
+const ONELINE_TEXT_LEN: usize = 80;
+
+/// One line per message for `--oneline`: `[time] #chan @user: first 80 chars…`.
+/// Newlines are folded into spaces so every message stays greppable.
+/// `channel_name` labels messages that don't carry their own channel (history).
+pub fn format_messages_oneline(
+    messages: &[Message],
+    channel_name: Option<&str>,
+    users: &HashMap<String, User>,
+    writer: &mut ColorWriter,
+) -> Result<()> {
+    for msg in messages {
+        let time_str = if writer.options().raw_ts {
+            msg.ts.clone()
+        } else {
+            local_time(&msg.ts).format("%Y-%m-%d %H:%M").to_string()
+        };
+        writer.print_colored(&format!("[{}] ", time_str), Color::White)?;
+
+        let channel = msg
+            .channel
+            .as_ref()
+            .map(|c| c.name().unwrap_or(c.id()))
+            .or(channel_name);
+        if let Some(channel) = channel {
+            writer.print_colored(&format!("#{} ", channel), Color::Green)?;
+        }
+
+        writer.print_colored(&author_label(msg, users, writer.options().id_format), Color::Cyan)?;
+        writer.write(": ")?;
+
+        let text = writer.emoji_text(&msg.text).split_whitespace().collect::<Vec<_>>().join(" ");
+        let max_len = writer.options().max_text_length.map_or(ONELINE_TEXT_LEN, |n| n.min(ONELINE_TEXT_LEN));
+        match text.char_indices().nth(max_len) {
+            Some((end, _)) => {
+                writer.write(text[..end].trim_end())?;
+                writer.write(writer.glyphs().ellipsis)?;
+            }
+            None => writer.write(&text)?,
+        }
+        writer.writeln()?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -282,13 +504,19 @@ mod tests {
             is_mpim: None,
             is_private: Some(false),
             is_archived: Some(false),
+            is_member: None,
             topic: Some(ChannelTopic {
                 value: "General discussions".to_string(),
+                creator: None,
+                last_set: None,
             }),
             purpose: Some(ChannelPurpose {
                 value: "Company-wide communication".to_string(),
+                creator: None,
+                last_set: None,
             }),
             num_members: Some(42),
+            user: None,
         }
     }
 
@@ -309,6 +537,8 @@ mod tests {
             is_admin: None,
             is_owner: None,
             tz: None,
+            presence: None,
+            enterprise_user: None,
         }
     }
 
@@ -321,6 +551,10 @@ mod tests {
             reactions: None,
             channel: None,
             permalink: None,
+            bot_id: None,
+            subtype: None,
+            edited: None,
+            reply_count: None,
         }
     }
 
@@ -331,11 +565,101 @@ mod tests {
         let users = HashMap::new();
         let mut writer = ColorWriter::new(true); // no_color = true for testing
 
-        format_messages(&messages, &channel, &users, &mut writer).unwrap();
+        format_messages_with_thread_info(&messages, &channel, &users, &HashMap::new(), false, None, &mut writer).unwrap();
 
         // Test passes if no panic - actual output would be verified in integration tests
     }
 
+    #[test]
+    fn test_message_url_prefers_permalink() {
+        let mut message = create_test_message("1234567890.123456", Some("U123"), "Hello world");
+        assert_eq!(
+            message_url(&message, "C123", &RenderOptions::default()),
+            "https://slack.com/archives/C123/p1234567890123456"
+        );
+
+        message.permalink = Some("https://acme.slack.com/archives/C123/p1234567890123456".to_string());
+        let mut writer = ColorWriter::new(true);
+        format_message(&message, "general", "C123", &HashMap::new(), &HashMap::new(), None, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("🔗 https://acme.slack.com/archives/C123/p1234567890123456"));
+    }
+
+    #[test]
+    fn test_format_message_raw_ts() {
+        let message = create_test_message("1234567890.123456", Some("U123"), "Hello world");
+        let mut writer = ColorWriter::new(true);
+        format_message(&message, "general", "C123", &HashMap::new(), &HashMap::new(), None, &mut writer).unwrap();
+        assert!(!writer.into_string().unwrap().contains("1234567890.123456"));
+
+        let mut writer = ColorWriter::new(true).with_options(RenderOptions { raw_ts: true, ..Default::default() });
+        format_message(&message, "general", "C123", &HashMap::new(), &HashMap::new(), None, &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().contains("1234567890.123456"));
+
+        let mut writer = ColorWriter::new(true).with_options(RenderOptions { raw_ts: true, ..Default::default() });
+        format_message_compact(&message, &HashMap::new(), &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().starts_with("[1234567890.123456] "));
+    }
+
+    #[test]
+    fn test_format_messages_oneline() {
+        let mut users = HashMap::new();
+        users.insert("U123".to_string(), create_test_user("U123", "alice"));
+        let messages = vec![
+            create_test_message("1234567890.123456", Some("U123"), "Deploy is done\n\nsee   the notes"),
+            create_test_message("1234567891.123456", Some("U999"), &"word ".repeat(30)),
+        ];
+
+        let mut writer = ColorWriter::new(true).with_options(RenderOptions { raw_ts: true, ..Default::default() });
+        format_messages_oneline(&messages, Some("general"), &users, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "[1234567890.123456] #general @alice: Deploy is done see the notes");
+        assert!(lines[1].starts_with("[1234567891.123456] #general U999: word word"));
+        assert!(lines[1].ends_with("word…"));
+        assert_eq!(lines[1].split(": ").nth(1).unwrap().chars().count(), 80);
+
+        let mut writer = ColorWriter::new(true).with_options(RenderOptions { ascii: true, ..Default::default() });
+        format_messages_oneline(&messages[1..], None, &users, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains(" U999: word") && output.trim_end().ends_with("word..."));
+        assert!(!output.contains('#'));
+    }
+
+    #[test]
+    fn test_format_messages_id_format() {
+        let mut users = HashMap::new();
+        users.insert("U123".to_string(), create_test_user("U123", "alice"));
+        let messages = vec![create_test_message("1234567890.123456", Some("U123"), "Hello world")];
+        let channel = create_test_channel();
+
+        let mut writer = ColorWriter::new(true);
+        format_messages_with_thread_info(&messages, &channel, &users, &HashMap::new(), false, None, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.starts_with("#general (C123)\n"));
+        assert!(output.contains("#general @alice"));
+
+        let mut writer = ColorWriter::new(true).with_options(RenderOptions { id_format: IdFormat::Only, ..Default::default() });
+        format_messages_with_thread_info(&messages, &channel, &users, &HashMap::new(), false, None, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.starts_with("C123\n"));
+        assert!(output.contains("#C123 U123"));
+        assert!(!output.contains("alice"));
+    }
+
+    #[test]
+    fn test_format_message_ascii_glyphs() {
+        let mut message = create_test_message("1234567890.123456", Some("U123"), "Hello world");
+        message.thread_ts = Some("1234567890.123456".to_string());
+        let mut writer = ColorWriter::new(true).with_options(RenderOptions { ascii: true, ..Default::default() });
+        format_message(&message, "general", "C123", &HashMap::new(), &HashMap::new(), None, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("[thread] Part of thread"));
+        assert!(output.contains("link: https://slack.com/archives/C123/p1234567890123456"));
+        assert!(output.is_ascii());
+    }
+
     #[test]
     fn test_format_message_with_user_handle() {
         let channel = create_test_channel();
@@ -346,7 +670,7 @@ mod tests {
         let message = create_test_message("1234567890.123456", Some("U123"), "Hello world");
 
         let mut writer = ColorWriter::new(true);
-        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), &mut writer).unwrap();
+        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), None, &mut writer).unwrap();
 
         // Test passes if no panic - user handle formatting is tested visually
     }
@@ -359,7 +683,7 @@ mod tests {
         let message = create_test_message("1234567890.123456", Some("U999"), "Hello world");
 
         let mut writer = ColorWriter::new(true);
-        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), &mut writer).unwrap();
+        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), None, &mut writer).unwrap();
 
         // Test passes if no panic - falls back to showing user ID
     }
@@ -372,7 +696,7 @@ mod tests {
         let message = create_test_message("1234567890.123456", None, "System message");
 
         let mut writer = ColorWriter::new(true);
-        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), &mut writer).unwrap();
+        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), None, &mut writer).unwrap();
 
         // Test passes if no panic - system messages shown correctly
     }
@@ -385,7 +709,7 @@ mod tests {
         let message = create_test_message("1234567890.123456", None, "Test");
 
         let mut writer = ColorWriter::new(true);
-        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), &mut writer).unwrap();
+        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), None, &mut writer).unwrap();
 
         // URL should contain channel ID "C123"
         // Actual URL generation verified through integration tests
@@ -401,17 +725,26 @@ mod tests {
             Reaction {
                 name: "thumbsup".to_string(),
                 count: 5,
+                users: None,
             },
             Reaction {
                 name: "heart".to_string(),
                 count: 3,
+                users: None,
             },
         ]);
 
         let mut writer = ColorWriter::new(true);
-        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), &mut writer).unwrap();
+        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), None, &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().contains("  :thumbsup:5 :heart:3\n"));
+
+        let mut writer = ColorWriter::new(true).with_options(RenderOptions { reaction_display: ReactionDisplay::Compact, ..Default::default() });
+        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), None, &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().contains("  :thumbsup: ×8 reactions\n"));
 
-        // Test passes if no panic - reactions formatted correctly
+        let mut writer = ColorWriter::new(true).with_options(RenderOptions { reaction_display: ReactionDisplay::Off, ..Default::default() });
+        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), None, &mut writer).unwrap();
+        assert!(!writer.into_string().unwrap().contains(":thumbsup:"));
     }
 
     #[test]
@@ -423,11 +756,107 @@ mod tests {
         message.thread_ts = Some("1234567890.123456".to_string());
 
         let mut writer = ColorWriter::new(true);
-        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), &mut writer).unwrap();
+        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), None, &mut writer).unwrap();
 
         // Test passes if no panic - thread indicator shown
     }
 
+    #[test]
+    fn test_group_by_day_inserts_separators() {
+        let channel = create_test_channel();
+        let users = HashMap::new();
+
+        // Two messages a day apart plus one an hour later, oldest first
+        let messages = vec![
+            create_test_message("1717236000.000000", None, "first"),
+            create_test_message("1717322400.000000", None, "second"),
+            create_test_message("1717326000.000000", None, "third"),
+        ];
+
+        let mut writer = ColorWriter::new(true);
+        format_messages_with_thread_info(&messages, &channel, &users, &HashMap::new(), true, None, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+
+        let first_day = local_time("1717236000.000000").format("%Y-%m-%d").to_string();
+        let second_day = local_time("1717322400.000000").format("%Y-%m-%d").to_string();
+        assert_eq!(output.matches("── ").count(), 2);
+        assert!(output.contains(&format!("── {} ──", first_day)));
+        assert!(output.contains(&format!("── {} ──", second_day)));
+        assert!(output.find("first").unwrap() < output.find(&second_day).unwrap());
+    }
+
+    #[test]
+    fn test_transcript_is_oldest_first_without_links() {
+        let channel = create_test_channel();
+        let mut users = HashMap::new();
+        users.insert("U1".to_string(), create_test_user("U1", "alice"));
+
+        // Newest first, as conversations.history returns them
+        let mut root = create_test_message("1717322400.000000", Some("U1"), "thanks <@U1>, see <#C9|ops>");
+        root.reply_count = Some(2);
+        let messages = vec![root, create_test_message("1717236000.000000", Some("U2"), "first")];
+
+        let mut writer = ColorWriter::new(true).with_options(RenderOptions { transcript: true, ..Default::default() });
+        format_messages_with_thread_info(&messages, &channel, &users, &HashMap::new(), false, None, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+
+        let first_time = local_time("1717236000.000000").format("%H:%M").to_string();
+        assert!(output.starts_with("#general (C123)\n"));
+        assert!(output.contains(&format!("{} U2: first\n", first_time)));
+        assert!(output.contains("@alice: thanks @alice, see #ops\n"));
+        assert!(output.contains("💬 2 replies"));
+        assert!(output.find("first").unwrap() < output.find("thanks").unwrap());
+        assert_eq!(output.matches("── ").count(), 2);
+        assert!(!output.contains("https://") && !output.contains("Topic"));
+    }
+
+    #[test]
+    fn test_author_name_width_aligns_timestamps() {
+        let channel = create_test_channel();
+        let mut users = HashMap::new();
+        users.insert("U1".to_string(), create_test_user("U1", "al"));
+        users.insert("U2".to_string(), create_test_user("U2", "bartholomew"));
+
+        let messages = vec![
+            create_test_message("1704067200.000000", Some("U1"), "short"),
+            create_test_message("1704067260.000000", Some("U2"), "long"),
+        ];
+
+        let header_offsets = |width: Option<AuthorWidth>| {
+            let mut writer = ColorWriter::new(true);
+            format_messages_with_thread_info(&messages, &channel, &users, &HashMap::new(), false, width, &mut writer)
+                .unwrap();
+            writer
+                .into_string()
+                .unwrap()
+                .lines()
+                .filter(|line| line.starts_with("#general @"))
+                // Timestamps of old messages are a fixed 19 characters at the end of the header
+                .map(|line| line.len() - "2024-01-01 00:00:00".len())
+                .collect::<Vec<_>>()
+        };
+
+        // Off by default: headers are not aligned
+        let offsets = header_offsets(None);
+        assert_ne!(offsets[0], offsets[1]);
+
+        // Auto pads to the longest handle
+        let offsets = header_offsets(Some(AuthorWidth::Auto));
+        assert_eq!(offsets[0], offsets[1]);
+
+        // Fixed widths pad shorter handles and let longer ones overflow
+        let offsets = header_offsets(Some(AuthorWidth::Fixed(20)));
+        assert_eq!(offsets[0], offsets[1]);
+        assert_eq!(offsets[0], "#general ".len() + 20 + 1);
+    }
+
+    #[test]
+    fn test_author_width_parsing() {
+        assert_eq!("auto".parse::<AuthorWidth>().unwrap(), AuthorWidth::Auto);
+        assert_eq!("16".parse::<AuthorWidth>().unwrap(), AuthorWidth::Fixed(16));
+        assert!("wide".parse::<AuthorWidth>().is_err());
+    }
+
     #[test]
     fn test_timestamp_parsing() {
         let channel = create_test_channel();
@@ -437,7 +866,7 @@ mod tests {
         let message = create_test_message("1704067200.000000", None, "New Year!");
 
         let mut writer = ColorWriter::new(true);
-        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), &mut writer).unwrap();
+        format_message(&message, &channel.name, &channel.id, &users, &HashMap::new(), None, &mut writer).unwrap();
 
         // Timestamp should be parsed and converted to local timezone
         // Exact output depends on system timezone
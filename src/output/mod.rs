@@ -1,11 +1,66 @@
 pub mod auth_formatter;
 pub mod channel_formatter;
 pub mod color;
+pub mod emoji;
 pub mod file_formatter;
+pub mod format;
+pub mod glyphs;
+pub mod grep_formatter;
+pub mod jsonpath;
 pub mod message_formatter;
 pub mod pager;
 pub mod pin_formatter;
+pub mod reaction_formatter;
+pub mod render;
+pub mod response_time_formatter;
 pub mod search_formatter;
+pub mod summary;
+pub mod text;
 pub mod thread_formatter;
+pub mod timezone_formatter;
 pub mod user_formatter;
+pub mod version_formatter;
 pub mod width;
+
+use serde::Serialize;
+
+/// Serialize a value for the machine-readable formats (`json`, `yaml`).
+///
+/// JSON is pretty-printed when `pretty` is `Some(true)` and compact when
+/// `Some(false)`; with `None` it is pretty on a terminal and compact when piped.
+pub fn serialize<T: Serialize + ?Sized>(value: &T, format: &str, pretty: Option<bool>) -> anyhow::Result<String> {
+    match format {
+        "json" => {
+            if pretty.unwrap_or_else(|| atty::is(atty::Stream::Stdout)) {
+                Ok(serde_json::to_string_pretty(value)?)
+            } else {
+                Ok(serde_json::to_string(value)?)
+            }
+        }
+        "yaml" => Ok(serde_yaml::to_string(value)?),
+        other => anyhow::bail!("Format '{}' is not a serialization format", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_json_pretty_and_compact() {
+        let value = serde_json::json!({"id": "U1", "name": "alice"});
+
+        let pretty = serialize(&value, "json", Some(true)).unwrap();
+        assert!(pretty.contains('\n'));
+
+        let compact = serialize(&value, "json", Some(false)).unwrap();
+        assert_eq!(compact, r#"{"id":"U1","name":"alice"}"#);
+    }
+
+    #[test]
+    fn test_serialize_yaml_and_unknown_format() {
+        let value = serde_json::json!({"id": "U1"});
+        assert_eq!(serialize(&value, "yaml", None).unwrap(), "id: U1\n");
+        assert!(serialize(&value, "human", None).is_err());
+    }
+}
@@ -1,9 +1,19 @@
 use crate::models::pin::PinItem;
+use crate::models::user::User;
 use crate::output::color::ColorWriter;
+use std::collections::HashMap;
 use std::io::Result;
 use termcolor::Color;
 
-pub fn format_pins_list(pins: &[PinItem], writer: &mut ColorWriter) -> Result<()> {
+/// `@name` for known users, the raw ID otherwise
+fn user_label(user_id: &str, users: &HashMap<String, User>) -> String {
+    match users.get(user_id) {
+        Some(user) => format!("@{}", user.name),
+        None => user_id.to_string(),
+    }
+}
+
+pub fn format_pins_list(pins: &[PinItem], users: &HashMap<String, User>, writer: &mut ColorWriter) -> Result<()> {
     writer.print_header(&format!("Pinned Items ({})", pins.len()))?;
     writer.print_separator()?;
 
@@ -15,31 +25,44 @@ pub fn format_pins_list(pins: &[PinItem], writer: &mut ColorWriter) -> Result<()
 
     for (i, pin) in pins.iter().enumerate() {
         // Pin type
-        writer.print_colored("📌 ", Color::Yellow)?;
-        writer.print_colored(&format!("{}", pin.pin_type), Color::Cyan)?;
+        writer.print_colored(&format!("{} ", writer.glyphs().pin), Color::Yellow)?;
+        writer.print_colored(&pin.pin_type, Color::Cyan)?;
         writer.writeln()?;
 
         // Pinned by and when
         writer.write("  ")?;
         writer.print_colored("Pinned by: ", Color::Blue)?;
-        writer.write(&pin.created_by)?;
+        writer.write(&user_label(&pin.created_by, users))?;
         writer.write(" on ")?;
         let datetime = chrono::DateTime::from_timestamp(pin.created as i64, 0)
-            .unwrap_or_else(|| chrono::Utc::now());
+            .unwrap_or_else(chrono::Utc::now);
         writer.write(&datetime.format("%Y-%m-%d %H:%M:%S").to_string())?;
         writer.writeln()?;
 
         // Message content if available
         if let Some(ref message) = pin.message {
+            if let Some(ref author) = message.user {
+                writer.write("  ")?;
+                writer.print_colored("Author: ", Color::Blue)?;
+                writer.write(&user_label(author, users))?;
+                writer.writeln()?;
+            }
+
             writer.write("  ")?;
             writer.print_colored("Message: ", Color::Blue)?;
-            writer.write(&message.text)?;
+            let text = writer.message_text(&message.text);
+            writer.write(&text)?;
+            writer.writeln()?;
+
+            writer.write("  ")?;
+            writer.print_colored("Timestamp: ", Color::Blue)?;
+            writer.write(&message.ts)?;
             writer.writeln()?;
 
-            if let Some(ref ts) = Some(&message.ts) {
+            if let Some(ref permalink) = message.permalink {
                 writer.write("  ")?;
-                writer.print_colored("Timestamp: ", Color::Blue)?;
-                writer.write(ts)?;
+                writer.print_colored("Link: ", Color::Blue)?;
+                writer.write(permalink)?;
                 writer.writeln()?;
             }
         }
@@ -62,6 +85,41 @@ mod tests {
     fn test_format_empty_pins_list() {
         let pins: Vec<PinItem> = vec![];
         let mut writer = ColorWriter::new(true);
-        format_pins_list(&pins, &mut writer).unwrap();
+        format_pins_list(&pins, &HashMap::new(), &mut writer).unwrap();
+    }
+
+    #[test]
+    fn test_format_pins_resolves_names_and_permalink() {
+        let pins: Vec<PinItem> = serde_json::from_value(serde_json::json!([{
+            "channel": "C1",
+            "created": 1700000000,
+            "created_by": "U1",
+            "type": "message",
+            "message": {
+                "ts": "1699999999.000100",
+                "user": "U2",
+                "text": "ship it",
+                "permalink": "https://test.slack.com/archives/C1/p1699999999000100"
+            }
+        }]))
+        .unwrap();
+        let users: HashMap<String, User> = [("U1", "alice")]
+            .into_iter()
+            .map(|(id, name)| {
+                let user: User = serde_json::from_value(serde_json::json!({
+                    "id": id, "name": name, "deleted": false, "is_bot": false, "profile": {}
+                }))
+                .unwrap();
+                (id.to_string(), user)
+            })
+            .collect();
+
+        let mut writer = ColorWriter::new(true);
+        format_pins_list(&pins, &users, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("Pinned by: @alice"));
+        // Unresolved authors fall back to the raw ID
+        assert!(output.contains("Author: U2"));
+        assert!(output.contains("Link: https://test.slack.com/archives/C1/p1699999999000100"));
     }
 }
@@ -0,0 +1,179 @@
+use crate::models::message::Message;
+use crate::models::user::User;
+use crate::output::color::ColorWriter;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Result;
+use termcolor::Color;
+
+#[derive(Debug, Serialize)]
+pub struct EmojiCount {
+    pub name: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReactorCount {
+    pub user: String,
+    pub count: u32,
+}
+
+/// Reaction usage tallied across a set of messages
+#[derive(Debug, Serialize)]
+pub struct ReactionLeaderboard {
+    pub messages_scanned: usize,
+    pub reactions: Vec<EmojiCount>,
+    // Empty when Slack did not include reacting users in the history payload
+    pub reactors: Vec<ReactorCount>,
+}
+
+/// Tally reaction emoji (and reacting users, when present) across messages,
+/// keeping the `top` entries of each ranked by count. With `normalize_skin_tones`,
+/// `wave::skin-tone-3` and friends count towards `wave`.
+pub fn tally_reactions(messages: &[Message], top: usize, normalize_skin_tones: bool) -> ReactionLeaderboard {
+    let mut emoji: HashMap<&str, u32> = HashMap::new();
+    let mut reactors: HashMap<&str, u32> = HashMap::new();
+
+    for reaction in messages.iter().flat_map(|m| m.reactions.iter().flatten()) {
+        let name = if normalize_skin_tones {
+            crate::output::emoji::strip_skin_tone(&reaction.name)
+        } else {
+            &reaction.name
+        };
+        *emoji.entry(name).or_default() += reaction.count;
+        for user_id in reaction.users.iter().flatten() {
+            *reactors.entry(user_id).or_default() += 1;
+        }
+    }
+
+    ReactionLeaderboard {
+        messages_scanned: messages.len(),
+        reactions: rank(emoji, top)
+            .into_iter()
+            .map(|(name, count)| EmojiCount { name, count })
+            .collect(),
+        reactors: rank(reactors, top)
+            .into_iter()
+            .map(|(user, count)| ReactorCount { user, count })
+            .collect(),
+    }
+}
+
+// Sort by count descending, then name so ties are stable
+fn rank(counts: HashMap<&str, u32>, top: usize) -> Vec<(String, u32)> {
+    let mut ranked: Vec<(String, u32)> = counts
+        .into_iter()
+        .map(|(key, count)| (key.to_string(), count))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked.truncate(top);
+    ranked
+}
+
+pub fn format_reaction_leaderboard(
+    board: &ReactionLeaderboard,
+    channel_name: &str,
+    users: &HashMap<String, User>,
+    writer: &mut ColorWriter,
+) -> Result<()> {
+    writer.print_header(&format!(
+        "Reactions in #{} ({} messages scanned)",
+        channel_name, board.messages_scanned
+    ))?;
+    writer.print_separator()?;
+
+    if board.reactions.is_empty() {
+        return writer.print_empty("reactions");
+    }
+
+    for (i, reaction) in board.reactions.iter().enumerate() {
+        writer.write(&format!("{:>3}. ", i + 1))?;
+        writer.print_colored(&writer.emoji_label(&reaction.name), Color::Yellow)?;
+        writer.write(&format!(" {}", reaction.count))?;
+        writer.writeln()?;
+    }
+
+    if !board.reactors.is_empty() {
+        writer.writeln()?;
+        writer.print_header("Top reactors")?;
+        for (i, reactor) in board.reactors.iter().enumerate() {
+            let name = users
+                .get(&reactor.user)
+                .map(|u| format!("@{}", u.name))
+                .unwrap_or_else(|| reactor.user.clone());
+            writer.write(&format!("{:>3}. ", i + 1))?;
+            writer.print_colored(&name, Color::Cyan)?;
+            writer.write(&format!(" {}", reactor.count))?;
+            writer.writeln()?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message_with_reactions(json: &str) -> Message {
+        serde_json::from_str(&format!(
+            r#"{{"ts": "1234567890.123456", "user": "U1", "text": "hi", "reactions": {}}}"#,
+            json
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tally_reactions_ranks_emoji_and_reactors() {
+        let messages = vec![
+            message_with_reactions(r#"[{"name": "tada", "count": 2, "users": ["U1", "U2"]}]"#),
+            message_with_reactions(
+                r#"[{"name": "heart", "count": 1, "users": ["U2"]}, {"name": "tada", "count": 1, "users": ["U2"]}]"#,
+            ),
+        ];
+
+        let board = tally_reactions(&messages, 10, false);
+        assert_eq!(board.messages_scanned, 2);
+        assert_eq!(board.reactions[0].name, "tada");
+        assert_eq!(board.reactions[0].count, 3);
+        assert_eq!(board.reactions[1].name, "heart");
+        assert_eq!(board.reactors[0].user, "U2");
+        assert_eq!(board.reactors[0].count, 3);
+    }
+
+    #[test]
+    fn test_tally_reactions_truncates_to_top() {
+        let messages = vec![message_with_reactions(
+            r#"[{"name": "a", "count": 1}, {"name": "b", "count": 3}, {"name": "c", "count": 2}]"#,
+        )];
+
+        let board = tally_reactions(&messages, 2, false);
+        let names: Vec<&str> = board.reactions.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["b", "c"]);
+        assert!(board.reactors.is_empty());
+    }
+
+    #[test]
+    fn test_tally_reactions_normalizes_skin_tones() {
+        let messages = vec![message_with_reactions(
+            r#"[{"name": "wave", "count": 1}, {"name": "wave::skin-tone-3", "count": 2}, {"name": "tada", "count": 2}]"#,
+        )];
+
+        let exact = tally_reactions(&messages, 10, false);
+        assert_eq!(exact.reactions.len(), 3);
+
+        let board = tally_reactions(&messages, 10, true);
+        let counts: Vec<(&str, u32)> = board.reactions.iter().map(|r| (r.name.as_str(), r.count)).collect();
+        assert_eq!(counts, vec![("wave", 3), ("tada", 2)]);
+    }
+
+    #[test]
+    fn test_format_reaction_leaderboard() {
+        let board = tally_reactions(&[message_with_reactions(r#"[{"name": "tada", "count": 2}]"#)], 10, false);
+        let mut writer = ColorWriter::new(true);
+        format_reaction_leaderboard(&board, "general", &HashMap::new(), &mut writer).unwrap();
+
+        let output = writer.into_string().unwrap();
+        assert!(output.contains(":tada:"));
+    }
+}
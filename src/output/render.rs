@@ -0,0 +1,80 @@
+//! Display settings shared by every human-format formatter.
+
+use crate::output::color::IdFormat;
+use crate::output::glyphs::{self, Glyphs};
+use crate::output::message_formatter::ReactionDisplay;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// How human output is rendered, built once from the CLI (`Cli::render_options`)
+/// and carried by each `ColorWriter` into the formatters
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Truncate message bodies (`--max-text-length`)
+    pub max_text_length: Option<usize>,
+    /// Render skin-toned emoji as their base emoji (`--normalize-emoji-skin-tones`)
+    pub normalize_skin_tones: bool,
+    /// Tidy whitespace in message bodies before wrapping (`--trim`)
+    pub trim: bool,
+    /// Show message times as Slack's raw `ts` instead of a local date (`--raw-ts`)
+    pub raw_ts: bool,
+    /// Plain-ASCII glyphs instead of emoji and box drawing (`--ascii`)
+    pub ascii: bool,
+    /// How reactions are shown under messages (`--reactions`)
+    pub reaction_display: ReactionDisplay,
+    /// Whether IDs are shown next to names (`--id-format`)
+    pub id_format: IdFormat,
+    /// Workspace subdomain for archive links (`--team-domain`, else from auth.test)
+    pub team_domain: Option<String>,
+    /// Chat-log layout for message lists (`--format transcript`)
+    pub transcript: bool,
+    /// Display strings for custom emoji shortcodes (`--emoji-map-file`)
+    pub emoji_map: Arc<HashMap<String, String>>,
+}
+
+impl RenderOptions {
+    /// Glyph table matching `ascii`
+    pub fn glyphs(&self) -> &'static Glyphs {
+        if self.ascii {
+            &glyphs::ASCII
+        } else {
+            &glyphs::UNICODE
+        }
+    }
+
+    /// Archive URL for a message: `https://<domain>.slack.com/archives/C123/p1700000000000100`,
+    /// or the bare slack.com host (which redirects) when the domain isn't known
+    pub fn archive_url(&self, channel_id: &str, ts: &str) -> String {
+        let host = match &self.team_domain {
+            Some(domain) => format!("{}.slack.com", domain),
+            None => "slack.com".to_string(),
+        };
+        format!("https://{}/archives/{}/p{}", host, channel_id, ts.replace('.', ""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_render_options() {
+        let options = RenderOptions::default();
+        assert_eq!(options.max_text_length, None);
+        assert_eq!(options.reaction_display, ReactionDisplay::Full);
+        assert_eq!(options.id_format, IdFormat::Show);
+        assert_eq!(options.glyphs(), &glyphs::UNICODE);
+        assert_eq!(RenderOptions { ascii: true, ..Default::default() }.glyphs(), &glyphs::ASCII);
+    }
+
+    #[test]
+    fn test_archive_url_uses_team_domain() {
+        let options = RenderOptions::default();
+        assert_eq!(options.archive_url("C1", "1700000000.000100"), "https://slack.com/archives/C1/p1700000000000100");
+        let options = RenderOptions { team_domain: Some("acme".to_string()), ..Default::default() };
+        assert_eq!(
+            options.archive_url("C1", "1700000000.000100"),
+            "https://acme.slack.com/archives/C1/p1700000000000100"
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::models::message::Message;
+use crate::output::color::ColorWriter;
+use serde::Serialize;
+use std::io::Result;
+use termcolor::Color;
+
+/// Time from each thread root to its first reply (`conversations history --author-stats`)
+#[derive(Debug, Serialize)]
+pub struct ResponseTimes {
+    /// Thread roots with at least one reply
+    pub threads: usize,
+    pub mean_secs: Option<f64>,
+    pub median_secs: Option<f64>,
+    pub p90_secs: Option<f64>,
+}
+
+fn ts_secs(ts: &str) -> f64 {
+    ts.parse().unwrap_or(0.0)
+}
+
+/// Seconds between a thread's root and its earliest reply, `None` without replies
+pub fn first_reply_delay(thread: &[Message]) -> Option<f64> {
+    let root = thread.iter().find(|m| m.thread_ts.as_deref().is_none_or(|ts| ts == m.ts))?;
+    let root_ts = ts_secs(&root.ts);
+    thread
+        .iter()
+        .map(|m| ts_secs(&m.ts))
+        .filter(|&ts| ts > root_ts)
+        .min_by(f64::total_cmp)
+        .map(|ts| ts - root_ts)
+}
+
+/// Mean, median and 90th percentile (nearest rank) of the reply delays
+pub fn response_times(mut delays: Vec<f64>) -> ResponseTimes {
+    delays.sort_by(f64::total_cmp);
+    let n = delays.len();
+    if n == 0 {
+        return ResponseTimes {
+            threads: 0,
+            mean_secs: None,
+            median_secs: None,
+            p90_secs: None,
+        };
+    }
+
+    let median = if n.is_multiple_of(2) {
+        (delays[n / 2 - 1] + delays[n / 2]) / 2.0
+    } else {
+        delays[n / 2]
+    };
+    let p90_rank = (n as f64 * 0.9).ceil() as usize;
+
+    ResponseTimes {
+        threads: n,
+        mean_secs: Some(delays.iter().sum::<f64>() / n as f64),
+        median_secs: Some(median),
+        p90_secs: Some(delays[p90_rank.saturating_sub(1)]),
+    }
+}
+
+/// "45s", "3m 20s", "1h 05m", "2d 03h"
+fn format_delay(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        3600..=86399 => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d {:02}h", secs / 86400, secs % 86400 / 3600),
+    }
+}
+
+pub fn format_response_times(times: &ResponseTimes, channel_name: &str, writer: &mut ColorWriter) -> Result<()> {
+    writer.print_header(&format!("Time to first reply in #{}", channel_name))?;
+    writer.print_separator()?;
+
+    let (Some(mean), Some(median), Some(p90)) = (times.mean_secs, times.median_secs, times.p90_secs) else {
+        writer.write("No threads with replies found")?;
+        writer.writeln()?;
+        return Ok(());
+    };
+
+    writer.print_colored("Threads: ", Color::Blue)?;
+    writer.write(&times.threads.to_string())?;
+    writer.writeln()?;
+    for (label, secs) in [("Mean: ", mean), ("Median: ", median), ("p90: ", p90)] {
+        writer.print_colored(label, Color::Blue)?;
+        writer.print_colored(&format_delay(secs), Color::Yellow)?;
+        writer.writeln()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(ts: &str, thread_ts: Option<&str>) -> Message {
+        serde_json::from_value(serde_json::json!({"ts": ts, "thread_ts": thread_ts, "text": "hi"})).unwrap()
+    }
+
+    #[test]
+    fn test_first_reply_delay() {
+        let thread = vec![
+            message("1700000000.000100", Some("1700000000.000100")),
+            message("1700000300.000100", Some("1700000000.000100")),
+            message("1700000090.000100", Some("1700000000.000100")),
+        ];
+        assert_eq!(first_reply_delay(&thread), Some(90.0));
+        assert_eq!(first_reply_delay(&thread[..1]), None);
+    }
+
+    #[test]
+    fn test_response_times_and_format() {
+        let times = response_times(vec![600.0, 60.0, 7200.0, 120.0]);
+        assert_eq!(times.threads, 4);
+        assert_eq!(times.mean_secs, Some(1995.0));
+        assert_eq!(times.median_secs, Some(360.0));
+        assert_eq!(times.p90_secs, Some(7200.0));
+
+        let mut writer = ColorWriter::new(true);
+        format_response_times(&times, "support", &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("Threads: 4\nMean: 33m 15s\nMedian: 6m 00s\np90: 2h 00m\n"));
+
+        let mut writer = ColorWriter::new(true);
+        format_response_times(&response_times(Vec::new()), "support", &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().contains("No threads with replies found"));
+    }
+
+    #[test]
+    fn test_format_delay() {
+        assert_eq!(format_delay(45.2), "45s");
+        assert_eq!(format_delay(200.0), "3m 20s");
+        assert_eq!(format_delay(3900.0), "1h 05m");
+        assert_eq!(format_delay(183600.0), "2d 03h");
+    }
+}
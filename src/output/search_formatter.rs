@@ -1,6 +1,8 @@
 use crate::models::channel::Channel;
 use crate::models::message::Message;
-use crate::models::search::{FileResult, SearchAllResponse, SearchFilesResponse, SearchMessagesResponse, SearchPagination};
+use crate::models::search::{
+    FileResult, SearchAllResponse, SearchFilesResponse, SearchHit, SearchMessagesResponse, SearchPagination,
+};
 use crate::models::user::User;
 use crate::output::color::ColorWriter;
 use chrono::{DateTime, Local};
@@ -14,7 +16,7 @@ fn format_pagination(pagination: &SearchPagination, writer: &mut ColorWriter) ->
     writer.writeln()?;
     writer.print_colored(
         &format!(
-            "Page {} of {} ({}-{} of {} results)",
+            "Showing page {} of {} ({}-{} of {} results)",
             pagination.page,
             pagination.page_count,
             pagination.first,
@@ -23,6 +25,9 @@ fn format_pagination(pagination: &SearchPagination, writer: &mut ColorWriter) ->
         ),
         Color::White,
     )?;
+    if pagination.has_more() {
+        writer.print_colored(&format!(" - use --page {} for more", pagination.page + 1), Color::Cyan)?;
+    }
     writer.writeln()?;
     Ok(())
 }
@@ -40,6 +45,11 @@ pub fn format_search_messages(
     ))?;
     writer.print_separator()?;
 
+    // The page can be empty even when the total isn't (e.g. past the last page)
+    if response.messages.matches.is_empty() {
+        writer.print_empty("messages")?;
+    }
+
     for (i, msg) in response.messages.matches.iter().enumerate() {
         format_search_message(msg, users, writer)?;
 
@@ -71,7 +81,10 @@ pub fn format_search_message(
     let duration = now.signed_duration_since(dt_local);
 
     // Format timestamp based on age (same logic as message_formatter)
-    let time_str = if duration.num_hours() < 24 {
+    let time_str = if writer.options().raw_ts {
+        // --raw-ts: Slack's own ts, for correlating with other API data
+        msg.ts.clone()
+    } else if duration.num_hours() < 24 {
         if duration.num_minutes() < 1 {
             "just now".to_string()
         } else if duration.num_minutes() < 60 {
@@ -121,7 +134,8 @@ pub fn format_search_message(
 
     // Message text wrapped dynamically
     let wrap_width = crate::output::width::get_wrap_width();
-    let wrapped = wrap(&msg.text, wrap_width);
+    let text = writer.message_text(&msg.text);
+    let wrapped = wrap(&text, wrap_width);
     for line in wrapped {
         writer.write("  ")?;
         writer.write(&line)?;
@@ -130,7 +144,7 @@ pub fn format_search_message(
 
     // Permalink if available
     if let Some(permalink) = &msg.permalink {
-        writer.write("  🔗 ")?;
+        writer.write(&format!("  {} ", writer.glyphs().link))?;
         writer.write(permalink)?;
         writer.writeln()?;
     }
@@ -149,12 +163,12 @@ pub fn format_search_files(
         response.query
     ))?;
 
+    writer.print_separator()?;
+
     if response.files.matches.is_empty() {
-        return Ok(());
+        return writer.print_empty("files");
     }
 
-    writer.print_separator()?;
-
     for (i, file) in response.files.matches.iter().enumerate() {
         format_file(file, writer)?;
 
@@ -240,8 +254,56 @@ pub fn format_search_all(
 
     if response.messages.total == 0 && response.files.total == 0 {
         writer.writeln()?;
-        writer.write("No results found.")?;
-        writer.writeln()?;
+        writer.print_empty("results")?;
+    }
+
+    Ok(())
+}
+
+/// Format search.all results as a single timeline, newest first, labeling each row's type
+pub fn format_search_all_interleaved(
+    response: &SearchAllResponse,
+    users: &HashMap<String, User>,
+    writer: &mut ColorWriter,
+) -> Result<()> {
+    writer.print_header(&format!("Search results for '{}'", response.query))?;
+    writer.print_separator()?;
+
+    let timeline = response.timeline();
+    for (i, hit) in timeline.iter().enumerate() {
+        match hit {
+            SearchHit::Message(msg) => {
+                writer.print_colored("[message] ", Color::Yellow)?;
+                format_search_message(msg, users, writer)?;
+            }
+            SearchHit::File(file) => {
+                writer.print_colored("[file] ", Color::Yellow)?;
+                format_file(file, writer)?;
+            }
+        }
+
+        if i < timeline.len() - 1 {
+            writer.writeln()?;
+        }
+    }
+
+    if timeline.is_empty() {
+        return writer.print_empty("results");
+    }
+
+    // Each half of search.all is paged separately
+    writer.writeln()?;
+    for (label, pagination) in [("Messages", &response.messages.pagination), ("Files", &response.files.pagination)] {
+        if let Some(pagination) = pagination {
+            writer.print_colored(
+                &format!(
+                    "{}: page {} of {} ({} results)",
+                    label, pagination.page, pagination.page_count, pagination.total_count
+                ),
+                Color::White,
+            )?;
+            writer.writeln()?;
+        }
     }
 
     Ok(())
@@ -282,7 +344,7 @@ fn format_file(file: &FileResult, writer: &mut ColorWriter) -> Result<()> {
 
     // Permalink
     if let Some(permalink) = &file.permalink {
-        writer.write("  🔗 ")?;
+        writer.write(&format!("  {} ", writer.glyphs().link))?;
         writer.write(permalink)?;
         writer.writeln()?;
     }
@@ -295,17 +357,27 @@ pub fn format_channel_search_results(
     channels: &[Channel],
     writer: &mut ColorWriter,
 ) -> Result<()> {
+    if channels.is_empty() {
+        writer.print_header(&format!("Found 0 channels matching '{}'", query))?;
+        writer.print_separator()?;
+        return writer.print_empty("channels");
+    }
+
+    // Breakdown by visibility; archived only shows up with --include-archived
+    let private = channels.iter().filter(|c| c.is_private.unwrap_or(false)).count();
+    let archived = channels.iter().filter(|c| c.is_archived.unwrap_or(false)).count();
+    let mut breakdown = format!("{} public, {} private", channels.len() - private, private);
+    if archived > 0 {
+        breakdown.push_str(&format!(", {} archived", archived));
+    }
     writer.print_header(&format!(
-        "Found {} channel{} matching '{}'",
+        "Found {} channel{} matching '{}' ({})",
         channels.len(),
         if channels.len() == 1 { "" } else { "s" },
-        query
+        query,
+        breakdown
     ))?;
 
-    if channels.is_empty() {
-        return Ok(());
-    }
-
     writer.print_separator()?;
 
     for (i, channel) in channels.iter().enumerate() {
@@ -318,12 +390,12 @@ pub fn format_channel_search_results(
 
         // Privacy indicator
         if channel.is_private.unwrap_or(false) {
-            writer.write(" 🔒")?;
+            writer.write(&format!(" {}", writer.glyphs().private))?;
         }
 
         // Archived indicator
         if channel.is_archived.unwrap_or(false) {
-            writer.write(" 📦")?;
+            writer.write(&format!(" {}", writer.glyphs().archived))?;
         }
         writer.writeln()?;
 
@@ -358,3 +430,147 @@ pub fn format_channel_search_results(
 
     Ok(())
 }
+
+/// Search response as json with each match's resolved author and channel embedded as
+/// `resolved_user` and `resolved_channel` next to the original IDs (`search messages --enrich`).
+/// Matches whose user or channel couldn't be resolved get `null`.
+pub fn enrich_search_messages(
+    response: &SearchMessagesResponse,
+    users: &HashMap<String, User>,
+    channels: &HashMap<String, Channel>,
+) -> serde_json::Result<serde_json::Value> {
+    let mut value = serde_json::to_value(response)?;
+    let Some(matches) = value["messages"]["matches"].as_array_mut() else {
+        return Ok(value);
+    };
+
+    for (entry, msg) in matches.iter_mut().zip(&response.messages.matches) {
+        let user = msg.user.as_ref().and_then(|id| users.get(id));
+        let channel = msg.channel.as_ref().and_then(|c| channels.get(c.id()));
+        entry["resolved_user"] = serde_json::to_value(user)?;
+        entry["resolved_channel"] = serde_json::to_value(channel)?;
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages_response(total: u32, pagination: SearchPagination) -> SearchMessagesResponse {
+        serde_json::from_value(serde_json::json!({
+            "ok": true,
+            "query": "deploy",
+            "messages": {
+                "total": total,
+                "matches": [{"ts": "1700000000.000100", "text": "deploy done", "user": "U1"}],
+                "pagination": pagination
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_format_channel_search_results_breakdown() {
+        let channel = |name: &str, private: bool, archived: bool| -> Channel {
+            serde_json::from_value(serde_json::json!({
+                "id": format!("C{}", name), "name": name, "is_private": private, "is_archived": archived
+            }))
+            .unwrap()
+        };
+        let channels = vec![
+            channel("eng-api", false, false),
+            channel("eng-web", false, true),
+            channel("eng-secret", true, false),
+        ];
+
+        let mut writer = ColorWriter::new(true);
+        format_channel_search_results("eng", &channels, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.starts_with("Found 3 channels matching 'eng' (2 public, 1 private, 1 archived)"));
+
+        let mut writer = ColorWriter::new(true);
+        format_channel_search_results("eng", &channels[..1], &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().starts_with("Found 1 channel matching 'eng' (1 public, 0 private)\n"));
+
+        let mut writer = ColorWriter::new(true);
+        format_channel_search_results("nope", &[], &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.starts_with("Found 0 channels matching 'nope'\n"));
+        assert!(output.ends_with("No channels found\n"));
+    }
+
+    #[test]
+    fn test_format_search_messages_next_page_hint() {
+        let response = messages_response(45, SearchPagination::from_total(45, 2, 20));
+        let mut writer = ColorWriter::new(true);
+        format_search_messages(&response, &HashMap::new(), &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("Showing page 2 of 3 (21-40 of 45 results) - use --page 3 for more"));
+    }
+
+    #[test]
+    fn test_format_empty_search_results() {
+        let mut response = messages_response(0, SearchPagination::from_total(0, 1, 20));
+        response.messages.matches.clear();
+        let mut writer = ColorWriter::new(true);
+        format_search_messages(&response, &HashMap::new(), &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().contains("No messages found\n"));
+
+        let files: SearchFilesResponse = serde_json::from_value(serde_json::json!({
+            "ok": true, "query": "report", "files": {"total": 0, "matches": []}
+        }))
+        .unwrap();
+        let mut writer = ColorWriter::new(true);
+        format_search_files(&files, &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().ends_with("No files found\n"));
+    }
+
+    #[test]
+    fn test_enrich_search_messages_embeds_resolved_objects() {
+        let response: SearchMessagesResponse = serde_json::from_value(serde_json::json!({
+            "ok": true,
+            "query": "deploy",
+            "messages": {
+                "total": 2,
+                "matches": [
+                    {"ts": "1.0", "text": "deploy done", "user": "U1", "channel": {"id": "C1", "name": "ops"}},
+                    {"ts": "2.0", "text": "deploy again", "user": "U404", "channel": {"id": "C1", "name": "ops"}}
+                ]
+            }
+        }))
+        .unwrap();
+        let users: HashMap<String, User> = [(
+            "U1".to_string(),
+            serde_json::from_value(serde_json::json!({
+                "id": "U1", "name": "alice", "deleted": false, "is_bot": false, "profile": {}
+            }))
+            .unwrap(),
+        )]
+        .into();
+        let channels: HashMap<String, Channel> = [(
+            "C1".to_string(),
+            serde_json::from_value(serde_json::json!({"id": "C1", "name": "ops"})).unwrap(),
+        )]
+        .into();
+
+        let value = enrich_search_messages(&response, &users, &channels).unwrap();
+        let matches = value["messages"]["matches"].as_array().unwrap();
+        assert_eq!(matches[0]["user"], "U1");
+        assert_eq!(matches[0]["resolved_user"]["name"], "alice");
+        assert_eq!(matches[0]["resolved_channel"]["name"], "ops");
+        assert!(matches[1]["resolved_user"].is_null());
+        assert_eq!(value["messages"]["total"], 2);
+    }
+
+    #[test]
+    fn test_format_search_messages_last_page_has_no_hint() {
+        let response = messages_response(45, SearchPagination::from_total(45, 3, 20));
+        let mut writer = ColorWriter::new(true);
+        format_search_messages(&response, &HashMap::new(), &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("Showing page 3 of 3 (41-45 of 45 results)"));
+        assert!(!output.contains("--page"));
+    }
+}
@@ -0,0 +1,45 @@
+use std::fmt;
+
+/// One-line result summary printed to stderr after command output (--summary)
+#[derive(Debug, Default)]
+pub struct Summary {
+    parts: Vec<String>,
+}
+
+impl Summary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a counted item, e.g. "42 messages" or "1 channel"
+    pub fn count(mut self, count: usize, singular: &str, plural: &str) -> Self {
+        let noun = if count == 1 { singular } else { plural };
+        self.parts.push(format!("{} {}", count, noun));
+        self
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.parts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_pluralization() {
+        let summary = Summary::new()
+            .count(42, "message", "messages")
+            .count(1, "channel", "channels")
+            .count(0, "user resolved", "users resolved");
+        assert_eq!(summary.to_string(), "42 messages, 1 channel, 0 users resolved");
+    }
+
+    #[test]
+    fn test_summary_empty() {
+        assert_eq!(Summary::new().to_string(), "");
+    }
+}
@@ -0,0 +1,148 @@
+use crate::models::user::User;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Truncate message text to at most `max_len` characters, ending in "..." when cut.
+/// `None` leaves the text untouched.
+pub fn truncate(text: &str, max_len: Option<usize>) -> Cow<'_, str> {
+    match max_len {
+        // Too short for the marker: hard cut so the result never exceeds max_len
+        Some(max_len) if max_len < 4 => match text.char_indices().nth(max_len) {
+            Some((end, _)) => Cow::Borrowed(&text[..end]),
+            None => Cow::Borrowed(text),
+        },
+        Some(max_len) if text.chars().count() > max_len => {
+            let kept: String = text.chars().take(max_len.saturating_sub(3)).collect();
+            Cow::Owned(format!("{}...", kept))
+        }
+        _ => Cow::Borrowed(text),
+    }
+}
+
+/// Tidy message whitespace (`--trim`): trim every line, drop leading and trailing
+/// blank lines, and collapse runs of blank lines into one. Fenced code blocks
+/// (```...```) are kept verbatim.
+pub fn trim_whitespace(text: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_code = false;
+    for line in text.lines() {
+        let fence = line.matches("```").count() % 2 == 1;
+        let kept = if in_code && !fence { line } else { line.trim() };
+        // Only one blank line in a row outside code blocks
+        if !in_code && kept.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(kept);
+        if fence {
+            in_code = !in_code;
+        }
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Render Slack's message markup as it reads in the client: `<@U123>` becomes
+/// `@alice` (resolved from `users`, else the label or the ID), `<#C123|general>`
+/// `#general`, `<!here>` `@here`, `<https://x.io|docs>` `docs (https://x.io)`,
+/// and `&lt;`/`&gt;`/`&amp;` are unescaped. Emphasis markers are left alone.
+pub fn render_mrkdwn(text: &str, users: &HashMap<String, User>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let Some(len) = rest[start..].find('>') else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let inner = &rest[start + 1..start + len];
+        let (target, label) = match inner.split_once('|') {
+            Some((target, label)) => (target, Some(label)),
+            None => (inner, None),
+        };
+        match target.chars().next() {
+            Some('@') => {
+                let user_id = &target[1..];
+                match (users.get(user_id), label) {
+                    (Some(user), _) => out.push_str(&format!("@{}", user.name)),
+                    (None, Some(label)) => out.push_str(&format!("@{}", label.trim_start_matches('@'))),
+                    (None, None) => out.push_str(&format!("@{}", user_id)),
+                }
+            }
+            Some('#') => out.push_str(&format!("#{}", label.unwrap_or(&target[1..]))),
+            // Special mentions (<!here>) and user groups / dates (<!subteam^S1|@team>)
+            Some('!') => match label {
+                Some(label) => out.push_str(label),
+                None => out.push_str(&format!("@{}", &target[1..])),
+            },
+            _ => match label {
+                Some(label) if label != target.trim_start_matches("mailto:") => {
+                    out.push_str(&format!("{} ({})", label, target))
+                }
+                Some(label) => out.push_str(label),
+                None => out.push_str(target),
+            },
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    out.replace("&lt;", "<").replace("&gt;", ">").replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_long_text() {
+        assert_eq!(truncate("hello world", Some(8)), "hello...");
+        assert_eq!(truncate("hello world", Some(8)).chars().count(), 8);
+    }
+
+    #[test]
+    fn test_truncate_never_exceeds_tiny_limits() {
+        assert_eq!(truncate("hello", Some(2)), "he");
+        assert_eq!(truncate("hello", Some(0)), "");
+        assert_eq!(truncate("hi", Some(3)), "hi");
+    }
+
+    #[test]
+    fn test_truncate_short_text_and_no_limit() {
+        assert_eq!(truncate("hello", Some(8)), "hello");
+        assert_eq!(truncate("hello world", None), "hello world");
+    }
+
+    #[test]
+    fn test_truncate_counts_characters_not_bytes() {
+        assert_eq!(truncate("héllo wörld", Some(8)), "héllo...");
+    }
+
+    #[test]
+    fn test_trim_whitespace() {
+        assert_eq!(trim_whitespace("\n\n  hello  \n\n\n\t world\n\n"), "hello\n\nworld");
+        assert_eq!(trim_whitespace("one\ntwo"), "one\ntwo");
+    }
+
+    #[test]
+    fn test_trim_whitespace_keeps_code_blocks() {
+        let text = "  see:  \n```\nfn main() {\n\n\n    run();\n}\n```\n\n\n  done ";
+        assert_eq!(trim_whitespace(text), "see:\n```\nfn main() {\n\n\n    run();\n}\n```\n\ndone");
+        // Inline fences open and close on the same line
+        assert_eq!(trim_whitespace("  use ```x```  \n\n\n  ok"), "use ```x```\n\nok");
+    }
+
+    #[test]
+    fn test_render_mrkdwn() {
+        let users: HashMap<String, User> = HashMap::from([(
+            "U1".to_string(),
+            serde_json::from_value(serde_json::json!({"id": "U1", "name": "alice", "deleted": false, "is_bot": false, "profile": {}})).unwrap(),
+        )]);
+        assert_eq!(render_mrkdwn("hi <@U1>, ask <@U2|bob> or <@U3>", &users), "hi @alice, ask @bob or @U3");
+        assert_eq!(render_mrkdwn("see <#C1|general> <!here> <!subteam^S1|@oncall>", &users), "see #general @here @oncall");
+        assert_eq!(
+            render_mrkdwn("<https://x.io|docs>, <https://y.io> and <mailto:a@b.co|a@b.co>", &users),
+            "docs (https://x.io), https://y.io and a@b.co"
+        );
+        assert_eq!(render_mrkdwn("a &lt;b&gt; &amp; c < d", &users), "a <b> & c < d");
+    }
+}
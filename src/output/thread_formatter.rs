@@ -2,8 +2,10 @@ use crate::models::channel::Channel;
 use crate::models::message::Message;
 use crate::models::user::User;
 use crate::output::color::ColorWriter;
+use crate::output::render::RenderOptions;
 use chrono::{DateTime, Local};
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Result;
 use termcolor::Color;
 use textwrap::wrap;
@@ -30,23 +32,31 @@ pub fn format_thread(
         messages.len()
     ))?;
 
-    // Calculate participants
-    let mut participant_ids = std::collections::HashSet::new();
-    for msg in messages {
-        if let Some(user_id) = &msg.user {
-            participant_ids.insert(user_id);
-        }
-    }
+    // Who was involved, most active first
+    let participants = thread_participants(messages);
+    if !participants.is_empty() {
+        writer.print_colored(&format!("Participants ({})", participants.len()), Color::Blue)?;
+        writer.writeln()?;
 
-    // Show participants
-    if !participant_ids.is_empty() {
-        writer.print_field("Participants", &{
-            let names: Vec<String> = participant_ids
-                .iter()
-                .filter_map(|id| users.get(*id).map(|u| format!("@{}", u.name)))
-                .collect();
-            names.join(", ")
-        })?;
+        let labels: Vec<String> = participants
+            .iter()
+            .map(|(user_id, _)| match users.get(*user_id) {
+                Some(user) => match &user.profile.email {
+                    Some(email) => format!("@{} <{}>", user.name, email),
+                    None => format!("@{}", user.name),
+                },
+                None => user_id.to_string(),
+            })
+            .collect();
+        let width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+        for (label, (_, count)) in labels.iter().zip(&participants) {
+            writer.write("  ")?;
+            writer.print_colored(&format!("{:<width$}", label, width = width), Color::Cyan)?;
+            let noun = if *count == 1 { "message" } else { "messages" };
+            writer.write(&format!("  {} {}", count, noun))?;
+            writer.writeln()?;
+        }
     }
 
     writer.print_separator()?;
@@ -79,17 +89,119 @@ pub fn format_thread(
     // Thread URL
     writer.writeln()?;
     writer.print_separator()?;
-    let thread_ts_clean = thread_ts.replace('.', "");
-    writer.write("🔗 Thread URL: ")?;
-    writer.write(&format!(
-        "https://slack.com/archives/{}/p{}",
-        channel.id, thread_ts_clean
-    ))?;
+    writer.write(&format!("{} ", writer.glyphs().labelled(writer.glyphs().link, "Thread URL:")))?;
+    writer.write(&writer.options().archive_url(&channel.id, thread_ts))?;
     writer.writeln()?;
 
     Ok(())
 }
 
+/// A thread saved by `conversations replies --export` as JSON or YAML: the messages as
+/// returned by Slack plus what's needed to read them offline
+#[derive(Debug, Serialize)]
+pub struct ThreadTranscript<'a> {
+    pub channel_id: &'a str,
+    pub channel_name: &'a str,
+    pub permalink: Option<String>,
+    /// `@name` of each resolved author, by user ID
+    pub users: BTreeMap<&'a str, String>,
+    pub messages: &'a [Message],
+}
+
+pub fn thread_transcript<'a>(
+    messages: &'a [Message],
+    channel: &'a Channel,
+    users: &'a HashMap<String, User>,
+    options: &RenderOptions,
+) -> ThreadTranscript<'a> {
+    ThreadTranscript {
+        channel_id: &channel.id,
+        channel_name: &channel.name,
+        permalink: messages
+            .first()
+            .map(|root| crate::output::message_formatter::message_url(root, &channel.id, options)),
+        users: messages
+            .iter()
+            .filter_map(|m| m.user.as_deref())
+            .filter_map(|id| users.get(id).map(|u| (id, format!("@{}", u.name))))
+            .collect(),
+        messages,
+    }
+}
+
+/// Render a thread (root first) as a standalone Markdown document, for
+/// `export threads` and `conversations replies --export`. Times are UTC so archives don't depend on the exporter's zone.
+pub fn format_thread_markdown(
+    messages: &[Message],
+    channel: &Channel,
+    users: &HashMap<String, User>,
+    options: &RenderOptions,
+) -> String {
+    let author = |msg: &Message| match msg.user.as_deref() {
+        Some(user_id) => users
+            .get(user_id)
+            .map(|u| format!("@{}", u.name))
+            .unwrap_or_else(|| user_id.to_string()),
+        None => "<system>".to_string(),
+    };
+    let utc_time = |msg: &Message| {
+        let ts_float: f64 = msg.ts.parse().unwrap_or(0.0);
+        DateTime::from_timestamp(ts_float as i64, 0)
+            .unwrap_or_default()
+            .format("%Y-%m-%d %H:%M:%S UTC")
+            .to_string()
+    };
+
+    let Some(root) = messages.first() else {
+        return String::new();
+    };
+
+    let mut out = format!("# Thread in #{}\n\n", channel.name);
+    out.push_str(&format!("- Started: {} by {}\n", utc_time(root), author(root)));
+    out.push_str(&format!("- Replies: {}\n", messages.len() - 1));
+    let participants: Vec<String> = thread_participants(messages)
+        .iter()
+        .map(|(user_id, count)| {
+            let name = users.get(*user_id).map(|u| format!("@{}", u.name)).unwrap_or_else(|| user_id.to_string());
+            format!("{} ({})", name, count)
+        })
+        .collect();
+    if !participants.is_empty() {
+        out.push_str(&format!("- Participants: {}\n", participants.join(", ")));
+    }
+    out.push_str(&format!(
+        "- Link: {}\n",
+        crate::output::message_formatter::message_url(root, &channel.id, options)
+    ));
+
+    for (i, msg) in messages.iter().enumerate() {
+        out.push_str(if i == 0 { "\n## Root message\n\n" } else { "\n---\n\n" });
+        out.push_str(&format!("**{}** · {}\n\n", author(msg), utc_time(msg)));
+        out.push_str(msg.text.trim_end());
+        out.push('\n');
+        if i == 0 && messages.len() > 1 {
+            out.push_str("\n## Replies\n");
+        }
+    }
+
+    out
+}
+
+/// Count messages per author, ordered by count then first appearance
+fn thread_participants(messages: &[Message]) -> Vec<(&str, usize)> {
+    let mut participants: Vec<(&str, usize)> = Vec::new();
+    for user_id in messages.iter().filter_map(|m| m.user.as_deref()) {
+        match participants.iter_mut().find(|(id, _)| *id == user_id) {
+            Some((_, count)) => *count += 1,
+            None => participants.push((user_id, 1)),
+        }
+    }
+
+    // Stable sort keeps first-appearance order among equal counts
+    participants.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    participants
+}
+
 fn format_message(
     msg: &Message,
     channel_name: &str,
@@ -111,7 +223,10 @@ fn format_message(
     let duration = now.signed_duration_since(dt_local);
 
     // Format timestamp based on age
-    let time_str = if duration.num_hours() < 24 {
+    let time_str = if writer.options().raw_ts {
+        // --raw-ts: Slack's own ts, for correlating with other API data
+        msg.ts.clone()
+    } else if duration.num_hours() < 24 {
         // Less than 1 day old - use "N units ago"
         if duration.num_minutes() < 1 {
             "just now".to_string()
@@ -158,39 +273,28 @@ fn format_message(
     writer.writeln()?;
 
     // Message text wrapped dynamically to terminal width (accounting for indent)
-    let base_width = crate::output::width::get_wrap_width();
     let indent_size = if is_reply { 4 } else { 2 }; // 2 spaces for root, 4 for replies
-    let wrap_width = base_width.saturating_sub(indent_size);
+    let wrap_width = crate::output::width::get_wrap_width_with_indent(indent_size);
     let text_indent = format!("{}  ", indent);
-    let wrapped = wrap(&msg.text, wrap_width);
+    let text = writer.message_text(&msg.text);
+    let wrapped = wrap(&text, wrap_width);
     for line in wrapped {
         writer.write(&text_indent)?;
         writer.write(&line)?;
         writer.writeln()?;
     }
 
-    // Reactions if present
-    if let Some(reactions) = &msg.reactions {
-        if !reactions.is_empty() {
-            writer.write(&text_indent)?;
-            for (i, reaction) in reactions.iter().enumerate() {
-                if i > 0 {
-                    writer.write(" ")?;
-                }
-                writer.write(&format!(":{}:{}", reaction.name, reaction.count))?;
-            }
-            writer.writeln()?;
-        }
+    // Reactions if present, as --reactions asks
+    if let Some(line) = crate::output::message_formatter::reaction_line(msg, writer) {
+        writer.write(&text_indent)?;
+        writer.write(&line)?;
+        writer.writeln()?;
     }
 
     // Message URL
-    let msg_ts = msg.ts.replace('.', "");
     writer.write(&text_indent)?;
-    writer.write("🔗 ")?;
-    writer.write(&format!(
-        "https://slack.com/archives/{}/p{}",
-        channel_id, msg_ts
-    ))?;
+    writer.write(&format!("{} ", writer.glyphs().link))?;
+    writer.write(&crate::output::message_formatter::message_url(msg, channel_id, writer.options()))?;
     writer.writeln()?;
 
     Ok(())
@@ -213,13 +317,19 @@ mod tests {
             is_mpim: None,
             is_private: Some(false),
             is_archived: Some(false),
+            is_member: None,
             topic: Some(ChannelTopic {
                 value: "General discussions".to_string(),
+                creator: None,
+                last_set: None,
             }),
             purpose: Some(ChannelPurpose {
                 value: "Company-wide communication".to_string(),
+                creator: None,
+                last_set: None,
             }),
             num_members: Some(42),
+            user: None,
         }
     }
 
@@ -240,6 +350,8 @@ mod tests {
             is_admin: None,
             is_owner: None,
             tz: None,
+            presence: None,
+            enterprise_user: None,
         }
     }
 
@@ -252,6 +364,10 @@ mod tests {
             reactions: None,
             channel: None,
             permalink: None,
+            bot_id: None,
+            subtype: None,
+            edited: None,
+            reply_count: None,
         }
     }
 
@@ -276,6 +392,70 @@ mod tests {
         // Test passes if no panic
     }
 
+    #[test]
+    fn test_format_thread_participants_header() {
+        let channel = create_test_channel();
+        let mut users = HashMap::new();
+        users.insert("U123".to_string(), create_test_user("U123", "alice"));
+
+        let messages = vec![
+            create_test_message("1234567890.123456", Some("U456"), "Root message", Some("1234567890.123456")),
+            create_test_message("1234567891.123456", Some("U123"), "Reply 1", Some("1234567890.123456")),
+            create_test_message("1234567892.123456", Some("U123"), "Reply 2", Some("1234567890.123456")),
+        ];
+
+        assert_eq!(thread_participants(&messages), vec![("U123", 2), ("U456", 1)]);
+
+        let mut writer = ColorWriter::new(true);
+        format_thread(&messages, &channel, &users, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+
+        assert!(output.contains("Participants (2)"));
+        assert!(output.contains("@alice <alice@example.com>  2 messages"));
+        // Unresolved users fall back to their ID
+        assert!(output.contains("U456"));
+        assert!(output.contains("1 message\n"));
+    }
+
+    #[test]
+    fn test_format_thread_markdown() {
+        let channel = create_test_channel();
+        let mut users = HashMap::new();
+        users.insert("U123".to_string(), create_test_user("U123", "alice"));
+
+        let messages = vec![
+            create_test_message("1700000000.000100", Some("U123"), "Root message", Some("1700000000.000100")),
+            create_test_message("1700000060.000100", Some("U456"), "Reply 1", Some("1700000000.000100")),
+        ];
+
+        let markdown = format_thread_markdown(&messages, &channel, &users, &RenderOptions::default());
+        assert!(markdown.starts_with("# Thread in #general\n"));
+        assert!(markdown.contains("- Started: 2023-11-14 22:13:20 UTC by @alice\n"));
+        assert!(markdown.contains("- Participants: @alice (1), U456 (1)\n"));
+        assert!(markdown.contains("- Link: https://slack.com/archives/C123/p1700000000000100\n"));
+        assert!(markdown.contains("## Root message\n\n**@alice** · 2023-11-14 22:13:20 UTC\n\nRoot message\n"));
+        assert!(markdown.contains("## Replies\n\n---\n\n**U456** · 2023-11-14 22:14:20 UTC\n\nReply 1\n"));
+        assert!(format_thread_markdown(&[], &channel, &users, &RenderOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_thread_transcript() {
+        let channel = create_test_channel();
+        let mut users = HashMap::new();
+        users.insert("U123".to_string(), create_test_user("U123", "alice"));
+        let messages = vec![
+            create_test_message("1700000000.000100", Some("U123"), "Root message", Some("1700000000.000100")),
+            create_test_message("1700000060.000100", Some("U456"), "Reply 1", Some("1700000000.000100")),
+        ];
+
+        let json = serde_json::to_value(thread_transcript(&messages, &channel, &users, &RenderOptions::default())).unwrap();
+        assert_eq!(json["channel_name"], "general");
+        assert_eq!(json["permalink"], "https://slack.com/archives/C123/p1700000000000100");
+        // Only resolved authors are listed; the messages keep their raw user IDs
+        assert_eq!(json["users"], serde_json::json!({"U123": "@alice"}));
+        assert_eq!(json["messages"][1]["user"], "U456");
+    }
+
     #[test]
     fn test_format_thread_with_only_root() {
         let channel = create_test_channel();
@@ -330,6 +510,7 @@ mod tests {
             Reaction {
                 name: "thumbsup".to_string(),
                 count: 5,
+                users: None,
             },
         ]);
 
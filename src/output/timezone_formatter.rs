@@ -0,0 +1,143 @@
+use crate::models::message::Message;
+use crate::models::user::User;
+use crate::output::color::ColorWriter;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::io::Result;
+use termcolor::Color;
+
+/// Label for authors whose timezone is not known (lookup failed or no `tz`)
+pub const UNKNOWN_TZ: &str = "unknown";
+
+#[derive(Debug, Serialize)]
+pub struct TimezoneCount {
+    pub tz: String,
+    pub messages: usize,
+    pub authors: usize,
+}
+
+/// Message counts grouped by the authors' timezones
+#[derive(Debug, Serialize)]
+pub struct TimezoneSummary {
+    pub messages_counted: usize,
+    pub timezones: Vec<TimezoneCount>,
+}
+
+/// Group messages with a user author by that user's `tz`, busiest first.
+/// Messages without a user (bots, integrations) are not counted.
+pub fn tally_timezones(messages: &[Message], users: &HashMap<String, User>) -> TimezoneSummary {
+    let mut counts: HashMap<&str, (usize, HashSet<&str>)> = HashMap::new();
+    let mut messages_counted = 0;
+
+    for msg in messages {
+        let Some(user_id) = msg.user.as_deref() else {
+            continue;
+        };
+        let tz = users
+            .get(user_id)
+            .and_then(|u| u.tz.as_deref())
+            .unwrap_or(UNKNOWN_TZ);
+        let entry = counts.entry(tz).or_default();
+        entry.0 += 1;
+        entry.1.insert(user_id);
+        messages_counted += 1;
+    }
+
+    let mut timezones: Vec<TimezoneCount> = counts
+        .into_iter()
+        .map(|(tz, (messages, authors))| TimezoneCount {
+            tz: tz.to_string(),
+            messages,
+            authors: authors.len(),
+        })
+        .collect();
+    // Sort by count descending, then name so ties are stable
+    timezones.sort_by(|a, b| b.messages.cmp(&a.messages).then_with(|| a.tz.cmp(&b.tz)));
+
+    TimezoneSummary {
+        messages_counted,
+        timezones,
+    }
+}
+
+pub fn format_timezone_summary(summary: &TimezoneSummary, channel_name: &str, writer: &mut ColorWriter) -> Result<()> {
+    writer.print_header(&format!(
+        "Author timezones in #{} ({} messages)",
+        channel_name, summary.messages_counted
+    ))?;
+    writer.print_separator()?;
+
+    if summary.timezones.is_empty() {
+        writer.write("No messages from users found")?;
+        writer.writeln()?;
+        return Ok(());
+    }
+
+    let tz_width = summary.timezones.iter().map(|t| t.tz.len()).max().unwrap_or(0);
+    for entry in &summary.timezones {
+        writer.print_colored(&format!("{:<width$}", entry.tz, width = tz_width), Color::Cyan)?;
+        writer.write(&format!(
+            "  {:>5}  ({} author{})",
+            entry.messages,
+            entry.authors,
+            if entry.authors == 1 { "" } else { "s" }
+        ))?;
+        writer.writeln()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message_from(user: Option<&str>) -> Message {
+        serde_json::from_value(serde_json::json!({"ts": "1234567890.123456", "user": user, "text": "hi"})).unwrap()
+    }
+
+    fn user_in(id: &str, tz: Option<&str>) -> User {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "name": id, "deleted": false, "is_bot": false, "tz": tz, "profile": {}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tally_timezones_groups_by_author_tz() {
+        let messages = vec![
+            message_from(Some("U1")),
+            message_from(Some("U2")),
+            message_from(Some("U1")),
+            message_from(Some("U3")),
+            message_from(Some("U404")),
+            message_from(None),
+        ];
+        let users: HashMap<String, User> = [
+            user_in("U1", Some("Europe/Berlin")),
+            user_in("U2", Some("America/New_York")),
+            user_in("U3", Some("Europe/Berlin")),
+        ]
+        .into_iter()
+        .map(|u| (u.id.clone(), u))
+        .collect();
+
+        let summary = tally_timezones(&messages, &users);
+        assert_eq!(summary.messages_counted, 5);
+        let rows: Vec<(&str, usize, usize)> = summary
+            .timezones
+            .iter()
+            .map(|t| (t.tz.as_str(), t.messages, t.authors))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![("Europe/Berlin", 3, 2), ("America/New_York", 1, 1), (UNKNOWN_TZ, 1, 1)]
+        );
+
+        let mut writer = ColorWriter::new(true);
+        format_timezone_summary(&summary, "general", &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("Europe/Berlin"));
+        assert!(output.contains("(2 authors)"));
+    }
+}
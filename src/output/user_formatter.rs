@@ -1,14 +1,257 @@
 use crate::models::user::{User, UserProfile};
 use crate::output::color::ColorWriter;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
 use std::io::Result;
 use termcolor::Color;
 
+/// Roster changes between the cached users and a fresh `users.list` (`users diff`)
+#[derive(Debug, Serialize)]
+pub struct UserChanges {
+    pub added: Vec<User>,
+    /// Deactivated since the snapshot, or no longer listed at all
+    pub removed: Vec<User>,
+    pub changed: Vec<UserChange>,
+}
+
+impl UserChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserChange {
+    pub id: String,
+    pub name: String,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Emoji and text joined, `None` when both are empty
+fn user_status(user: &User) -> Option<String> {
+    let status = [&user.profile.status_emoji, &user.profile.status_text]
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!status.is_empty()).then_some(status)
+}
+
+/// Diff two rosters by user id. `fresh` should include deactivated users so they
+/// show up as removed rather than silently disappearing. Each list is sorted by name.
+pub fn diff_users(cached: Vec<User>, fresh: Vec<User>) -> UserChanges {
+    let mut before: HashMap<String, User> = cached.into_iter().filter(|u| !u.deleted).map(|u| (u.id.clone(), u)).collect();
+    let mut changes = UserChanges {
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+    };
+
+    for user in fresh {
+        match before.remove(&user.id) {
+            None if !user.deleted => changes.added.push(user),
+            None => {}
+            Some(_) if user.deleted => changes.removed.push(user),
+            Some(old) => {
+                let fields = [
+                    ("name", Some(old.name.clone()), Some(user.name.clone())),
+                    ("real_name", old.real_name.clone(), user.real_name.clone()),
+                    ("email", old.profile.email.clone(), user.profile.email.clone()),
+                    ("status", user_status(&old), user_status(&user)),
+                ];
+                let fields: Vec<FieldChange> = fields
+                    .into_iter()
+                    .filter(|(_, before, after)| before != after)
+                    .map(|(field, before, after)| FieldChange { field, before, after })
+                    .collect();
+                if !fields.is_empty() {
+                    changes.changed.push(UserChange {
+                        id: user.id,
+                        name: user.name,
+                        changes: fields,
+                    });
+                }
+            }
+        }
+    }
+    // Whoever is left in the snapshot wasn't listed this time
+    changes.removed.extend(before.into_values());
+
+    changes.added.sort_by(|a, b| a.name.cmp(&b.name));
+    changes.removed.sort_by(|a, b| a.name.cmp(&b.name));
+    changes.changed.sort_by(|a, b| a.name.cmp(&b.name));
+    changes
+}
+
+pub fn format_user_changes(changes: &UserChanges, writer: &mut ColorWriter) -> Result<()> {
+    writer.print_header(&format!(
+        "User changes: {} added, {} removed, {} changed",
+        changes.added.len(),
+        changes.removed.len(),
+        changes.changed.len()
+    ))?;
+    writer.print_separator()?;
+
+    if changes.is_empty() {
+        writer.write("No changes since the cached snapshot")?;
+        writer.writeln()?;
+        return Ok(());
+    }
+
+    for user in &changes.added {
+        writer.print_colored("+ ", Color::Green)?;
+        write_user_name(user, writer)?;
+        writer.writeln()?;
+    }
+    for user in &changes.removed {
+        writer.print_colored("- ", Color::Red)?;
+        write_user_name(user, writer)?;
+        if user.deleted {
+            writer.write(" (deactivated)")?;
+        } else {
+            writer.write(" (no longer listed)")?;
+        }
+        writer.writeln()?;
+    }
+    for change in &changes.changed {
+        writer.print_colored("~ ", Color::Yellow)?;
+        if writer.options().id_format.shows_name() {
+            writer.write("@")?;
+            writer.print_bold(&change.name)?;
+        }
+        writer.print_id(&change.id)?;
+        writer.writeln()?;
+        for field in &change.changes {
+            writer.write(&format!(
+                "    {}: {} {} {}",
+                field.field,
+                field.before.as_deref().unwrap_or("(none)"),
+                writer.glyphs().arrow,
+                field.after.as_deref().unwrap_or("(none)")
+            ))?;
+            writer.writeln()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Membership of two channels split three ways (`conversations members-diff`)
+#[derive(Debug, Serialize)]
+pub struct MemberDiff<T> {
+    pub only_a: Vec<T>,
+    pub only_b: Vec<T>,
+    pub both: Vec<T>,
+}
+
+impl MemberDiff<String> {
+    /// Split two member id lists; duplicates are dropped and each side comes back sorted
+    pub fn new(a: &[String], b: &[String]) -> Self {
+        let a: BTreeSet<&String> = a.iter().collect();
+        let b: BTreeSet<&String> = b.iter().collect();
+        MemberDiff {
+            only_a: a.difference(&b).map(|id| id.to_string()).collect(),
+            only_b: b.difference(&a).map(|id| id.to_string()).collect(),
+            both: a.intersection(&b).map(|id| id.to_string()).collect(),
+        }
+    }
+
+    /// All ids across the three sides, in order
+    pub fn ids(&self) -> impl Iterator<Item = &String> {
+        self.only_a.iter().chain(&self.only_b).chain(&self.both)
+    }
+
+    /// Swap ids for resolved users; ids missing from `users` are dropped.
+    /// Each side is sorted by name.
+    pub fn resolve(self, mut users: HashMap<String, User>) -> MemberDiff<User> {
+        let mut side = |ids: Vec<String>| {
+            let mut side: Vec<User> = ids.iter().filter_map(|id| users.remove(id)).collect();
+            side.sort_by(|a, b| a.name.cmp(&b.name));
+            side
+        };
+        MemberDiff {
+            only_a: side(self.only_a),
+            only_b: side(self.only_b),
+            both: side(self.both),
+        }
+    }
+}
+
+/// One `<side>\t<id>` line per member (`a`, `b` or `both`), for `--ids-only`
+pub fn format_member_diff_ids(diff: &MemberDiff<String>) -> String {
+    let mut output = String::new();
+    for (side, ids) in [("a", &diff.only_a), ("b", &diff.only_b), ("both", &diff.both)] {
+        for id in ids {
+            output.push_str(&format!("{}\t{}\n", side, id));
+        }
+    }
+    output
+}
+
+pub fn format_member_diff(diff: &MemberDiff<User>, a: &str, b: &str, writer: &mut ColorWriter) -> Result<()> {
+    writer.print_header(&format!(
+        "Members: {} only in {}, {} only in {}, {} in both",
+        diff.only_a.len(),
+        a,
+        diff.only_b.len(),
+        b,
+        diff.both.len()
+    ))?;
+    writer.print_separator()?;
+
+    let sections = [
+        (format!("Only in {}", a), &diff.only_a),
+        (format!("Only in {}", b), &diff.only_b),
+        ("In both".to_string(), &diff.both),
+    ];
+    for (i, (title, users)) in sections.iter().enumerate() {
+        if i > 0 {
+            writer.writeln()?;
+        }
+        writer.print_bold(&format!("{} ({})", title, users.len()))?;
+        writer.writeln()?;
+        if users.is_empty() {
+            writer.write("  (none)")?;
+            writer.writeln()?;
+        }
+        for user in users.iter() {
+            writer.write("  ")?;
+            write_user_name(user, writer)?;
+            writer.writeln()?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_user_name(user: &User, writer: &mut ColorWriter) -> Result<()> {
+    if !writer.options().id_format.shows_name() {
+        return writer.print_id(&user.id);
+    }
+    writer.write("@")?;
+    writer.print_bold(&user.name)?;
+    writer.print_id(&user.id)?;
+    if let Some(real_name) = &user.real_name {
+        writer.write(&format!(" {}", real_name))?;
+    }
+    Ok(())
+}
+
 pub fn format_user(user: &User, writer: &mut ColorWriter) -> Result<()> {
     writer.print_header(&format!("User: {}", user.name))?;
     writer.print_separator()?;
 
     // Basic info
-    writer.print_field("User ID", &format!("@{} ({})", user.name, user.id))?;
+    writer.print_field("User ID", &writer.options().id_format.label(&format!("@{}", user.name), &user.id))?;
 
     if let Some(real_name) = &user.real_name {
         writer.print_field("Real Name", real_name)?;
@@ -54,9 +297,31 @@ pub fn format_user(user: &User, writer: &mut ColorWriter) -> Result<()> {
         writer.print_field("Flags", &flags.join(", "))?;
     }
 
-    // Profile URL - note: team ID would need to be fetched separately in real implementation
-    let profile_url = format!("https://slack.com/app_redirect?channel={}", user.id);
-    writer.print_field("Profile URL", &profile_url)?;
+    // Enterprise Grid org membership
+    if let Some(enterprise) = &user.enterprise_user {
+        let org = match &enterprise.enterprise_name {
+            Some(name) => format!("{} ({})", name, enterprise.enterprise_id),
+            None => enterprise.enterprise_id.clone(),
+        };
+        writer.print_field("Organization", &org)?;
+
+        let org_flags: Vec<&str> = [
+            (enterprise.is_primary_owner, "Org Primary Owner"),
+            (enterprise.is_owner && !enterprise.is_primary_owner, "Org Owner"),
+            (enterprise.is_admin, "Org Admin"),
+        ]
+        .into_iter()
+        .filter_map(|(set, label)| set.then_some(label))
+        .collect();
+        if !org_flags.is_empty() {
+            writer.print_field("Org Roles", &org_flags.join(", "))?;
+        }
+        if !enterprise.teams.is_empty() {
+            writer.print_field("Workspaces", &enterprise.teams.join(", "))?;
+        }
+    }
+
+    writer.print_field("Profile URL", &user.profile_url())?;
 
     Ok(())
 }
@@ -65,21 +330,35 @@ pub fn format_users_list(users: &[User], writer: &mut ColorWriter) -> Result<()>
     writer.print_header(&format!("Users ({})", users.len()))?;
     writer.print_separator()?;
 
+    if users.is_empty() {
+        return writer.print_empty("users");
+    }
+
     for (i, user) in users.iter().enumerate() {
-        // Name in bold with @ prefix, then ID in parentheses
-        writer.write("@")?;
-        writer.print_bold(&user.name)?;
-        writer.write(" ")?;
-        writer.print_colored(&format!("({})", user.id), Color::Yellow)?;
+        // Availability dot, only when presence was fetched (--presence)
+        match user.presence.as_deref() {
+            Some("active") => writer.print_colored(&format!("{} ", writer.glyphs().active), Color::Green)?,
+            Some(_) => writer.print_colored(&format!("{} ", writer.glyphs().away), Color::White)?,
+            None => {}
+        }
+
+        // Name in bold with @ prefix, then ID in parentheses (--id-format)
+        let shows_name = writer.options().id_format.shows_name();
+        if shows_name {
+            writer.write("@")?;
+            writer.print_bold(&user.name)?;
+        }
+        writer.print_id(&user.id)?;
 
         // Real name in parentheses
-        if let Some(real_name) = &user.real_name {
+        if let Some(real_name) = user.real_name.as_ref().filter(|_| shows_name) {
             writer.write(&format!(" ({})", real_name))?;
         }
 
-        // Status emoji if present
-        if let Some(emoji) = &user.profile.status_emoji {
-            writer.write(&format!(" {}", emoji))?;
+        // Status emoji and text if present
+        if let Some(status) = user_status(user) {
+            writer.write(" ")?;
+            writer.print_colored(&status, Color::Magenta)?;
         }
 
         writer.writeln()?;
@@ -87,7 +366,7 @@ pub fn format_users_list(users: &[User], writer: &mut ColorWriter) -> Result<()>
         // Email on second line if present
         if let Some(email) = &user.profile.email {
             writer.write("  ")?;
-            writer.print_colored("✉", Color::Blue)?;
+            writer.print_colored(writer.glyphs().email, Color::Blue)?;
             writer.write(&format!(" {}", email))?;
             writer.writeln()?;
         }
@@ -101,6 +380,31 @@ pub fn format_users_list(users: &[User], writer: &mut ColorWriter) -> Result<()>
     Ok(())
 }
 
+/// Print every field of a raw profile, including custom fields, in key order;
+/// null and empty values are skipped so the listing stays readable
+pub fn format_profile_all_fields(profile: &serde_json::Value, writer: &mut ColorWriter) -> Result<()> {
+    writer.print_header("User Profile")?;
+    writer.print_separator()?;
+
+    let Some(fields) = profile.as_object() else {
+        return Ok(());
+    };
+
+    let mut keys: Vec<&String> = fields.keys().collect();
+    keys.sort();
+    for key in keys {
+        let value = match &fields[key] {
+            serde_json::Value::Null => continue,
+            serde_json::Value::String(s) if s.is_empty() => continue,
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        writer.print_field(key, &value)?;
+    }
+
+    Ok(())
+}
+
 pub fn format_profile(profile: &UserProfile, writer: &mut ColorWriter) -> Result<()> {
     writer.print_header("User Profile")?;
     writer.print_separator()?;
@@ -130,3 +434,151 @@ pub fn format_profile(profile: &UserProfile, writer: &mut ColorWriter) -> Result
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::color::IdFormat;
+    use crate::output::render::RenderOptions;
+
+    #[test]
+    fn test_format_users_list_status_and_presence() {
+        let mut user: User = serde_json::from_value(serde_json::json!({
+            "id": "U1", "name": "alice", "deleted": false, "is_bot": false,
+            "profile": {"status_emoji": ":palm_tree:", "status_text": "On vacation"}
+        }))
+        .unwrap();
+
+        let mut writer = ColorWriter::new(true);
+        format_users_list(std::slice::from_ref(&user), &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("@alice (U1) :palm_tree: On vacation"));
+        assert!(!output.contains('●'));
+
+        user.presence = Some("active".to_string());
+        let mut writer = ColorWriter::new(true);
+        format_users_list(&[user], &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().contains("● @alice"));
+    }
+
+    #[test]
+    fn test_format_users_list_id_format() {
+        let users = vec![user("U1", "alice", "alice@example.com", false)];
+        let render = |id_format| {
+            let mut writer = ColorWriter::new(true).with_options(RenderOptions { id_format, ..Default::default() });
+            format_users_list(&users, &mut writer).unwrap();
+            writer.into_string().unwrap()
+        };
+
+        assert!(render(IdFormat::Show).contains("@alice (U1)"));
+        let hidden = render(IdFormat::Hide);
+        assert!(hidden.contains("@alice") && !hidden.contains("U1"));
+        let only = render(IdFormat::Only);
+        assert!(only.contains("\nU1") && !only.contains("@alice"));
+    }
+
+    #[test]
+    fn test_format_empty_users_list() {
+        let mut writer = ColorWriter::new(true);
+        format_users_list(&[], &mut writer).unwrap();
+        assert!(writer.into_string().unwrap().ends_with("No users found\n"));
+    }
+
+    #[test]
+    fn test_format_user_enterprise_fields() {
+        let mut user: User = serde_json::from_value(serde_json::json!({
+            "id": "W1", "name": "alice", "deleted": false, "is_bot": false, "profile": {},
+            "enterprise_user": {
+                "id": "W1", "enterprise_id": "E123", "enterprise_name": "Acme",
+                "is_admin": true, "is_owner": false, "teams": ["T1", "T2"]
+            }
+        }))
+        .unwrap();
+
+        let mut writer = ColorWriter::new(true);
+        format_user(&user, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("Organization: Acme (E123)"));
+        assert!(output.contains("Org Roles: Org Admin"));
+        assert!(output.contains("Workspaces: T1, T2"));
+
+        // Non-Grid workspaces: nothing extra, and nothing new in json
+        user.enterprise_user = None;
+        let mut writer = ColorWriter::new(true);
+        format_user(&user, &mut writer).unwrap();
+        assert!(!writer.into_string().unwrap().contains("Organization"));
+        assert!(!serde_json::to_string(&user).unwrap().contains("enterprise"));
+    }
+
+    fn user(id: &str, name: &str, email: &str, deleted: bool) -> User {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "name": name, "deleted": deleted, "is_bot": false,
+            "profile": {"email": email}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diff_users() {
+        let cached = vec![
+            user("U1", "alice", "alice@example.com", false),
+            user("U2", "bob", "bob@example.com", false),
+            user("U3", "carol", "carol@example.com", false),
+            user("U4", "dave", "dave@example.com", false),
+        ];
+        let fresh = vec![
+            user("U1", "alice", "alice@example.com", false),
+            user("U2", "bobby", "bob@example.org", false),
+            user("U3", "carol", "carol@example.com", true),
+            user("U5", "erin", "erin@example.com", false),
+            // Deactivated before the snapshot was ever taken: not news
+            user("U6", "frank", "frank@example.com", true),
+        ];
+
+        let changes = diff_users(cached, fresh);
+        assert_eq!(changes.added.iter().map(|u| u.id.as_str()).collect::<Vec<_>>(), ["U5"]);
+        assert_eq!(changes.removed.iter().map(|u| u.id.as_str()).collect::<Vec<_>>(), ["U3", "U4"]);
+        assert_eq!(changes.changed.len(), 1);
+        let fields: Vec<_> = changes.changed[0].changes.iter().map(|c| c.field).collect();
+        assert_eq!(fields, ["name", "email"]);
+
+        let mut writer = ColorWriter::new(true);
+        format_user_changes(&changes, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("1 added, 2 removed, 1 changed"));
+        assert!(output.contains("- @carol (U3) (deactivated)"));
+        assert!(output.contains("- @dave (U4) (no longer listed)"));
+        assert!(output.contains("    email: bob@example.com → bob@example.org"));
+    }
+
+    #[test]
+    fn test_member_diff() {
+        let a = ["U1", "U2", "U3", "U2"].map(String::from);
+        let b = ["U3", "U4", "U1"].map(String::from);
+        let diff = MemberDiff::new(&a, &b);
+        assert_eq!(diff.only_a, ["U2"]);
+        assert_eq!(diff.only_b, ["U4"]);
+        assert_eq!(diff.both, ["U1", "U3"]);
+        assert_eq!(format_member_diff_ids(&diff), "a\tU2\nb\tU4\nboth\tU1\nboth\tU3\n");
+
+        // U4 failed to resolve
+        let users: HashMap<String, User> = [
+            user("U1", "zed", "", false),
+            user("U2", "bob", "", false),
+            user("U3", "alice", "", false),
+        ]
+        .into_iter()
+        .map(|u| (u.id.clone(), u))
+        .collect();
+        let diff = diff.resolve(users);
+        assert!(diff.only_b.is_empty());
+        assert_eq!(diff.both.iter().map(|u| u.name.as_str()).collect::<Vec<_>>(), ["alice", "zed"]);
+
+        let mut writer = ColorWriter::new(true);
+        format_member_diff(&diff, "#eng", "#eng-private", &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains("1 only in #eng, 0 only in #eng-private, 2 in both"));
+        assert!(output.contains("Only in #eng (1)\n  @bob (U2)"));
+        assert!(output.contains("Only in #eng-private (0)\n  (none)"));
+    }
+}
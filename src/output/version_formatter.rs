@@ -0,0 +1,73 @@
+use crate::output::color::ColorWriter;
+use serde::Serialize;
+use std::io::Result;
+
+/// What went into this binary, captured by build.rs (`clack version`)
+#[derive(Debug, Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_commit: &'static str,
+    /// RFC 3339, UTC
+    pub build_date: String,
+    pub rustc_version: &'static str,
+    pub features: Vec<&'static str>,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        let build_date = env!("CLACK_BUILD_TIMESTAMP")
+            .parse::<i64>()
+            .ok()
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        BuildInfo {
+            version: env!("CARGO_PKG_VERSION"),
+            git_commit: env!("CLACK_GIT_SHA"),
+            build_date,
+            rustc_version: env!("CLACK_RUSTC_VERSION"),
+            features: env!("CLACK_FEATURES").split(',').filter(|f| !f.is_empty()).collect(),
+        }
+    }
+}
+
+pub fn format_build_info(info: &BuildInfo, writer: &mut ColorWriter) -> Result<()> {
+    writer.print_header(&format!("clack {}", info.version))?;
+    writer.print_separator()?;
+
+    writer.print_field("Commit", info.git_commit)?;
+    writer.print_field("Built", &info.build_date)?;
+    writer.print_field("Rustc", info.rustc_version)?;
+    let features = if info.features.is_empty() {
+        "(none)".to_string()
+    } else {
+        info.features.join(", ")
+    };
+    writer.print_field("Features", &features)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_info_current() {
+        let info = BuildInfo::current();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(!info.git_commit.is_empty());
+        assert!(info.build_date.ends_with('Z'), "{}", info.build_date);
+        assert!(info.rustc_version.starts_with("rustc") || info.rustc_version == "unknown");
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert!(json["features"].is_array());
+
+        let mut writer = ColorWriter::new(true);
+        format_build_info(&info, &mut writer).unwrap();
+        let output = writer.into_string().unwrap();
+        assert!(output.contains(&format!("clack {}", info.version)));
+        assert!(output.contains("Commit"));
+    }
+}
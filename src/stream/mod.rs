@@ -1,24 +1,94 @@
 pub mod search;
 
+use crate::models::message::parse_ts;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-/// Set up a signal handler for graceful shutdown on Ctrl+C
+/// Maximum number of seen message keys written to a state file
+const MAX_PERSISTED_MESSAGES: usize = 1000;
+
+/// Shortest `--interval` accepted
+pub const MIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// search.messages is a Tier 2 method (about 20 calls a minute); polling faster than
+/// this will run into rate limits
+pub const RECOMMENDED_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Set up a signal handler for graceful shutdown on Ctrl+C.
+///
+/// The first Ctrl+C lets the current poll finish; a second one restores the terminal
+/// and exits immediately.
 pub fn setup_signal_handler() -> Arc<AtomicBool> {
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
 
     ctrlc::set_handler(move || {
-        eprintln!("\nStopping stream...");
-        r.store(false, Ordering::SeqCst);
+        if r.swap(false, Ordering::SeqCst) {
+            eprintln!("\nStopping stream... (Ctrl+C again to quit immediately)");
+        } else {
+            restore_terminal();
+            std::process::exit(130);
+        }
     })
     .expect("Error setting Ctrl-C handler");
 
     running
 }
 
+/// Write one batch of stream output and flush it, so nothing sits in a buffer if the
+/// stream is interrupted
+pub fn emit(output: &str) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(output.as_bytes())?;
+    stdout.flush()
+}
+
+/// Flush stdout and, on a terminal, reset colors in case a color sequence was cut short
+pub fn restore_terminal() {
+    let mut stdout = std::io::stdout().lock();
+    if atty::is(atty::Stream::Stdout) {
+        let _ = stdout.write_all(b"\x1b[0m");
+    }
+    let _ = stdout.flush();
+}
+
+/// `--prefix` template with `{channel}` and `{query}` filled in
+pub fn expand_prefix(template: &str, channel: &str, query: &str) -> String {
+    template.replace("{channel}", channel).replace("{query}", query)
+}
+
+/// Put `prefix` in front of every line of `text`, blank ones included, so each line
+/// of a merged log can be traced back to its stream
+pub fn prefix_lines(text: &str, prefix: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        out.push_str(prefix);
+        out.push_str(line);
+    }
+    out
+}
+
+/// Restores the terminal when dropped, covering both normal shutdown and early error returns
+pub struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// On-disk representation of the seen message set
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct PersistedState {
+    seen_messages: Vec<(String, String)>,
+}
+
 /// State for tracking seen messages and poll timing
 pub struct StreamState {
     /// Set of seen message keys (channel_id, ts) to avoid duplicates
@@ -29,15 +99,67 @@ pub struct StreamState {
 
     /// Poll interval
     interval: Duration,
+
+    /// Optional file the seen set is persisted to between runs
+    state_file: Option<PathBuf>,
 }
 
 impl StreamState {
-    pub fn new(interval_secs: u64) -> Self {
+    pub fn new(interval: Duration) -> Self {
         Self {
             seen_messages: HashSet::new(),
             last_poll: Instant::now(),
-            interval: Duration::from_secs(interval_secs),
+            interval,
+            state_file: None,
+        }
+    }
+
+    /// Create stream state backed by a state file.
+    /// Previously seen messages are loaded from the file if it exists.
+    pub fn with_state_file(interval: Duration, state_file: &Path) -> Result<Self> {
+        let mut state = Self::new(interval);
+
+        if state_file.exists() {
+            let contents = std::fs::read_to_string(state_file).with_context(|| {
+                format!("Failed to read stream state file {}", state_file.display())
+            })?;
+            let persisted: PersistedState = serde_json::from_str(&contents).with_context(|| {
+                format!("Failed to parse stream state file {}", state_file.display())
+            })?;
+            state.seen_messages.extend(persisted.seen_messages);
         }
+
+        state.state_file = Some(state_file.to_path_buf());
+        Ok(state)
+    }
+
+    /// Number of message keys currently tracked
+    pub fn seen_count(&self) -> usize {
+        self.seen_messages.len()
+    }
+
+    /// Write the seen set to the state file (no-op without one).
+    /// Only the most recent messages (by ts) are kept, capped at MAX_PERSISTED_MESSAGES.
+    pub fn save(&self) -> Result<()> {
+        let Some(ref path) = self.state_file else {
+            return Ok(());
+        };
+
+        let mut seen: Vec<(String, String)> = self.seen_messages.iter().cloned().collect();
+        seen.sort_by_key(|(_, ts)| std::cmp::Reverse(parse_ts(ts).unwrap_or_default()));
+        seen.truncate(MAX_PERSISTED_MESSAGES);
+
+        let persisted = PersistedState { seen_messages: seen };
+        let contents = serde_json::to_string(&persisted)?;
+
+        // Write to a temporary file first so an interrupted save can't corrupt the state
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, contents)
+            .with_context(|| format!("Failed to write stream state file {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to write stream state file {}", path.display()))?;
+
+        Ok(())
     }
 
     /// Returns true if this message is new (not seen before)
@@ -61,9 +183,18 @@ impl StreamState {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_prefix_lines() {
+        let prefix = expand_prefix("[{channel} {query}] ", "deploys", "shipped");
+        assert_eq!(prefix, "[deploys shipped] ");
+        assert_eq!(prefix_lines("one\n\ntwo\n", "> "), "> one\n> \n> two\n");
+        assert_eq!(prefix_lines("{\"ts\":\"1\"}\n", "a: "), "a: {\"ts\":\"1\"}\n");
+        assert_eq!(prefix_lines("", "> "), "");
+    }
+
     #[test]
     fn test_stream_state_is_new() {
-        let mut state = StreamState::new(10);
+        let mut state = StreamState::new(Duration::from_secs(10));
 
         // First time seeing a message - should be new
         assert!(state.is_new("C123", "1234567890.123456"));
@@ -77,4 +208,47 @@ mod tests {
         // Same ts, different channel - should be new
         assert!(state.is_new("C456", "1234567890.123456"));
     }
+
+    #[test]
+    fn test_stream_state_file_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("stream-state.json");
+
+        let mut state = StreamState::with_state_file(Duration::from_secs(10), &path).unwrap();
+        assert_eq!(state.seen_count(), 0);
+        assert!(state.is_new("C123", "1234567890.123456"));
+        state.save().unwrap();
+
+        // A restarted stream should remember the message
+        let mut restored = StreamState::with_state_file(Duration::from_secs(10), &path).unwrap();
+        assert_eq!(restored.seen_count(), 1);
+        assert!(!restored.is_new("C123", "1234567890.123456"));
+        assert!(restored.is_new("C123", "1234567891.123456"));
+    }
+
+    #[test]
+    fn test_stream_state_file_keeps_most_recent() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("stream-state.json");
+
+        let mut state = StreamState::with_state_file(Duration::from_secs(10), &path).unwrap();
+        for i in 0..(MAX_PERSISTED_MESSAGES + 10) {
+            state.is_new("C123", &format!("{}.000000", 1700000000 + i));
+        }
+        state.save().unwrap();
+
+        let mut restored = StreamState::with_state_file(Duration::from_secs(10), &path).unwrap();
+        assert_eq!(restored.seen_count(), MAX_PERSISTED_MESSAGES);
+        // Oldest messages are dropped, newest are kept
+        assert!(restored.is_new("C123", "1700000000.000000"));
+        let newest = format!("{}.000000", 1700000000 + MAX_PERSISTED_MESSAGES + 9);
+        assert!(!restored.is_new("C123", &newest));
+    }
+
+    #[test]
+    fn test_stream_state_save_without_file_is_noop() {
+        let mut state = StreamState::new(Duration::from_secs(10));
+        state.is_new("C123", "1234567890.123456");
+        assert!(state.save().is_ok());
+    }
 }
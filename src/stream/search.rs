@@ -7,34 +7,61 @@ use crate::output::message_formatter::format_message_compact;
 use crate::output::search_formatter::format_search_message;
 use anyhow::Result;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tracing::debug;
 
-use super::{setup_signal_handler, StreamState};
+use super::{
+    emit, expand_prefix, prefix_lines, setup_signal_handler, StreamState, TerminalGuard, RECOMMENDED_POLL_INTERVAL,
+};
 
 /// Stream search messages continuously until interrupted
+#[allow(clippy::too_many_arguments)]
 pub async fn stream_search_messages(
     client: &SlackClient,
     query: &str,
-    interval_secs: u64,
+    interval: Duration,
     format: &str,
-    no_color: bool,
+    new_writer: impl Fn() -> ColorWriter,
+    state_file: Option<&Path>,
+    exclude_bots: bool,
+    prefix: Option<&str>,
+    skip_initial: bool,
 ) -> Result<()> {
     let running = setup_signal_handler();
-    let mut state = StreamState::new(interval_secs);
+    let _terminal = TerminalGuard;
+    let mut state = match state_file {
+        Some(path) => {
+            let state = StreamState::with_state_file(interval, path)?;
+            debug!(seen = state.seen_count(), path = %path.display(), "loaded stream state");
+            state
+        }
+        None => StreamState::new(interval),
+    };
+
+    if interval < RECOMMENDED_POLL_INTERVAL {
+        eprintln!(
+            "Warning: polling every {:?} is faster than search.messages' rate limit (about 20 calls a minute) sustains; use {}s or more to avoid waiting on rate limits",
+            interval,
+            RECOMMENDED_POLL_INTERVAL.as_secs()
+        );
+    }
 
     eprintln!(
         "Streaming messages matching '{}' (Ctrl+C to stop)...\n",
         query
     );
 
+    // --skip-initial: the first successful poll only seeds the seen set
+    let mut seeding = skip_initial;
+
     while running.load(Ordering::SeqCst) {
         // Fetch latest results
         let response = match search_messages(client, query, Some(20), Some(1)).await {
             Ok(r) => r,
             Err(e) => {
-                if client.verbose() {
-                    eprintln!("[STREAM] Error fetching results: {}", e);
-                }
+                debug!(error = %e, "stream poll failed");
                 state.wait_for_next_poll().await;
                 continue;
             }
@@ -43,11 +70,28 @@ pub async fn stream_search_messages(
         // Cache ALL fetched messages immediately (before filtering)
         cache_search_messages(client, &response.messages.matches).await;
 
+        if seeding {
+            seeding = false;
+            let skipped = response
+                .messages
+                .matches
+                .iter()
+                .filter(|msg| state.is_new(msg.channel.as_ref().map_or("unknown", |c| c.id()), &msg.ts))
+                .count();
+            debug!(skipped, "seeded stream state from the first poll");
+            if let Err(e) = state.save() {
+                eprintln!("Warning: Failed to save stream state: {}", e);
+            }
+            state.wait_for_next_poll().await;
+            continue;
+        }
+
         // Filter to only new messages (for display)
         let new_messages: Vec<_> = response
             .messages
             .matches
             .iter()
+            .filter(|msg| !(exclude_bots && msg.is_bot()))
             .filter(|msg| {
                 if let Some(ref channel) = msg.channel {
                     state.is_new(channel.id(), &msg.ts)
@@ -71,41 +115,47 @@ pub async fn stream_search_messages(
                 }
             }
 
-            // Output based on format
-            match format {
-                "json" => {
-                    for msg in &new_messages {
-                        println!("{}", serde_json::to_string(msg)?);
-                    }
-                }
-                "yaml" => {
-                    for msg in &new_messages {
-                        println!("{}", serde_yaml::to_string(msg)?);
-                    }
-                }
-                "human" => {
-                    let mut writer = ColorWriter::new(no_color);
-                    for msg in &new_messages {
+            // Output based on format, one message at a time so --prefix can name its channel
+            let mut batch = String::new();
+            for msg in &new_messages {
+                let output = match format {
+                    "json" => format!("{}\n", serde_json::to_string(msg)?),
+                    "yaml" => format!("{}\n", serde_yaml::to_string(msg)?),
+                    "human" => {
+                        let mut writer = new_writer();
                         format_search_message(msg, &user_map, &mut writer)?;
                         writer.writeln()?;
+                        writer.finish()?
                     }
-                    print!("{}", writer.into_string()?);
-                }
-                _ => {
-                    // "human-compact" is the default
-                    let mut writer = ColorWriter::new(no_color);
-                    for msg in &new_messages {
+                    _ => {
+                        // "human-compact" is the default
+                        let mut writer = new_writer();
                         format_message_compact(msg, &user_map, &mut writer)?;
+                        writer.finish()?
                     }
-                    print!("{}", writer.into_string()?);
+                };
+                match prefix {
+                    Some(template) => {
+                        let channel = msg.channel.as_ref().map(|c| c.name().unwrap_or(c.id())).unwrap_or("unknown");
+                        batch.push_str(&prefix_lines(&output, &expand_prefix(template, channel, query)));
+                    }
+                    None => batch.push_str(&output),
                 }
             }
+            emit(&batch)?;
+
+            // Persist periodically so a crash doesn't lose the dedup state
+            if let Err(e) = state.save() {
+                eprintln!("Warning: Failed to save stream state: {}", e);
+            }
         }
 
         // Wait for next poll
         state.wait_for_next_poll().await;
     }
 
+    state.save()?;
+
     eprintln!("Stream stopped.");
     Ok(())
 }
@@ -17,7 +17,7 @@ fn test_cache_db_initialization() {
     let db_path = temp_dir.path().join("test_cache.db");
 
     // Initialize the cache at the temp path
-    let result = init_cache_db_at_path(&db_path, true);
+    let result = init_cache_db_at_path(&db_path);
     assert!(result.is_ok(), "Failed to initialize cache: {:?}", result);
 
     // Verify database file was created